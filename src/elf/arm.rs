@@ -0,0 +1,124 @@
+//! Arm specific ELF data, such as the `.ARM.exidx` exception unwinding index and section flags
+//!
+//! See: "Exception Handling ABI for the Arm Architecture" (EHABI), section 6
+
+use scroll::{Pread, Endian};
+
+/// A section flag marking the section as containing only instructions, which may be mapped execute-only
+pub const SHF_ARM_PURECODE: u32 = 0x20000000;
+
+/// The size of an `.ARM.exidx` entry: two words
+pub const SIZEOF_EXIDX_ENTRY: usize = 8;
+
+/// The second word of an `.ARM.exidx` entry for a function which cannot be unwound
+pub const EXIDX_CANTUNWIND: u32 = 1;
+
+/// Decodes the signed 31-bit place-relative offset in the low bits of `word`, as used by `.ARM.exidx`
+#[inline]
+pub fn prel31(word: u32) -> i32 {
+    // shift the sign (bit 30) into bit 31, then arithmetic shift it back down
+    ((word << 1) as i32) >> 1
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How the function an `.ARM.exidx` entry covers is unwound
+pub enum ExidxEntry {
+    /// The function cannot be unwound (`EXIDX_CANTUNWIND`)
+    CantUnwind,
+    /// The unwinding instructions are inlined in the entry, in the compact model (high bit set)
+    Compact(u32),
+    /// The address of the generic model exception-handling table entry, usually in `.ARM.extab`, with PREL31 already applied
+    GenericOffset(u32),
+}
+
+/// Iterator over the entries of an `.ARM.exidx` section, yielding `(function_address, entry)`
+pub struct ExidxIterator<'a> {
+    data: &'a [u8],
+    addr: u64,
+    offset: usize,
+    le: Endian,
+}
+
+/// Returns an iterator over the entries in the `.ARM.exidx` section `bytes`, which is loaded at `sh_addr`;
+/// a trailing partial entry is ignored
+pub fn exidx<'a>(bytes: &'a [u8], sh_addr: u64, le: Endian) -> ExidxIterator<'a> {
+    ExidxIterator {
+        data: bytes,
+        addr: sh_addr,
+        offset: 0,
+        le: le,
+    }
+}
+
+impl<'a> Iterator for ExidxIterator<'a> {
+    type Item = (u64, ExidxEntry);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset + SIZEOF_EXIDX_ENTRY > self.data.len() {
+            return None;
+        }
+        let function = self.data.pread_with::<u32>(self.offset, self.le).ok()?;
+        let word = self.data.pread_with::<u32>(self.offset + 4, self.le).ok()?;
+        let entry_addr = self.addr.wrapping_add(self.offset as u64);
+        self.offset += SIZEOF_EXIDX_ENTRY;
+        let function_address = entry_addr.wrapping_add(prel31(function) as i64 as u64);
+        let entry = if word == EXIDX_CANTUNWIND {
+            ExidxEntry::CantUnwind
+        } else if word & 0x80000000 != 0 {
+            ExidxEntry::Compact(word)
+        } else {
+            // relative to the second word of the entry
+            let table = entry_addr.wrapping_add(4).wrapping_add(prel31(word) as i64 as u64);
+            ExidxEntry::GenericOffset(table as u32)
+        };
+        Some((function_address, entry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scroll::LE;
+
+    #[test]
+    fn prel31_sign_extension() {
+        assert_eq!(prel31(0x00000010), 0x10);
+        assert_eq!(prel31(0x3fffffff), 0x3fffffff);
+        assert_eq!(prel31(0x7fffffff), -1);
+        assert_eq!(prel31(0x7ffffff0), -0x10);
+        assert_eq!(prel31(0x40000000), -0x40000000);
+        // bit 31 is not part of the offset
+        assert_eq!(prel31(0x80000010), 0x10);
+        assert_eq!(prel31(0xfffffff0), -0x10);
+    }
+
+    #[test]
+    fn pure_code_sections() {
+        use elf::section_header::{SectionHeader, SHF_ALLOC, SHF_EXECINSTR};
+        let mut shdr = SectionHeader::new();
+        shdr.sh_flags = (SHF_ALLOC | SHF_EXECINSTR) as u64;
+        assert!(!shdr.is_arm_pure_code());
+        shdr.sh_flags |= SHF_ARM_PURECODE as u64;
+        assert!(shdr.is_arm_pure_code());
+        assert!(shdr.is_executable());
+    }
+
+    #[test]
+    fn exidx_entries() {
+        let bytes = [
+            // a function 0x100 bytes before the entry, which cannot be unwound
+            0x00, 0xff, 0xff, 0x7f, 0x01, 0x00, 0x00, 0x00,
+            // a function 0x20 bytes after, with compact unwinding instructions
+            0x20, 0x00, 0x00, 0x00, 0xb0, 0xb0, 0xa8, 0x80,
+            // a function 0x10 bytes before, with a table entry 0x40 bytes after the second word
+            0xf0, 0xff, 0xff, 0x7f, 0x40, 0x00, 0x00, 0x00,
+            // a partial entry
+            0x00, 0x00, 0x00, 0x00,
+        ];
+        let entries = exidx(&bytes, 0x8000, LE).collect::<Vec<_>>();
+        assert_eq!(entries, [
+            (0x7f00, ExidxEntry::CantUnwind),
+            (0x8028, ExidxEntry::Compact(0x80a8b0b0)),
+            (0x8000, ExidxEntry::GenericOffset(0x8054)),
+        ]);
+    }
+}
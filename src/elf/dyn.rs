@@ -545,7 +545,7 @@ macro_rules! elf_dyn_std_impl {
                 let mut needed = Vec::with_capacity(count);
                 for dyn in dyns {
                     if dyn.d_tag as u64 == DT_NEEDED {
-                        let lib = &(*strtab)[dyn.d_val as usize];
+                        let lib = &(&*strtab)[dyn.d_val as usize];
                         needed.push(lib);
                     }
                 }
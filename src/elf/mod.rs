@@ -50,6 +50,7 @@ pub mod sym;
 pub mod dyn;
 #[macro_use]
 pub mod reloc;
+pub mod arm;
 
 #[cfg(all(feature = "std", feature = "elf32", feature = "elf64", feature = "endian_fd"))]
 pub use self::impure::*;
@@ -374,6 +374,7 @@ mod std {
     use core::ops::Range;
     use scroll::ctx;
     use container::{Container, Ctx};
+    use elf::arm;
 
     #[derive(Default, PartialEq, Clone)]
     /// A unified SectionHeader - convertable to and from 32-bit and 64-bit variants
@@ -458,6 +459,11 @@ mod std {
         pub fn is_alloc(&self) -> bool {
             self.sh_flags as u32 & SHF_ALLOC == SHF_ALLOC
         }
+        /// Whether this Arm section is execute-only code (`SHF_ARM_PURECODE`); the flag is processor specific, so this
+        /// is only meaningful for `EM_ARM` binaries
+        pub fn is_arm_pure_code(&self) -> bool {
+            self.sh_flags as u32 & arm::SHF_ARM_PURECODE == arm::SHF_ARM_PURECODE
+        }
     }
 
     impl fmt::Debug for SectionHeader {
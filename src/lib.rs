@@ -153,6 +153,19 @@ pub mod container {
         pub fn new (container: Container, le: scroll::Endian) -> Self {
             Ctx { container: container, le: le }
         }
+        #[cfg(all(feature = "mach32", feature = "mach64", feature = "endian_fd"))]
+        /// The container size and endianness of a Mach-o binary whose magic is `magic`, read big endian as `mach::peek`
+        /// does (so a little endian binary's magic is `MH_CIGAM` or `MH_CIGAM_64`), or `None` if it isn't a Mach-o magic
+        pub fn from_mach_magic(magic: u32) -> Option<Self> {
+            use mach::header::{MH_MAGIC, MH_CIGAM, MH_MAGIC_64, MH_CIGAM_64};
+            match magic {
+                MH_MAGIC => Some(Ctx::new(Container::Little, scroll::BE)),
+                MH_CIGAM => Some(Ctx::new(Container::Little, scroll::LE)),
+                MH_MAGIC_64 => Some(Ctx::new(Container::Big, scroll::BE)),
+                MH_CIGAM_64 => Some(Ctx::new(Container::Big, scroll::LE)),
+                _ => None,
+            }
+        }
         /// Return a dubious pointer/address byte size for the container
         pub fn size(&self) -> usize {
             match self.container {
@@ -232,7 +245,7 @@ mod peek {
             use mach::{fat, header};
             let magic = mach::peek(bytes, 0)?;
             match magic {
-                fat::FAT_MAGIC => {
+                fat::FAT_MAGIC | fat::FAT_MAGIC_64 => {
                     // should probably verify this is always Big Endian...
                     let narchitectures = bytes.pread_with::<u32>(4, BE)? as usize;
                     Ok(Hint::MachFat(narchitectures))
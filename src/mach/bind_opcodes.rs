@@ -20,6 +20,7 @@ pub const BIND_TYPE_TEXT_PCREL32			: u8     = 3;
 pub const BIND_SPECIAL_DYLIB_SELF			: u8     = 0;
 pub const BIND_SPECIAL_DYLIB_MAIN_EXECUTABLE		: u8     = 0xf; // -1
 pub const BIND_SPECIAL_DYLIB_FLAT_LOOKUP		: u8     = 0xe; // -2
+pub const BIND_SPECIAL_DYLIB_WEAK_LOOKUP		: u8     = 0xd; // -3
 pub const BIND_SYMBOL_FLAGS_WEAK_IMPORT	                : u8   = 0x1;
 pub const BIND_SYMBOL_FLAGS_NON_WEAK_DEFINITION         : u8    = 0x8;
 pub const BIND_OPCODE_MASK				: u8	 = 0xF0;
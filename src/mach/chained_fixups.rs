@@ -0,0 +1,436 @@
+//! Chained fixups, which replace the `LC_DYLD_INFO` rebase and bind opcodes in recent binaries.
+//!
+//! The `LC_DYLD_CHAINED_FIXUPS` command points at a `dyld_chained_fixups_header` in `__LINKEDIT`, which is followed by
+//! the starts of the pointer chains in each segment, the table of imported symbols, and the pool of their names.
+
+use core::fmt::{self, Debug};
+use scroll::{self, Pread};
+
+use error;
+use mach::load_command;
+use mach::segment;
+
+/// The only `fixups_version` there is
+pub const DYLD_CHAINED_FIXUPS_VERSION: u32 = 0;
+
+/// The imports are `dyld_chained_import`s
+pub const DYLD_CHAINED_IMPORT: u32 = 1;
+/// The imports are `dyld_chained_import_addend`s
+pub const DYLD_CHAINED_IMPORT_ADDEND: u32 = 2;
+/// The imports are `dyld_chained_import_addend64`s
+pub const DYLD_CHAINED_IMPORT_ADDEND64: u32 = 3;
+
+pub const SIZEOF_DYLD_CHAINED_IMPORT: usize = 4;
+pub const SIZEOF_DYLD_CHAINED_IMPORT_ADDEND: usize = 8;
+pub const SIZEOF_DYLD_CHAINED_IMPORT_ADDEND64: usize = 16;
+
+/// `dyld_chained_ptr_arm64e`, stride 8, with vmaddr rebase targets
+pub const DYLD_CHAINED_PTR_ARM64E: u16 = 1;
+/// `dyld_chained_ptr_64`, stride 4, with vmaddr rebase targets
+pub const DYLD_CHAINED_PTR_64: u16 = 2;
+pub const DYLD_CHAINED_PTR_32: u16 = 3;
+pub const DYLD_CHAINED_PTR_32_CACHE: u16 = 4;
+pub const DYLD_CHAINED_PTR_32_FIRMWARE: u16 = 5;
+/// `dyld_chained_ptr_64`, stride 4, with vmoffset rebase targets
+pub const DYLD_CHAINED_PTR_64_OFFSET: u16 = 6;
+/// `dyld_chained_ptr_arm64e`, stride 4, with vmoffset rebase targets
+pub const DYLD_CHAINED_PTR_ARM64E_KERNEL: u16 = 7;
+pub const DYLD_CHAINED_PTR_64_KERNEL_CACHE: u16 = 8;
+/// `dyld_chained_ptr_arm64e`, stride 8, with vmoffset rebase targets
+pub const DYLD_CHAINED_PTR_ARM64E_USERLAND: u16 = 9;
+pub const DYLD_CHAINED_PTR_ARM64E_FIRMWARE: u16 = 10;
+pub const DYLD_CHAINED_PTR_X86_64_KERNEL_CACHE: u16 = 11;
+/// `dyld_chained_ptr_arm64e` with 24-bit bind ordinals, stride 8, with vmoffset rebase targets
+pub const DYLD_CHAINED_PTR_ARM64E_USERLAND24: u16 = 12;
+
+/// The page has no fixups
+pub const DYLD_CHAINED_PTR_START_NONE: u16 = 0xffff;
+
+pub fn pointer_format_to_str(format: u16) -> &'static str {
+    match format {
+        DYLD_CHAINED_PTR_ARM64E => "DYLD_CHAINED_PTR_ARM64E",
+        DYLD_CHAINED_PTR_64 => "DYLD_CHAINED_PTR_64",
+        DYLD_CHAINED_PTR_32 => "DYLD_CHAINED_PTR_32",
+        DYLD_CHAINED_PTR_32_CACHE => "DYLD_CHAINED_PTR_32_CACHE",
+        DYLD_CHAINED_PTR_32_FIRMWARE => "DYLD_CHAINED_PTR_32_FIRMWARE",
+        DYLD_CHAINED_PTR_64_OFFSET => "DYLD_CHAINED_PTR_64_OFFSET",
+        DYLD_CHAINED_PTR_ARM64E_KERNEL => "DYLD_CHAINED_PTR_ARM64E_KERNEL",
+        DYLD_CHAINED_PTR_64_KERNEL_CACHE => "DYLD_CHAINED_PTR_64_KERNEL_CACHE",
+        DYLD_CHAINED_PTR_ARM64E_USERLAND => "DYLD_CHAINED_PTR_ARM64E_USERLAND",
+        DYLD_CHAINED_PTR_ARM64E_FIRMWARE => "DYLD_CHAINED_PTR_ARM64E_FIRMWARE",
+        DYLD_CHAINED_PTR_X86_64_KERNEL_CACHE => "DYLD_CHAINED_PTR_X86_64_KERNEL_CACHE",
+        DYLD_CHAINED_PTR_ARM64E_USERLAND24 => "DYLD_CHAINED_PTR_ARM64E_USERLAND24",
+        _ => "DYLD_CHAINED_PTR_UNKNOWN",
+    }
+}
+
+/// The symbol names are uncompressed
+pub const DYLD_CHAINED_SYMBOL_UNCOMPRESSED: u32 = 0;
+/// The symbol names are zlib compressed
+pub const DYLD_CHAINED_SYMBOL_ZLIB: u32 = 1;
+
+pub fn imports_format_to_str(format: u32) -> &'static str {
+    match format {
+        DYLD_CHAINED_IMPORT => "DYLD_CHAINED_IMPORT",
+        DYLD_CHAINED_IMPORT_ADDEND => "DYLD_CHAINED_IMPORT_ADDEND",
+        DYLD_CHAINED_IMPORT_ADDEND64 => "DYLD_CHAINED_IMPORT_ADDEND64",
+        _ => "DYLD_CHAINED_IMPORT_UNKNOWN",
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+/// The header of the chained fixups data, `dyld_chained_fixups_header`; all offsets are from the start of this header
+pub struct DyldChainedFixupsHeader {
+    /// 0
+    pub fixups_version: u32,
+    /// offset of dyld_chained_starts_in_image in chain_data
+    pub starts_offset: u32,
+    /// offset of imports table in chain_data
+    pub imports_offset: u32,
+    /// offset of symbol strings in chain_data
+    pub symbols_offset: u32,
+    /// number of imported symbol names
+    pub imports_count: u32,
+    /// DYLD_CHAINED_IMPORT*
+    pub imports_format: u32,
+    /// 0 => uncompressed, 1 => zlib compressed
+    pub symbols_format: u32,
+}
+
+pub const SIZEOF_DYLD_CHAINED_FIXUPS_HEADER: usize = 28;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An entry of the imports table, in any of the `DYLD_CHAINED_IMPORT*` formats
+pub struct ChainedImport {
+    /// The (two-level namespace) library ordinal of the symbol; the special ordinals are negative, as for binds
+    pub lib_ordinal: i64,
+    /// Whether it is a weak import, i.e., may be missing at runtime
+    pub weak_import: bool,
+    /// The offset of the symbol's name in the symbols pool
+    pub name_offset: u32,
+    /// The addend, which is always 0 for `DYLD_CHAINED_IMPORT`
+    pub addend: i64,
+}
+
+impl ChainedImport {
+    /// Decodes the import at `offset` in `bytes` in the `DYLD_CHAINED_IMPORT*` `format`
+    pub fn parse(bytes: &[u8], offset: usize, format: u32, le: scroll::Endian) -> error::Result<Self> {
+        match format {
+            DYLD_CHAINED_IMPORT | DYLD_CHAINED_IMPORT_ADDEND => {
+                let import = bytes.pread_with::<u32>(offset, le)?;
+                let addend = if format == DYLD_CHAINED_IMPORT_ADDEND {
+                    bytes.pread_with::<i32>(offset + 4, le)? as i64
+                } else {
+                    0
+                };
+                // only the ordinals in the last few values are special, and sign extended
+                let lib_ordinal = import & 0xff;
+                Ok(ChainedImport {
+                    lib_ordinal: if lib_ordinal > 0xf0 { lib_ordinal as u8 as i8 as i64 } else { lib_ordinal as i64 },
+                    weak_import: import & 0x100 != 0,
+                    name_offset: import >> 9,
+                    addend: addend,
+                })
+            },
+            DYLD_CHAINED_IMPORT_ADDEND64 => {
+                let import = bytes.pread_with::<u64>(offset, le)?;
+                let lib_ordinal = import & 0xffff;
+                Ok(ChainedImport {
+                    lib_ordinal: if lib_ordinal > 0xfff0 { lib_ordinal as u16 as i16 as i64 } else { lib_ordinal as i64 },
+                    weak_import: import & 0x1_0000 != 0,
+                    name_offset: (import >> 32) as u32,
+                    addend: bytes.pread_with::<i64>(offset + 8, le)?,
+                })
+            },
+            _ => Err(error::Error::Malformed(format!("unknown chained imports format {}", format))),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+/// The header of the chain starts of a segment, `dyld_chained_starts_in_segment`, which is followed by `page_count`
+/// `u16` offsets of the first fixup in each page
+pub struct DyldChainedStartsInSegment {
+    /// size of this (amount kernel needs to copy)
+    pub size: u32,
+    /// 0x1000 or 0x4000
+    pub page_size: u16,
+    /// DYLD_CHAINED_PTR_*
+    pub pointer_format: u16,
+    /// offset in memory to start of segment
+    pub segment_offset: u64,
+    /// for 32-bit OS, any value beyond this is not a pointer
+    pub max_valid_pointer: u32,
+    /// how many pages are in array
+    pub page_count: u16,
+}
+
+pub const SIZEOF_DYLD_CHAINED_STARTS_IN_SEGMENT: usize = 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A pointer which is rebased by a chained fixup
+pub struct ChainedRebase {
+    /// The index of the segment the pointer is in
+    pub segment_index: usize,
+    /// The offset of the pointer from the start of its segment
+    pub segment_offset: u64,
+    /// The virtual memory address of the pointer
+    pub address: u64,
+    /// The (unslid) virtual memory address the pointer points to
+    pub target: u64,
+    /// Whether the pointer is signed with pointer authentication
+    pub is_auth: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A pointer which is bound to an imported symbol by a chained fixup
+pub struct ChainedBind<'a> {
+    /// The index of the segment the pointer is in
+    pub segment_index: usize,
+    /// The offset of the pointer from the start of its segment
+    pub segment_offset: u64,
+    /// The virtual memory address of the pointer
+    pub address: u64,
+    /// The symbol name dyld binds the pointer to
+    pub name: &'a str,
+    /// The (two-level namespace) library ordinal of the symbol; the special ordinals are negative, as for binds
+    pub library_ordinal: i64,
+    /// The addend of the pointer and its import
+    pub addend: i64,
+    /// Whether it is a weak import, i.e., may be missing at runtime
+    pub weak: bool,
+    /// Whether the pointer is signed with pointer authentication
+    pub is_auth: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Fixup {
+    /// the target, and whether it is an offset from the image base
+    Rebase { target: u64, is_offset: bool, is_auth: bool },
+    Bind { ordinal: usize, addend: i64, is_auth: bool },
+}
+
+/// Sign extends the low `bits` of `value`
+fn sign_extend(value: u64, bits: u32) -> i64 {
+    let shift = 64 - bits;
+    ((value << shift) as i64) >> shift
+}
+
+/// Decodes the `raw` chained pointer in format `pointer_format`, returning the fixup and the distance in strides to the next
+fn decode_pointer(raw: u64, pointer_format: u16) -> error::Result<(Fixup, u64)> {
+    match pointer_format {
+        DYLD_CHAINED_PTR_64 | DYLD_CHAINED_PTR_64_OFFSET => {
+            let next = (raw >> 51) & 0xfff;
+            let fixup = if raw >> 63 != 0 {
+                Fixup::Bind { ordinal: (raw & 0xff_ffff) as usize, addend: ((raw >> 24) & 0xff) as i64, is_auth: false }
+            } else {
+                let target = (raw & 0xf_ffff_ffff) | (((raw >> 36) & 0xff) << 56);
+                Fixup::Rebase { target: target, is_offset: pointer_format == DYLD_CHAINED_PTR_64_OFFSET, is_auth: false }
+            };
+            Ok((fixup, next))
+        },
+        DYLD_CHAINED_PTR_ARM64E | DYLD_CHAINED_PTR_ARM64E_KERNEL | DYLD_CHAINED_PTR_ARM64E_USERLAND | DYLD_CHAINED_PTR_ARM64E_USERLAND24 => {
+            let next = (raw >> 51) & 0x7ff;
+            let is_bind = (raw >> 62) & 1 != 0;
+            let is_auth = raw >> 63 != 0;
+            let ordinal = if pointer_format == DYLD_CHAINED_PTR_ARM64E_USERLAND24 { raw & 0xff_ffff } else { raw & 0xffff } as usize;
+            let fixup = match (is_bind, is_auth) {
+                (true, true) => Fixup::Bind { ordinal: ordinal, addend: 0, is_auth: true },
+                (true, false) => Fixup::Bind { ordinal: ordinal, addend: sign_extend((raw >> 32) & 0x7_ffff, 19), is_auth: false },
+                // authenticated rebase targets are always offsets from the image base
+                (false, true) => Fixup::Rebase { target: raw & 0xffff_ffff, is_offset: true, is_auth: true },
+                (false, false) => {
+                    let target = (raw & 0x7ff_ffff_ffff) | (((raw >> 43) & 0xff) << 56);
+                    Fixup::Rebase { target: target, is_offset: pointer_format != DYLD_CHAINED_PTR_ARM64E, is_auth: false }
+                },
+            };
+            Ok((fixup, next))
+        },
+        _ => Err(error::Error::Malformed(format!("unsupported chained pointer format {}", pointer_format_to_str(pointer_format)))),
+    }
+}
+
+/// The distance in bytes between the chained pointers of `pointer_format`
+fn stride(pointer_format: u16) -> u64 {
+    match pointer_format {
+        DYLD_CHAINED_PTR_ARM64E | DYLD_CHAINED_PTR_ARM64E_USERLAND | DYLD_CHAINED_PTR_ARM64E_USERLAND24 => 8,
+        _ => 4,
+    }
+}
+
+/// The virtual memory address of the pointer `segment_offset` into the `segment_index`-th of the `segments`
+fn address(segments: &[segment::Segment], segment_index: usize, segment_offset: u64) -> error::Result<u64> {
+    match segments[segment_index].vmaddr.checked_add(segment_offset) {
+        Some(address) => Ok(address),
+        None => Err(error::Error::Malformed(format!("chained fixup at {:#x} in segment {} overflows its address", segment_offset, segment_index))),
+    }
+}
+
+/// The chained fixups an `LC_DYLD_CHAINED_FIXUPS` command points to
+pub struct ChainedFixups<'a> {
+    pub header: DyldChainedFixupsHeader,
+    /// the chained fixups data, beginning with the header
+    data: &'a [u8],
+    le: scroll::Endian,
+}
+
+impl<'a> ChainedFixups<'a> {
+    /// Parses the header of the chained fixups the `LC_DYLD_CHAINED_FIXUPS` `command` points to in `bytes`
+    pub fn parse(bytes: &'a [u8], command: &load_command::LinkeditDataCommand, le: scroll::Endian) -> error::Result<Self> {
+        let start = command.dataoff as usize;
+        let end = start + command.datasize as usize;
+        let data = match bytes.get(start..end) {
+            Some(data) => data,
+            None => return Err(error::Error::Malformed(format!("chained fixups {:#x}..{:#x} are outside of the binary (size {:#x})", start, end, bytes.len()))),
+        };
+        let header = data.pread_with::<DyldChainedFixupsHeader>(0, le)?;
+        if header.fixups_version != DYLD_CHAINED_FIXUPS_VERSION {
+            return Err(error::Error::Malformed(format!("unknown chained fixups version {}", header.fixups_version)));
+        }
+        Ok(ChainedFixups {
+            header: header,
+            data: data,
+            le: le,
+        })
+    }
+    /// The size of each entry of the imports table
+    fn sizeof_import(&self) -> error::Result<usize> {
+        match self.header.imports_format {
+            DYLD_CHAINED_IMPORT => Ok(SIZEOF_DYLD_CHAINED_IMPORT),
+            DYLD_CHAINED_IMPORT_ADDEND => Ok(SIZEOF_DYLD_CHAINED_IMPORT_ADDEND),
+            DYLD_CHAINED_IMPORT_ADDEND64 => Ok(SIZEOF_DYLD_CHAINED_IMPORT_ADDEND64),
+            format => Err(error::Error::Malformed(format!("unknown chained imports format {}", format))),
+        }
+    }
+    /// Returns the import at `index` of the imports table
+    pub fn import(&self, index: usize) -> error::Result<ChainedImport> {
+        if index >= self.header.imports_count as usize {
+            return Err(error::Error::Malformed(format!("chained import {} is past the end of the {} imports", index, self.header.imports_count)));
+        }
+        let offset = self.header.imports_offset as usize + index * self.sizeof_import()?;
+        ChainedImport::parse(self.data, offset, self.header.imports_format, self.le)
+    }
+    /// Decodes the `imports_count` entries of the imports table
+    pub fn import_table(&self) -> error::Result<Vec<ChainedImport>> {
+        let count = self.header.imports_count as usize;
+        let size = self.sizeof_import()?;
+        // don't trust imports_count to preallocate
+        let mut imports = Vec::with_capacity(::std::cmp::min(count, self.data.len() / size));
+        for i in 0..count {
+            imports.push(self.import(i)?);
+        }
+        Ok(imports)
+    }
+    /// Returns the chain starts of the segment with `segment_index`, and the offsets of the first fixup in each of its pages,
+    /// or `None` if the segment has no fixups
+    pub fn starts_in_segment(&self, segment_index: usize) -> error::Result<Option<(DyldChainedStartsInSegment, Vec<u16>)>> {
+        let starts = self.header.starts_offset as usize;
+        let seg_count = self.data.pread_with::<u32>(starts, self.le)? as usize;
+        if segment_index >= seg_count {
+            return Ok(None);
+        }
+        let seg_info_offset = self.data.pread_with::<u32>(starts + 4 + segment_index * 4, self.le)? as usize;
+        if seg_info_offset == 0 {
+            return Ok(None);
+        }
+        let offset = &mut (starts + seg_info_offset);
+        let segment_starts = self.data.gread_with::<DyldChainedStartsInSegment>(offset, self.le)?;
+        let mut page_starts = Vec::with_capacity(::std::cmp::min(segment_starts.page_count as usize, self.data.len() / 2));
+        for _ in 0..segment_starts.page_count {
+            page_starts.push(self.data.gread_with::<u16>(offset, self.le)?);
+        }
+        Ok(Some((segment_starts, page_starts)))
+    }
+    /// Follows the pointer chains of every page of the `segments` (of the binary these fixups belong to)
+    fn fixups(&self, segments: &[segment::Segment]) -> error::Result<Vec<(usize, u64, Fixup)>> {
+        let mut fixups = Vec::new();
+        for (segment_index, segment) in segments.iter().enumerate() {
+            let (starts, page_starts) = match self.starts_in_segment(segment_index)? {
+                Some(starts) => starts,
+                None => continue,
+            };
+            let page_size = starts.page_size as u64;
+            let stride = stride(starts.pointer_format);
+            for (page_index, &page_start) in page_starts.iter().enumerate() {
+                if page_start == DYLD_CHAINED_PTR_START_NONE {
+                    continue;
+                }
+                let mut offset_in_page = page_start as u64;
+                loop {
+                    if offset_in_page >= page_size {
+                        return Err(error::Error::Malformed(format!("chained fixup at {:#x} is past the end of page {} of segment {}", offset_in_page, page_index, segment_index)));
+                    }
+                    let segment_offset = page_index as u64 * page_size + offset_in_page;
+                    // only the 64-bit formats are supported, which decode_pointer checks
+                    let raw = segment.data.pread_with::<u64>(segment_offset as usize, self.le)?;
+                    let (fixup, next) = decode_pointer(raw, starts.pointer_format)?;
+                    fixups.push((segment_index, segment_offset, fixup));
+                    if next == 0 {
+                        break;
+                    }
+                    offset_in_page += next * stride;
+                }
+            }
+        }
+        Ok(fixups)
+    }
+    /// Follows the pointer chains in the `segments` (of the binary these fixups belong to) for the pointers which dyld rebases
+    pub fn rebases(&self, segments: &[segment::Segment]) -> error::Result<Vec<ChainedRebase>> {
+        // the image base is the address of the mach header, i.e., of the segment which maps the start of the file
+        let base = segments.iter().find(|segment| segment.fileoff == 0 && segment.filesize != 0).map(|segment| segment.vmaddr).unwrap_or(0);
+        let mut rebases = Vec::new();
+        for (segment_index, segment_offset, fixup) in self.fixups(segments)? {
+            if let Fixup::Rebase { target, is_offset, is_auth } = fixup {
+                rebases.push(ChainedRebase {
+                    segment_index: segment_index,
+                    segment_offset: segment_offset,
+                    address: address(segments, segment_index, segment_offset)?,
+                    target: if is_offset { base.wrapping_add(target) } else { target },
+                    is_auth: is_auth,
+                });
+            }
+        }
+        Ok(rebases)
+    }
+    /// Follows the pointer chains in the `segments` (of the binary these fixups belong to) for the pointers which dyld binds,
+    /// resolving them through the imports table and the symbols pool
+    pub fn imports(&self, segments: &[segment::Segment]) -> error::Result<Vec<ChainedBind<'a>>> {
+        let mut binds = Vec::new();
+        for (segment_index, segment_offset, fixup) in self.fixups(segments)? {
+            if let Fixup::Bind { ordinal, addend, is_auth } = fixup {
+                let import = self.import(ordinal)?;
+                binds.push(ChainedBind {
+                    segment_index: segment_index,
+                    segment_offset: segment_offset,
+                    address: address(segments, segment_index, segment_offset)?,
+                    name: self.symbol_name(import.name_offset)?,
+                    library_ordinal: import.lib_ordinal,
+                    addend: addend.wrapping_add(import.addend),
+                    weak: import.weak_import,
+                    is_auth: is_auth,
+                });
+            }
+        }
+        Ok(binds)
+    }
+    /// Returns the symbol name at `name_offset` in the symbols pool
+    pub fn symbol_name(&self, name_offset: u32) -> error::Result<&'a str> {
+        if self.header.symbols_format != DYLD_CHAINED_SYMBOL_UNCOMPRESSED {
+            return Err(error::Error::Malformed(format!("compressed chained fixups symbols (format {}) are unsupported", self.header.symbols_format)));
+        }
+        let offset = self.header.symbols_offset as usize + name_offset as usize;
+        Ok(self.data.pread::<&str>(offset)?)
+    }
+}
+
+impl<'a> Debug for ChainedFixups<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("ChainedFixups")
+            .field("header", &self.header)
+            .field("imports_format", &imports_format_to_str(self.header.imports_format))
+            .field("size", &self.data.len())
+            .finish()
+    }
+}
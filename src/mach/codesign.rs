@@ -0,0 +1,319 @@
+//! The embedded code signature an `LC_CODE_SIGNATURE` load command points to
+//!
+//! **Note**: every multi-byte field of a code signature is big-endian, regardless of the endianness of the mach-o binary
+//! it is embedded in, so nothing in this module takes a `container::Ctx`.
+
+use core::fmt::{self, Debug};
+use scroll::{self, Pread};
+
+use error;
+
+// Magic numbers of the code signing blobs
+/// Single requirement blob
+pub const CSMAGIC_REQUIREMENT: u32 = 0xfade0c00;
+/// Requirements vector (internal requirements)
+pub const CSMAGIC_REQUIREMENTS: u32 = 0xfade0c01;
+/// CodeDirectory blob
+pub const CSMAGIC_CODEDIRECTORY: u32 = 0xfade0c02;
+/// Embedded form of signature data
+pub const CSMAGIC_EMBEDDED_SIGNATURE: u32 = 0xfade0cc0;
+/// Multi-arch collection of embedded signatures
+pub const CSMAGIC_DETACHED_SIGNATURE: u32 = 0xfade0cc1;
+/// CMS signature, among other things
+pub const CSMAGIC_BLOBWRAPPER: u32 = 0xfade0b01;
+/// Embedded entitlements
+pub const CSMAGIC_EMBEDDED_ENTITLEMENTS: u32 = 0xfade7171;
+
+// Slot types of the blob index entries of a SuperBlob
+pub const CSSLOT_CODEDIRECTORY: u32 = 0;
+pub const CSSLOT_INFOSLOT: u32 = 1;
+pub const CSSLOT_REQUIREMENTS: u32 = 2;
+pub const CSSLOT_RESOURCEDIR: u32 = 3;
+pub const CSSLOT_APPLICATION: u32 = 4;
+pub const CSSLOT_ENTITLEMENTS: u32 = 5;
+/// The first of the alternate code directories, used when a binary is signed with several hash types
+pub const CSSLOT_ALTERNATE_CODEDIRECTORIES: u32 = 0x1000;
+/// CMS signature
+pub const CSSLOT_SIGNATURESLOT: u32 = 0x10000;
+
+// Hash types of a CodeDirectory
+pub const CS_HASHTYPE_SHA1: u8 = 1;
+pub const CS_HASHTYPE_SHA256: u8 = 2;
+pub const CS_HASHTYPE_SHA256_TRUNCATED: u8 = 3;
+pub const CS_HASHTYPE_SHA384: u8 = 4;
+
+pub fn magic_to_str(magic: u32) -> &'static str {
+    match magic {
+        CSMAGIC_REQUIREMENT => "CSMAGIC_REQUIREMENT",
+        CSMAGIC_REQUIREMENTS => "CSMAGIC_REQUIREMENTS",
+        CSMAGIC_CODEDIRECTORY => "CSMAGIC_CODEDIRECTORY",
+        CSMAGIC_EMBEDDED_SIGNATURE => "CSMAGIC_EMBEDDED_SIGNATURE",
+        CSMAGIC_DETACHED_SIGNATURE => "CSMAGIC_DETACHED_SIGNATURE",
+        CSMAGIC_BLOBWRAPPER => "CSMAGIC_BLOBWRAPPER",
+        CSMAGIC_EMBEDDED_ENTITLEMENTS => "CSMAGIC_EMBEDDED_ENTITLEMENTS",
+        _ => "UNKNOWN CSMAGIC",
+    }
+}
+
+pub fn hash_type_to_str(hash_type: u8) -> &'static str {
+    match hash_type {
+        CS_HASHTYPE_SHA1 => "CS_HASHTYPE_SHA1",
+        CS_HASHTYPE_SHA256 => "CS_HASHTYPE_SHA256",
+        CS_HASHTYPE_SHA256_TRUNCATED => "CS_HASHTYPE_SHA256_TRUNCATED",
+        CS_HASHTYPE_SHA384 => "CS_HASHTYPE_SHA384",
+        _ => "UNKNOWN CS_HASHTYPE",
+    }
+}
+
+/// The header which begins every code signing blob
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct BlobHeader {
+    /// The `CSMAGIC_*` of the blob
+    pub magic: u32,
+    /// The total length of the blob, including this header
+    pub length: u32,
+}
+
+pub const SIZEOF_BLOB_HEADER: usize = 8;
+
+/// The header of a `CS_SuperBlob`, which is followed by `count` `BlobIndex`es
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct SuperBlobHeader {
+    /// `CSMAGIC_EMBEDDED_SIGNATURE`
+    pub magic: u32,
+    /// The total length of the SuperBlob
+    pub length: u32,
+    /// The number of blob index entries following this header
+    pub count: u32,
+}
+
+pub const SIZEOF_SUPER_BLOB_HEADER: usize = 12;
+
+/// An entry of a SuperBlob's index
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct BlobIndex {
+    /// The `CSSLOT_*` type of the blob
+    pub typ: u32,
+    /// The offset of the blob from the start of the SuperBlob
+    pub offset: u32,
+}
+
+pub const SIZEOF_BLOB_INDEX: usize = 8;
+
+/// The fixed header of a `CS_CodeDirectory`, common to all of its versions
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct CodeDirectoryHeader {
+    /// `CSMAGIC_CODEDIRECTORY`
+    pub magic: u32,
+    /// The total length of the CodeDirectory blob
+    pub length: u32,
+    /// The compatibility version
+    pub version: u32,
+    /// The setup and mode flags
+    pub flags: u32,
+    /// The offset of the hash slot with index zero
+    pub hash_offset: u32,
+    /// The offset of the NUL terminated identifier string
+    pub ident_offset: u32,
+    /// The number of special hash slots, which precede `hash_offset`
+    pub n_special_slots: u32,
+    /// The number of ordinary (code) hash slots
+    pub n_code_slots: u32,
+    /// Limit to main image signature range
+    pub code_limit: u32,
+    /// The size of each hash in bytes
+    pub hash_size: u8,
+    /// The `CS_HASHTYPE_*` of the hashes
+    pub hash_type: u8,
+    /// The platform identifier; zero if not a platform binary
+    pub platform: u8,
+    /// log2 of the page size in bytes; zero means unlimited
+    pub page_size: u8,
+    /// Unused, must be zero
+    pub spare2: u32,
+}
+
+pub const SIZEOF_CODE_DIRECTORY_HEADER: usize = 44;
+
+/// A parsed `CS_CodeDirectory`, which holds the hashes of each page of the signed code
+pub struct CodeDirectory<'a> {
+    pub header: CodeDirectoryHeader,
+    /// The signing identifier
+    pub identifier: &'a str,
+    /// The hashes of each page of the code, in order
+    pub code_hashes: Vec<&'a [u8]>,
+    /// The special slot hashes; `special_hashes[i]` is the hash of slot `-(i + 1)`, e.g., of the `CSSLOT_REQUIREMENTS` blob for `i == 1`
+    pub special_hashes: Vec<&'a [u8]>,
+}
+
+impl<'a> CodeDirectory<'a> {
+    /// Parses a CodeDirectory from the bytes of its blob
+    pub fn parse(bytes: &'a [u8]) -> error::Result<Self> {
+        let header: CodeDirectoryHeader = bytes.pread_with(0, scroll::BE)?;
+        if header.magic != CSMAGIC_CODEDIRECTORY {
+            return Err(error::Error::BadMagic(header.magic as u64));
+        }
+        let identifier = bytes.pread::<&str>(header.ident_offset as usize)?;
+        let hash_size = header.hash_size as usize;
+        let hash_offset = header.hash_offset as usize;
+        let nspecial = header.n_special_slots as usize;
+        let ncode = header.n_code_slots as usize;
+        // empty hashes take up no room in the blob, so their counts would be unbounded
+        if hash_size == 0 && (nspecial != 0 || ncode != 0) {
+            return Err(error::Error::Malformed(format!("CodeDirectory has {} special and {} code slots of size 0", nspecial, ncode)));
+        }
+        if nspecial.saturating_mul(hash_size) > hash_offset {
+            return Err(error::Error::Malformed(format!("CodeDirectory has {} special slots of size {} before hash offset {:#x}", nspecial, hash_size, hash_offset)));
+        }
+        let hash_at = |offset: usize| -> error::Result<&'a [u8]> {
+            match bytes.get(offset..offset + hash_size) {
+                Some(hash) => Ok(hash),
+                None => Err(error::Error::Malformed(format!("CodeDirectory hash at {:#x} (size {}) is outside of the blob (length {:#x})", offset, hash_size, bytes.len()))),
+            }
+        };
+        let mut special_hashes = Vec::with_capacity(nspecial);
+        for i in 1..nspecial + 1 {
+            special_hashes.push(hash_at(hash_offset - i * hash_size)?);
+        }
+        if hash_offset.saturating_add(ncode.saturating_mul(hash_size)) > bytes.len() {
+            return Err(error::Error::Malformed(format!("CodeDirectory has {} code slots of size {} which do not fit in the blob (length {:#x})", ncode, hash_size, bytes.len())));
+        }
+        let mut code_hashes = Vec::with_capacity(ncode);
+        for i in 0..ncode {
+            code_hashes.push(hash_at(hash_offset + i * hash_size)?);
+        }
+        Ok(CodeDirectory {
+            header: header,
+            identifier: identifier,
+            code_hashes: code_hashes,
+            special_hashes: special_hashes,
+        })
+    }
+    /// The `CS_HASHTYPE_*` of this directory's hashes
+    pub fn hash_type(&self) -> u8 {
+        self.header.hash_type
+    }
+    /// The size in bytes of the pages each code hash covers, or `None` if a single hash covers the whole code
+    pub fn page_size(&self) -> Option<usize> {
+        match self.header.page_size {
+            0 => None,
+            // larger shifts would overflow, and are nonsensical anyway
+            shift if shift < 32 => Some(1 << shift),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Debug for CodeDirectory<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("CodeDirectory")
+            .field("version", &format_args!("{:#x}", self.header.version))
+            .field("flags", &format_args!("{:#x}", self.header.flags))
+            .field("identifier", &self.identifier)
+            .field("hash_type", &hash_type_to_str(self.header.hash_type))
+            .field("hash_size", &self.header.hash_size)
+            .field("page_size", &self.page_size())
+            .field("code_limit", &format_args!("{:#x}", self.header.code_limit))
+            .field("n_code_slots", &self.code_hashes.len())
+            .field("n_special_slots", &self.special_hashes.len())
+            .finish()
+    }
+}
+
+/// A typed code signing blob
+#[derive(Debug)]
+pub enum Blob<'a> {
+    CodeDirectory(CodeDirectory<'a>),
+    /// The requirements vector, unparsed
+    Requirements(&'a [u8]),
+    /// The entitlements plist
+    Entitlements(&'a [u8]),
+    /// The CMS signature, unparsed
+    BlobWrapper(&'a [u8]),
+    /// A blob of another kind, with its magic and its payload
+    Unknown(u32, &'a [u8]),
+}
+
+impl<'a> Blob<'a> {
+    /// Parses the blob at the start of `bytes`, which is bounded by the blob's length
+    pub fn parse(bytes: &'a [u8]) -> error::Result<Self> {
+        let header: BlobHeader = bytes.pread_with(0, scroll::BE)?;
+        let length = header.length as usize;
+        if length < SIZEOF_BLOB_HEADER || length > bytes.len() {
+            return Err(error::Error::Malformed(format!("{} blob has length {:#x}, but only {:#x} bytes are available", magic_to_str(header.magic), length, bytes.len())));
+        }
+        let bytes = &bytes[..length];
+        let payload = &bytes[SIZEOF_BLOB_HEADER..];
+        Ok(match header.magic {
+            CSMAGIC_CODEDIRECTORY => Blob::CodeDirectory(CodeDirectory::parse(bytes)?),
+            CSMAGIC_REQUIREMENTS => Blob::Requirements(payload),
+            CSMAGIC_EMBEDDED_ENTITLEMENTS => Blob::Entitlements(payload),
+            CSMAGIC_BLOBWRAPPER => Blob::BlobWrapper(payload),
+            magic => Blob::Unknown(magic, payload),
+        })
+    }
+}
+
+/// An entry of a code signature: the slot it occupies and the blob in it
+#[derive(Debug)]
+pub struct BlobEntry<'a> {
+    /// The `CSSLOT_*` of the blob
+    pub typ: u32,
+    pub blob: Blob<'a>,
+}
+
+/// An embedded code signature, i.e., a SuperBlob and the blobs it indexes
+#[derive(Debug)]
+pub struct CodeSignature<'a> {
+    pub header: SuperBlobHeader,
+    pub blobs: Vec<BlobEntry<'a>>,
+}
+
+impl<'a> CodeSignature<'a> {
+    /// Parses the code signature SuperBlob at the start of `bytes`
+    pub fn parse(bytes: &'a [u8]) -> error::Result<Self> {
+        let header: SuperBlobHeader = bytes.pread_with(0, scroll::BE)?;
+        if header.magic != CSMAGIC_EMBEDDED_SIGNATURE {
+            return Err(error::Error::BadMagic(header.magic as u64));
+        }
+        let length = header.length as usize;
+        if length > bytes.len() {
+            return Err(error::Error::Malformed(format!("code signature has length {:#x}, but only {:#x} bytes are available", length, bytes.len())));
+        }
+        let bytes = &bytes[..length];
+        let count = header.count as usize;
+        if count.saturating_mul(SIZEOF_BLOB_INDEX) > length {
+            return Err(error::Error::Malformed(format!("code signature has {} blob indices, which do not fit in its length {:#x}", count, length)));
+        }
+        let mut blobs = Vec::with_capacity(count);
+        let mut offset = SIZEOF_SUPER_BLOB_HEADER;
+        for _ in 0..count {
+            let index: BlobIndex = bytes.gread_with(&mut offset, scroll::BE)?;
+            let blob_offset = index.offset as usize;
+            if blob_offset >= length {
+                return Err(error::Error::Malformed(format!("blob in slot {:#x} has offset {:#x}, outside of the code signature (length {:#x})", index.typ, blob_offset, length)));
+            }
+            blobs.push(BlobEntry {
+                typ: index.typ,
+                blob: Blob::parse(&bytes[blob_offset..])?,
+            });
+        }
+        Ok(CodeSignature {
+            header: header,
+            blobs: blobs,
+        })
+    }
+    /// Returns the primary CodeDirectory, i.e., the one in the `CSSLOT_CODEDIRECTORY` slot, if any
+    pub fn code_directory(&self) -> Option<&CodeDirectory<'a>> {
+        self.blobs.iter().filter_map(|entry| {
+            match entry.blob {
+                Blob::CodeDirectory(ref cd) if entry.typ == CSSLOT_CODEDIRECTORY => Some(cd),
+                _ => None,
+            }
+        }).next()
+    }
+}
@@ -1,4 +1,7 @@
 //! Miscellaneous constants used inside of and when constructing, Mach-o binaries
+
+pub use mach::header::filetype_to_str;
+
 // Convienence constants for return values from dyld_get_sdk_version() and friends.
 pub const DYLD_MACOSX_VERSION_10_4: u32 = 0x000A0400;
 pub const DYLD_MACOSX_VERSION_10_5: u32 = 0x000A0500;
@@ -26,8 +29,33 @@ pub const DYLD_IOS_VERSION_7_0: u32 = 0x00070000;
 pub const DYLD_IOS_VERSION_7_1: u32 = 0x00070100;
 pub const DYLD_IOS_VERSION_8_0: u32 = 0x00080000;
 
+// Virtual memory protection, for the maxprot and initprot of a segment
+/// no access
+pub const VM_PROT_NONE: u32 = 0x0;
+/// read permission
+pub const VM_PROT_READ: u32 = 0x1;
+/// write permission
+pub const VM_PROT_WRITE: u32 = 0x2;
+/// execute permission
+pub const VM_PROT_EXECUTE: u32 = 0x4;
+
 // Segment and Section Constants
 
+// Constants for the flags field of a segment
+/// the file contents for this segment is for the high part of the VM space, the low part is zero filled (for stacks in
+/// core files)
+pub const SG_HIGHVM: u32 = 0x1;
+/// this segment is the VM that is allocated by a fixed VM library, for overlap checking in the link editor
+pub const SG_FVMLIB: u32 = 0x2;
+/// this segment has nothing that was relocated in it and nothing relocated to it, that is it maybe safely replaced
+/// without relocation
+pub const SG_NORELOC: u32 = 0x4;
+/// this segment is protected; if the segment starts at file offset 0, the first page of the segment is not protected,
+/// all other pages of the segment are protected (encrypted)
+pub const SG_PROTECTED_VERSION_1: u32 = 0x8;
+/// this segment is made read-only after fixups
+pub const SG_READ_ONLY: u32 = 0x10;
+
 // The flags field of a section structure is separated into two parts a section
 // type and section attributes.  The section types are mutually exclusive (it
 // can only have one type) but the section attributes are not (it may have more
@@ -192,8 +220,10 @@ pub mod cputype {
     pub const CPU_ARCH_ABI64: CpuType = 0x01000000;
     pub const CPU_TYPE_X86: CpuType = 7;
     pub const CPU_TYPE_ARM: CpuType = 12;
+    pub const CPU_TYPE_POWERPC: CpuType = 18;
     pub const CPU_TYPE_X86_64: CpuType = CPU_TYPE_X86 | CPU_ARCH_ABI64;
     pub const CPU_TYPE_ARM64: CpuType = CPU_TYPE_ARM | CPU_ARCH_ABI64;
+    pub const CPU_TYPE_POWERPC64: CpuType = CPU_TYPE_POWERPC | CPU_ARCH_ABI64;
 
     #[inline(always)]
     pub fn cpu_type_to_str(cputype: CpuType) -> &'static str {
@@ -202,7 +232,99 @@ pub mod cputype {
             CPU_TYPE_X86_64 => "x86_64",
             CPU_TYPE_ARM => "ARM",
             CPU_TYPE_X86 => "x86",
+            CPU_TYPE_POWERPC64 => "PowerPC64",
+            CPU_TYPE_POWERPC => "PowerPC",
             _ => "UNIMPLEMENTED CPUTYPE",
         }
     }
+
+    pub type CpuSubType = u32;
+
+    /// mask for the feature flags (capability bits) of a cpusubtype
+    pub const CPU_SUBTYPE_MASK: CpuSubType = 0xff000000;
+    /// 64-bit libraries
+    pub const CPU_SUBTYPE_LIB64: CpuSubType = 0x80000000;
+    /// a subtype which runs on any subtype of its cputype (`CPU_SUBTYPE_MULTIPLE`)
+    pub const CPU_SUBTYPE_ANY: CpuSubType = !0;
+
+    pub const CPU_SUBTYPE_X86_ALL: CpuSubType = 3;
+    pub const CPU_SUBTYPE_X86_64_ALL: CpuSubType = 3;
+    pub const CPU_SUBTYPE_X86_ARCH1: CpuSubType = 4;
+    pub const CPU_SUBTYPE_X86_64_H: CpuSubType = 8;
+
+    pub const CPU_SUBTYPE_ARM_ALL: CpuSubType = 0;
+    pub const CPU_SUBTYPE_ARM_V4T: CpuSubType = 5;
+    pub const CPU_SUBTYPE_ARM_V6: CpuSubType = 6;
+    pub const CPU_SUBTYPE_ARM_V5TEJ: CpuSubType = 7;
+    pub const CPU_SUBTYPE_ARM_XSCALE: CpuSubType = 8;
+    pub const CPU_SUBTYPE_ARM_V7: CpuSubType = 9;
+    pub const CPU_SUBTYPE_ARM_V7F: CpuSubType = 10;
+    pub const CPU_SUBTYPE_ARM_V7S: CpuSubType = 11;
+    pub const CPU_SUBTYPE_ARM_V7K: CpuSubType = 12;
+    pub const CPU_SUBTYPE_ARM_V8: CpuSubType = 13;
+    pub const CPU_SUBTYPE_ARM_V6M: CpuSubType = 14;
+    pub const CPU_SUBTYPE_ARM_V7M: CpuSubType = 15;
+    pub const CPU_SUBTYPE_ARM_V7EM: CpuSubType = 16;
+
+    pub const CPU_SUBTYPE_ARM64_ALL: CpuSubType = 0;
+    pub const CPU_SUBTYPE_ARM64_V8: CpuSubType = 1;
+    pub const CPU_SUBTYPE_ARM64E: CpuSubType = 2;
+    /// the capability bit of an arm64e cpusubtype that marks its pointer authentication ABI as versioned
+    pub const CPU_SUBTYPE_PTRAUTH_ABI: CpuSubType = 0x80000000;
+    /// the capability bit of an arm64e cpusubtype that marks a kernel pointer authentication ABI
+    pub const CPU_SUBTYPE_ARM64E_KERNEL_PTRAUTH_ABI: CpuSubType = 0x40000000;
+    /// mask for the pointer authentication ABI version in the capability bits of an arm64e cpusubtype
+    pub const CPU_SUBTYPE_ARM64E_PTRAUTH_MASK: CpuSubType = 0x3f000000;
+
+    pub const CPU_SUBTYPE_POWERPC_ALL: CpuSubType = 0;
+    pub const CPU_SUBTYPE_POWERPC_7400: CpuSubType = 10;
+    pub const CPU_SUBTYPE_POWERPC_7450: CpuSubType = 11;
+    pub const CPU_SUBTYPE_POWERPC_970: CpuSubType = 100;
+
+    /// Returns the architecture name of the `cpusubtype` of `cputype`, ignoring its capability bits
+    pub fn cpu_subtype_to_str(cputype: CpuType, cpusubtype: CpuSubType) -> &'static str {
+        match (cputype, cpusubtype & !CPU_SUBTYPE_MASK) {
+            (CPU_TYPE_X86, CPU_SUBTYPE_X86_ALL) => "i386",
+            (CPU_TYPE_X86, CPU_SUBTYPE_X86_ARCH1) => "i486",
+            (CPU_TYPE_X86_64, CPU_SUBTYPE_X86_64_ALL) => "x86_64",
+            (CPU_TYPE_X86_64, CPU_SUBTYPE_X86_64_H) => "x86_64h",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_ALL) => "arm",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V4T) => "armv4t",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V6) => "armv6",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V5TEJ) => "armv5",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_XSCALE) => "xscale",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V7) => "armv7",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V7F) => "armv7f",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V7S) => "armv7s",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V7K) => "armv7k",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V8) => "armv8",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V6M) => "armv6m",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V7M) => "armv7m",
+            (CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V7EM) => "armv7em",
+            (CPU_TYPE_ARM64, CPU_SUBTYPE_ARM64_ALL) => "arm64",
+            (CPU_TYPE_ARM64, CPU_SUBTYPE_ARM64_V8) => "arm64v8",
+            (CPU_TYPE_ARM64, CPU_SUBTYPE_ARM64E) => "arm64e",
+            (CPU_TYPE_POWERPC, CPU_SUBTYPE_POWERPC_ALL) => "ppc",
+            (CPU_TYPE_POWERPC, CPU_SUBTYPE_POWERPC_7400) => "ppc7400",
+            (CPU_TYPE_POWERPC, CPU_SUBTYPE_POWERPC_7450) => "ppc7450",
+            (CPU_TYPE_POWERPC, CPU_SUBTYPE_POWERPC_970) => "ppc970",
+            (CPU_TYPE_POWERPC64, CPU_SUBTYPE_POWERPC_ALL) => "ppc64",
+            (CPU_TYPE_POWERPC64, CPU_SUBTYPE_POWERPC_970) => "ppc970-64",
+            _ => "UNIMPLEMENTED CPUSUBTYPE",
+        }
+    }
+
+    /// Whether `cputype` and `cpusubtype` are arm64e, i.e., arm64 with pointer authentication, whatever its capability bits
+    pub fn is_arm64e(cputype: CpuType, cpusubtype: CpuSubType) -> bool {
+        cputype == CPU_TYPE_ARM64 && cpusubtype & !CPU_SUBTYPE_MASK == CPU_SUBTYPE_ARM64E
+    }
+
+    /// The pointer authentication ABI version of an arm64e `cpusubtype`, or `None` if it is not arm64e or its ABI is
+    /// not versioned (`CPU_SUBTYPE_PTRAUTH_ABI` is unset)
+    pub fn ptrauth_abi_version(cpusubtype: CpuSubType) -> Option<u8> {
+        if cpusubtype & !CPU_SUBTYPE_MASK != CPU_SUBTYPE_ARM64E || cpusubtype & CPU_SUBTYPE_PTRAUTH_ABI == 0 {
+            return None;
+        }
+        Some(((cpusubtype & CPU_SUBTYPE_ARM64E_PTRAUTH_MASK) >> 24) as u8)
+    }
 }
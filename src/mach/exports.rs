@@ -7,9 +7,11 @@
 // (3) /usr/lib/libstdc++.6.0.9.dylib has flag 0xc at many offsets... they're weak 
 
 use core::ops::Range;
+use std::collections::HashSet;
 use scroll::{self, Pread, Uleb128};
 use error;
 use core::fmt::{self, Debug};
+use mach::leb;
 use mach::load_command;
 
 type Flag = u64;
@@ -24,7 +26,7 @@ pub const EXPORT_SYMBOL_FLAGS_WEAK_DEFINITION   : Flag = 0x04;
 pub const EXPORT_SYMBOL_FLAGS_REEXPORT          : Flag = 0x08;
 pub const EXPORT_SYMBOL_FLAGS_STUB_AND_RESOLVER : Flag = 0x10;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SymbolKind {
     Regular,
     Absolute,
@@ -92,7 +94,10 @@ impl<'a> ExportInfo<'a> {
                 tmp.into()
             };
             let lib_symbol_name = bytes.pread::<&str>(offset)?;
-            let lib = libs[lib_ordinal as usize];
+            let lib = match libs.get(lib_ordinal as usize) {
+                Some(lib) => *lib,
+                None => return Err(error::Error::Malformed(format!("re-export library ordinal {} is out of range ({} libraries)", lib_ordinal, libs.len()))),
+            };
             let lib_symbol_name = if lib_symbol_name == "" { None } else { Some (lib_symbol_name)};
             Ok(Reexport {
                 lib: lib,
@@ -134,6 +139,15 @@ impl<'a> ExportInfo<'a> {
     }
 }
 
+impl<'a> ExportInfo<'a> {
+    /// The `EXPORT_SYMBOL_FLAGS_*` of this export
+    pub fn flags(&self) -> Flag {
+        match *self {
+            ExportInfo::Regular { flags, .. } | ExportInfo::Reexport { flags, .. } | ExportInfo::Stub { flags, .. } => flags,
+        }
+    }
+}
+
 #[derive(Debug)]
 /// A finalized symbolic export reconstructed from the export trie
 pub struct Export<'a> {
@@ -141,10 +155,17 @@ pub struct Export<'a> {
     pub name: String,
     /// The export info in the node data
     pub info: ExportInfo<'a>,
-    /// How large this export is
+    /// The `EXPORT_SYMBOL_FLAGS_*` of this export
+    pub flags: Flag,
+    /// The kind of symbol exported, from the `EXPORT_SYMBOL_FLAGS_KIND_MASK` bits of its flags
+    pub kind: SymbolKind,
+    /// How large this export is, if known: the distance to the next export in the same section, or to the end of the section
     pub size: usize,
-    /// The offset this symbol export is found in the binary
+    /// The offset of this symbol export from the start of the image (its mach header); for absolute symbols, its value
     pub offset: u64,
+    /// The virtual memory address of this symbol export, i.e., `offset` from the address the image is loaded at,
+    /// or `offset` itself if that is not known
+    pub address: u64,
 }
 
 impl<'a> Export<'a> {
@@ -152,9 +173,22 @@ impl<'a> Export<'a> {
     pub fn new(name: String, info: ExportInfo<'a>) -> Export<'a> {
         let offset = match info {
             ExportInfo::Regular { address, .. } => address,
+            ExportInfo::Stub { ref stub_offset, .. } => (*stub_offset).into(),
             _ => 0x0,
         };
-        Export { name: name, info: info, size: 0, offset: offset }
+        let flags = info.flags();
+        Export { name: name, info: info, flags: flags, kind: SymbolKind::new(flags), size: 0, offset: offset, address: offset }
+    }
+    /// Whether this is an absolute symbol, whose offset is its value rather than an offset into the image
+    pub fn is_absolute(&self) -> bool {
+        self.kind == SymbolKind::Absolute
+    }
+    /// If this is a re-export, the library it is re-exported from and its name in that library
+    pub fn reexport(&self) -> Option<(&'a str, &str)> {
+        match self.info {
+            ExportInfo::Reexport { lib, lib_symbol_name, .. } => Some((lib, lib_symbol_name.unwrap_or(&self.name))),
+            _ => None,
+        }
     }
 }
 
@@ -166,87 +200,94 @@ pub struct ExportTrie<'a> {
 
 impl<'a> ExportTrie<'a> {
 
-    #[inline]
-    fn walk_nodes(&self, libs: &[&'a str], branches: Vec<(String, usize)>, acc: &mut Vec<Export<'a>>) -> error::Result<()> {
-        for (symbol, next_node) in branches {
-            self.walk_trie(libs, symbol, next_node, acc)?;
-        }
-        Ok(())
-    }
-
     // current_symbol can be a str iiuc
-    fn walk_branches(&self, nbranches: usize, current_symbol: String, mut offset: usize) -> error::Result<Vec<(String, usize)>> {
+    fn walk_branches(&self, nbranches: usize, current_symbol: &str, mut offset: usize) -> error::Result<Vec<(String, usize)>> {
         let mut branches = Vec::with_capacity(nbranches);
         //println!("\t@{:#x}", *offset);
         for _i in 0..nbranches {
             // additional offset calculations are relative to the base we received
             let offset = &mut offset;
             let string = self.data.pread::<&str>(*offset)?;
-            let mut key = current_symbol.clone();
+            let mut key = current_symbol.to_string();
             key.push_str(string);
             // +1 for null terminator
             *offset = *offset + string.len() + 1;
             //println!("\t({}) string_len: {} offset: {:#x}", i, string.len(), *offset);
             // value is relative to export trie base
-            let next_node = Uleb128::read(&self.data, offset)? as usize + self.location.start;
+            let next_node = (leb::read_uleb128(&self.data, offset)? as usize).checked_add(self.location.start);
+            let next_node = match next_node {
+                Some(next_node) if next_node < self.location.end => next_node,
+                _ => return Err(error::Error::Malformed(format!("export trie child node of {:?} is outside of the trie {:#x}..{:#x}", key, self.location.start, self.location.end))),
+            };
             //println!("\t({}) string: {} next_node: {:#x}", _i, key, next_node);
             branches.push((key, next_node));
         }
         Ok(branches)
     }
 
-    fn walk_trie(&self, libs: &[&'a str], current_symbol: String, start: usize, exports: &mut Vec<Export<'a>>) -> error::Result<()> {
-        if start < self.location.end {
+    fn walk_trie(&self, libs: &[&'a str], exports: &mut Vec<Export<'a>>) -> error::Result<()> {
+        if self.location.start >= self.location.end {
+            return Ok(());
+        }
+        // a node can only be reached once in a well formed trie; this guards against cycles in malformed ones
+        let mut visited = HashSet::new();
+        let mut nodes = vec![(String::new(), self.location.start)];
+        while let Some((current_symbol, start)) = nodes.pop() {
+            if !visited.insert(start) {
+                return Err(error::Error::Malformed(format!("export trie node at {:#x} ({:?}) is reachable more than once", start, current_symbol)));
+            }
             let offset = &mut start.clone();
-            let terminal_size = Uleb128::read(&self.data, offset)?;
-            // let mut input = String::new();
-            // ::std::io::stdin().read_line(&mut input).unwrap();
+            let terminal_size = leb::read_uleb128(&self.data, offset)?;
             // println!("@ {:#x} node: {:#x} current_symbol: {}", start, terminal_size, current_symbol);
-            if terminal_size == 0 {
-                let nbranches = Uleb128::read(&self.data, offset)? as usize;
+            let branches = if terminal_size == 0 {
+                let nbranches = leb::read_uleb128(&self.data, offset)? as usize;
                 //println!("\t@ {:#x} BRAN {}", *offset, nbranches);
-                let branches = self.walk_branches(nbranches, current_symbol, *offset)?;
-                self.walk_nodes(libs, branches, exports)
+                self.walk_branches(nbranches, &current_symbol, *offset)?
             } else { // terminal node, but the tricky part is that they can have children...
                 let pos = *offset;
                 let children_start = &mut (pos + terminal_size as usize);
-                let nchildren = Uleb128::read(&self.data, children_start)? as usize;
-                let flags = Uleb128::read(&self.data, offset)?;
+                let nchildren = leb::read_uleb128(&self.data, children_start)? as usize;
+                let flags = leb::read_uleb128(&self.data, offset)?;
                 //println!("\t@ {:#x} TERM {} flags: {:#x}", *offset, nchildren, flags);
                 let info = ExportInfo::parse(&self.data, libs, flags, *offset)?;
-                let export = Export::new(current_symbol.clone(), info);
+                let branches = self.walk_branches(nchildren, &current_symbol, *children_start)?;
+                let export = Export::new(current_symbol, info);
                 //println!("\t{:?}", &export);
                 exports.push(export);
-                if nchildren == 0 {
-                    // this branch is done
-                    Ok(())
-                } else {
-                    // more branches to walk
-                    let branches = self.walk_branches(nchildren, current_symbol, *children_start)?;
-                    self.walk_nodes(libs, branches, exports)
-                }
-            }
-        } else { Ok(()) }
+                branches
+            };
+            // reversed, so that branches are walked in the order they appear
+            nodes.extend(branches.into_iter().rev());
+        }
+        Ok(())
     }
 
     /// Walk the export trie for symbols exported by this binary, using the provided `libs` to resolve re-exports
     pub fn exports(&self, libs: &[&'a str]) -> error::Result<Vec<Export<'a>>> {
-        let offset = self.location.start.clone();
-        let current_symbol = String::new();
         let mut exports = Vec::new();
-        self.walk_trie(libs, current_symbol, offset, &mut exports)?;
+        self.walk_trie(libs, &mut exports)?;
         Ok(exports)
     }
 
     /// Create a new, lazy, zero-copy export trie from the `DyldInfo` `command`
     pub fn new(bytes: &'a [u8], command: &load_command::DyldInfoCommand) -> Self {
         let start = command.export_off as usize;
-        let end = (command.export_size + command.export_off) as usize;
+        let end = command.export_off as usize + command.export_size as usize;
         ExportTrie {
             data: bytes.as_ref(),
             location: start..end,
         }
     }
+
+    /// Create a new, lazy, zero-copy export trie from the `LC_DYLD_EXPORTS_TRIE` `command`
+    pub fn new_from_linkedit_data_command(bytes: &'a [u8], command: &load_command::LinkeditDataCommand) -> Self {
+        let start = command.dataoff as usize;
+        let end = command.dataoff as usize + command.datasize as usize;
+        ExportTrie {
+            data: bytes,
+            location: start..end,
+        }
+    }
 }
 
 impl<'a> Debug for ExportTrie<'a> {
@@ -273,4 +314,17 @@ mod tests {
         println!("len: {} exports: {:#?}", exports.len(), &exports);
         assert_eq!(exports.len() as usize, 3usize)
     }
+    #[test]
+    fn export_trie_cycle() {
+        // the root's only child, "_", points back at the root
+        const EXPORTS: [u8; 6] = [0x00, 0x01, 0x5f, 0x00, 0x00, 0x00];
+        let mut command = load_command::DyldInfoCommand::default();
+        command.export_size = EXPORTS.len() as u32;
+        let trie = ExportTrie::new(&EXPORTS, &command);
+        assert!(trie.exports(&[]).is_err());
+        // and a child past the end of the trie
+        const OUTSIDE: [u8; 6] = [0x00, 0x01, 0x5f, 0x00, 0x06, 0x00];
+        let trie = ExportTrie::new(&OUTSIDE, &command);
+        assert!(trie.exports(&[]).is_err());
+    }
 }
@@ -7,10 +7,37 @@ use std::io::{self, Read};
 
 use scroll::{self, Pread};
 use mach::constants::cputype;
+use container::{self, Container};
 use error;
 
 pub const FAT_MAGIC: u32 = 0xcafebabe;
 pub const FAT_CIGAM: u32 = 0xbebafeca;
+/// The magic of a fat binary with 64-bit architecture headers, `FatArch64`
+pub const FAT_MAGIC_64: u32 = 0xcafebabf;
+pub const FAT_CIGAM_64: u32 = 0xbfbafeca;
+
+/// The width and byte order of the architecture headers of a fat binary whose magic, read bigendian, is `magic`: `Big`
+/// for the `FatArch64`s of `FAT_MAGIC_64`, and little endian for the (nonstandard) byte swapped `FAT_CIGAM`s; or `None`
+/// if it isn't a fat magic
+pub fn arch_ctx(magic: u32) -> Option<container::Ctx> {
+    match magic {
+        FAT_MAGIC => Some(container::Ctx::new(Container::Little, scroll::BE)),
+        FAT_CIGAM => Some(container::Ctx::new(Container::Little, scroll::LE)),
+        FAT_MAGIC_64 => Some(container::Ctx::new(Container::Big, scroll::BE)),
+        FAT_CIGAM_64 => Some(container::Ctx::new(Container::Big, scroll::LE)),
+        _ => None,
+    }
+}
+
+/// The size of an architecture header of width `ctx`, i.e. `SIZEOF_FAT_ARCH_64` or `SIZEOF_FAT_ARCH`
+pub fn arch_size(ctx: container::Ctx) -> usize {
+    if ctx.is_big() { SIZEOF_FAT_ARCH_64 } else { SIZEOF_FAT_ARCH }
+}
+
+/// Whether an architecture of type `cputype` is a 64-bit binary
+fn is_64(cputype: u32) -> bool {
+    cputype == cputype::CPU_TYPE_X86_64 || cputype == cputype::CPU_TYPE_ARM64
+}
 
 #[repr(C)]
 #[derive(Clone, Copy, Default)]
@@ -78,7 +105,7 @@ impl fmt::Debug for FatArch {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("FatArch")
             .field("cputype", &cputype::cpu_type_to_str(self.cputype))
-            .field("cmdsize", &self.cpusubtype)
+            .field("cpusubtype", &cputype::cpu_subtype_to_str(self.cputype, self.cpusubtype))
             .field("offset",  &format_args!("{:#x}", &self.offset))
             .field("size",    &self.size)
             .field("align",   &self.align)
@@ -96,7 +123,7 @@ impl FatArch {
 
     /// Whether this fat architecture header describes a 64-bit binary
     pub fn is_64(&self) -> bool {
-        self.cputype == cputype::CPU_TYPE_X86_64 || self.cputype == cputype::CPU_TYPE_ARM64
+        is_64(self.cputype)
     }
     /// Parse a `FatArch` header from `bytes` at `offset`
     pub fn parse(bytes: &[u8], offset: usize) -> error::Result<Self> {
@@ -104,3 +131,84 @@ impl FatArch {
         Ok(arch)
     }
 }
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "std", derive(Pread, Pwrite, SizeWith))]
+/// The architecture header of a `FAT_MAGIC_64` fat binary, whose binaries can be past 4GiB into the file; like `FatArch`,
+/// its data is always bigendian
+pub struct FatArch64 {
+    /// What kind of CPU this binary is
+    pub cputype: u32,
+    pub cpusubtype: u32,
+    /// Where in the fat binary it starts
+    pub offset: u64,
+    /// How big the binary is
+    pub size: u64,
+    pub align: u32,
+    pub reserved: u32,
+}
+
+pub const SIZEOF_FAT_ARCH_64: usize = 32;
+
+impl fmt::Debug for FatArch64 {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("FatArch64")
+            .field("cputype", &cputype::cpu_type_to_str(self.cputype))
+            .field("cpusubtype", &cputype::cpu_subtype_to_str(self.cputype, self.cpusubtype))
+            .field("offset",  &format_args!("{:#x}", &self.offset))
+            .field("size",    &self.size)
+            .field("align",   &self.align)
+            .finish()
+    }
+}
+
+impl From<FatArch> for FatArch64 {
+    fn from(arch: FatArch) -> Self {
+        FatArch64 {
+            cputype: arch.cputype,
+            cpusubtype: arch.cpusubtype,
+            offset: arch.offset as u64,
+            size: arch.size as u64,
+            align: arch.align,
+            reserved: 0,
+        }
+    }
+}
+
+impl FatArch64 {
+    /// Get the slice of bytes this header describes from `bytes`, erroring if it isn't within them
+    pub fn slice<'a>(&self, bytes: &'a [u8]) -> error::Result<&'a [u8]> {
+        let range = match self.offset.checked_add(self.size) {
+            Some(end) if end <= bytes.len() as u64 => self.offset as usize..end as usize,
+            _ => return Err(error::Error::Malformed(format!("fat arch {:#x} + {:#x} is outside of the binary of size {:#x}", self.offset, self.size, bytes.len()))),
+        };
+        Ok(&bytes[range])
+    }
+    /// Whether this fat architecture header describes a 64-bit binary
+    pub fn is_64(&self) -> bool {
+        is_64(self.cputype)
+    }
+    /// Parse the architecture header of width and byte order `ctx` (see `arch_ctx`) from `bytes` at `offset`, widening
+    /// a 32-bit `FatArch`
+    pub fn parse(bytes: &[u8], offset: usize, ctx: container::Ctx) -> error::Result<Self> {
+        if ctx.is_big() {
+            Ok(bytes.pread_with::<FatArch64>(offset, ctx.le)?)
+        } else {
+            Ok(FatArch64::from(bytes.pread_with::<FatArch>(offset, ctx.le)?))
+        }
+    }
+    /// This header as a 32-bit `FatArch`, or `None` if its offset or size doesn't fit in one
+    pub fn to_fat_arch(&self) -> Option<FatArch> {
+        if self.offset > u32::MAX as u64 || self.size > u32::MAX as u64 {
+            return None;
+        }
+        Some(FatArch {
+            cputype: self.cputype,
+            cpusubtype: self.cpusubtype,
+            offset: self.offset as u32,
+            size: self.size as u32,
+            align: self.align,
+        })
+    }
+}
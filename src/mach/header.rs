@@ -74,6 +74,22 @@ pub const MH_NO_HEAP_EXECUTION: u32 = 0x1000000;
 // TODO: verify this number is correct, it was previously 0x02000000 which could indicate a typo/data entry error
 /// The code was linked for use in an application extension.
 pub const MH_APP_EXTENSION_SAFE: u32 = 0x2000000;
+/// The external symbols listed in the nlist symbol table do not include all the symbols listed in the dyld info.
+pub const MH_NLIST_OUTOFSYNC_WITH_DYLDINFO: u32 = 0x4000000;
+/// Allow LC_MIN_VERSION_MACOS and LC_BUILD_VERSION load commands with the platforms macOS, iOSMac,
+/// iOSSimulator, tvOSSimulator and watchOSSimulator.
+pub const MH_SIM_SUPPORT: u32 = 0x8000000;
+/// Only for use on dylibs. When this bit is set, the dylib is part of the dyld shared cache, rather than loose in the filesystem.
+pub const MH_DYLIB_IN_CACHE: u32 = 0x80000000;
+
+/// Every `MH_*` flag, in ascending order
+pub const MH_FLAGS: [u32; 29] = [
+    MH_NOUNDEFS, MH_INCRLINK, MH_DYLDLINK, MH_BINDATLOAD, MH_PREBOUND, MH_SPLIT_SEGS, MH_LAZY_INIT, MH_TWOLEVEL,
+    MH_FORCE_FLAT, MH_NOMULTIDEFS, MH_NOFIXPREBINDING, MH_PREBINDABLE, MH_ALLMODSBOUND, MH_SUBSECTIONS_VIA_SYMBOLS,
+    MH_CANONICAL, MH_WEAK_DEFINES, MH_BINDS_TO_WEAK, MH_ALLOW_STACK_EXECUTION, MH_ROOT_SAFE, MH_SETUID_SAFE,
+    MH_NO_REEXPORTED_DYLIBS, MH_PIE, MH_DEAD_STRIPPABLE_DYLIB, MH_HAS_TLV_DESCRIPTORS, MH_NO_HEAP_EXECUTION,
+    MH_APP_EXTENSION_SAFE, MH_NLIST_OUTOFSYNC_WITH_DYLDINFO, MH_SIM_SUPPORT, MH_DYLIB_IN_CACHE,
+];
 
 #[inline(always)]
 pub fn flag_to_str(flag: u32) -> &'static str {
@@ -89,7 +105,7 @@ pub fn flag_to_str(flag: u32) -> &'static str {
         MH_FORCE_FLAT => "MH_FORCE_FLAT",
         MH_NOMULTIDEFS => "MH_NOMULTIDEFS",
         MH_NOFIXPREBINDING => "MH_NOFIXPREBINDING",
-        MH_PREBINDABLE => "MH_PREBINDABLE ",
+        MH_PREBINDABLE => "MH_PREBINDABLE",
         MH_ALLMODSBOUND => "MH_ALLMODSBOUND",
         MH_SUBSECTIONS_VIA_SYMBOLS => "MH_SUBSECTIONS_VIA_SYMBOLS",
         MH_CANONICAL => "MH_CANONICAL",
@@ -104,6 +120,9 @@ pub fn flag_to_str(flag: u32) -> &'static str {
         MH_HAS_TLV_DESCRIPTORS => "MH_HAS_TLV_DESCRIPTORS",
         MH_NO_HEAP_EXECUTION => "MH_NO_HEAP_EXECUTION",
         MH_APP_EXTENSION_SAFE => "MH_APP_EXTENSION_SAFE",
+        MH_NLIST_OUTOFSYNC_WITH_DYLDINFO => "MH_NLIST_OUTOFSYNC_WITH_DYLDINFO",
+        MH_SIM_SUPPORT => "MH_SIM_SUPPORT",
+        MH_DYLIB_IN_CACHE => "MH_DYLIB_IN_CACHE",
         _ => "UNKNOWN FLAG",
     }
 }
@@ -138,6 +157,8 @@ pub const MH_DYLIB_STUB: u32 = 0x9;
 pub const MH_DSYM: u32 = 0xa;
 /// x86_64 kexts
 pub const MH_KEXT_BUNDLE: u32 = 0xb;
+/// set of mach-o's, e.g. a kernelcache
+pub const MH_FILESET: u32 = 0xc;
 
 pub fn filetype_to_str(filetype: u32) -> &'static str {
     match filetype {
@@ -152,10 +173,90 @@ pub fn filetype_to_str(filetype: u32) -> &'static str {
         MH_DYLIB_STUB => "DYLIB_STUB",
         MH_DSYM => "DSYM",
         MH_KEXT_BUNDLE => "KEXT_BUNDLE",
+        MH_FILESET => "FILESET",
         _ => "UNKNOWN FILETYPE",
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The `MH_*` filetype of a Mach-o binary
+pub enum FileType {
+    Object,
+    Execute,
+    Fvmlib,
+    Core,
+    Preload,
+    Dylib,
+    Dylinker,
+    Bundle,
+    DylibStub,
+    Dsym,
+    KextBundle,
+    Fileset,
+    Unknown(u32),
+}
+
+impl From<u32> for FileType {
+    fn from(filetype: u32) -> Self {
+        match filetype {
+            MH_OBJECT => FileType::Object,
+            MH_EXECUTE => FileType::Execute,
+            MH_FVMLIB => FileType::Fvmlib,
+            MH_CORE => FileType::Core,
+            MH_PRELOAD => FileType::Preload,
+            MH_DYLIB => FileType::Dylib,
+            MH_DYLINKER => FileType::Dylinker,
+            MH_BUNDLE => FileType::Bundle,
+            MH_DYLIB_STUB => FileType::DylibStub,
+            MH_DSYM => FileType::Dsym,
+            MH_KEXT_BUNDLE => FileType::KextBundle,
+            MH_FILESET => FileType::Fileset,
+            filetype => FileType::Unknown(filetype),
+        }
+    }
+}
+
+impl From<FileType> for u32 {
+    fn from(filetype: FileType) -> Self {
+        match filetype {
+            FileType::Object => MH_OBJECT,
+            FileType::Execute => MH_EXECUTE,
+            FileType::Fvmlib => MH_FVMLIB,
+            FileType::Core => MH_CORE,
+            FileType::Preload => MH_PRELOAD,
+            FileType::Dylib => MH_DYLIB,
+            FileType::Dylinker => MH_DYLINKER,
+            FileType::Bundle => MH_BUNDLE,
+            FileType::DylibStub => MH_DYLIB_STUB,
+            FileType::Dsym => MH_DSYM,
+            FileType::KextBundle => MH_KEXT_BUNDLE,
+            FileType::Fileset => MH_FILESET,
+            FileType::Unknown(filetype) => filetype,
+        }
+    }
+}
+
+impl FileType {
+    /// Whether this is a (demand paged) executable, `MH_EXECUTE`
+    pub fn is_executable(&self) -> bool {
+        *self == FileType::Execute
+    }
+    /// Whether this is a shared library that can be linked against, `MH_DYLIB` or its stub `MH_DYLIB_STUB`
+    pub fn is_library(&self) -> bool {
+        *self == FileType::Dylib || *self == FileType::DylibStub
+    }
+    /// Whether this is a relocatable object file, `MH_OBJECT`
+    pub fn is_object(&self) -> bool {
+        *self == FileType::Object
+    }
+}
+
+impl fmt::Display for FileType {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(filetype_to_str(u32::from(*self)))
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Default)]
 #[derive(Pread, Pwrite, SizeWith)]
@@ -388,6 +489,42 @@ impl Header {
     pub fn container(&self) -> container::Container {
         if self.magic == MH_MAGIC_64 || self.magic == MH_CIGAM_64 { Container::Big } else { Container::Little }
     }
+    /// The filetype of this binary
+    pub fn file_type(&self) -> FileType {
+        FileType::from(self.filetype)
+    }
+    /// Whether every bit of the `MH_*` `flag` is set
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags & flag == flag
+    }
+    /// Whether this is a position independent executable, `MH_PIE`
+    pub fn is_pie(&self) -> bool {
+        self.has_flag(MH_PIE)
+    }
+    /// Whether this binary uses two-level namespace bindings, `MH_TWOLEVEL`
+    pub fn is_two_level(&self) -> bool {
+        self.has_flag(MH_TWOLEVEL)
+    }
+    /// Whether the stack of this executable is executable, `MH_ALLOW_STACK_EXECUTION`
+    pub fn allows_stack_execution(&self) -> bool {
+        self.has_flag(MH_ALLOW_STACK_EXECUTION)
+    }
+    /// Whether the heap of this executable is non-executable even where the platform doesn't require it, `MH_NO_HEAP_EXECUTION`
+    pub fn has_no_heap_execution(&self) -> bool {
+        self.has_flag(MH_NO_HEAP_EXECUTION)
+    }
+    /// Whether this binary exports weak definitions, `MH_WEAK_DEFINES`
+    pub fn has_weak_defines(&self) -> bool {
+        self.has_flag(MH_WEAK_DEFINES)
+    }
+    /// Whether this binary uses weak symbols, `MH_BINDS_TO_WEAK`
+    pub fn binds_to_weak(&self) -> bool {
+        self.has_flag(MH_BINDS_TO_WEAK)
+    }
+    /// The names of the flags set in this header, in ascending order; unknown bits are `"UNKNOWN FLAG"`
+    pub fn flags_to_strings(&self) -> Vec<&'static str> {
+        (0..32).map(|bit| 1u32 << bit).filter(|&flag| self.flags & flag != 0).map(flag_to_str).collect()
+    }
     pub fn size(&self) -> usize {
         use scroll::ctx::SizeWith;
         Self::size_with(&self.container())
@@ -442,22 +579,18 @@ impl<'a> ctx::TryFromCtx<'a, Endian> for Header {
             Err(error)
         } else {
             let magic = mach::peek(&bytes, 0)?;
-            match magic {
-                MH_CIGAM_64 | MH_CIGAM | MH_MAGIC_64 | MH_MAGIC => {
-                    let is_lsb = magic == MH_CIGAM || magic == MH_CIGAM_64;
-                    let le = scroll::Endian::from(is_lsb);
-                    // todo check magic is 32 and not junk, and error otherwise
-                    let container = if magic == MH_MAGIC_64 || magic == MH_CIGAM_64 { Container::Big } else { Container::Little };
-                    match container {
+            match container::Ctx::from_mach_magic(magic) {
+                Some(ctx) => {
+                    match ctx.container {
                         Container::Little => {
-                            Ok((Header::from(bytes.pread_with::<Header32>(0, le)?), SIZEOF_HEADER_32))
+                            Ok((Header::from(bytes.pread_with::<Header32>(0, ctx.le)?), SIZEOF_HEADER_32))
                         },
                         Container::Big => {
-                            Ok((Header::from(bytes.pread_with::<Header64>(0, le)?), SIZEOF_HEADER_64))
+                            Ok((Header::from(bytes.pread_with::<Header64>(0, ctx.le)?), SIZEOF_HEADER_64))
                         },
                     }
                 },
-                _ => {
+                None => {
                     let error = error::Error::BadMagic(magic as u64);
                     Err(error)
                 }
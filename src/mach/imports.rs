@@ -6,56 +6,94 @@
 
 use core::ops::Range;
 use core::fmt::{self, Debug};
-use scroll::{Sleb128, Uleb128, Pread};
+use scroll::Pread;
 
 use container;
 use error;
+use mach::leb;
 use mach::load_command;
 use mach::bind_opcodes;
+use mach::{check_pointers, check_pointer_count};
 use mach::segment;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 /// Import binding information generated by running the Finite State Automaton programmed via `bind_opcodes`
 struct BindInformation<'a> {
   seg_index:              u8,
   seg_offset:             u64,
   bind_type:              u8,
-  symbol_library_ordinal: u8,
+  symbol_library_ordinal: i64,
   symbol_name:            &'a str,
   symbol_flags:           u8,
   addend:                 i64,
-  special_dylib:          u8, // seeing self = 0 assuming this means the symbol is imported from itself, because its... libSystem.B.dylib?
-  is_lazy:                bool,
 }
 
 impl<'a> BindInformation<'a> {
     pub fn new (is_lazy: bool) -> Self {
         let mut bind_info = BindInformation::default();
         if is_lazy {
-            bind_info.is_lazy = true;
             bind_info.bind_type = bind_opcodes::BIND_TYPE_POINTER;
         }
         bind_info
     }
-    pub fn is_weak(&self) -> bool {
-        self.symbol_flags & bind_opcodes::BIND_SYMBOL_FLAGS_WEAK_IMPORT != 0
-    }
 }
 
-impl<'a> Default for BindInformation<'a> {
-    fn default() -> Self {
-        BindInformation {
-            seg_index:     0,
-            seg_offset:    0x0,
-            bind_type:     0x0,
-            special_dylib: 1,
-            symbol_library_ordinal: 0,
-            symbol_name: "",
-            symbol_flags: 0,
-            addend: 0,
-            is_lazy: false
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Which of the bind opcode streams a `Bind` was described by
+pub enum BindKind {
+    /// The regular (non lazy) binds, at `bind_off`
+    Regular,
+    /// The weak binds, at `weak_bind_off`
+    Weak,
+    /// The lazy binds, at `lazy_bind_off`
+    Lazy,
+}
+
+#[derive(Debug, Clone)]
+/// A pointer which the dynamic linker binds to a symbol, as described by the bind opcodes
+pub struct Bind<'a> {
+    /// The index of the segment the pointer is in
+    pub segment_index: u8,
+    /// The offset of the pointer from the start of its segment
+    pub segment_offset: u64,
+    /// The ordinal of the library the symbol is in; the special (`BIND_SPECIAL_DYLIB_*`) ordinals are sign extended, i.e., 0 and below
+    pub library_ordinal: i64,
+    /// The name of the symbol
+    pub symbol_name: &'a str,
+    /// The `BIND_SYMBOL_FLAGS_*` of the symbol
+    pub symbol_flags: u8,
+    /// The addend of this bind
+    pub addend: i64,
+    /// The `BIND_TYPE_*` of this bind
+    pub bind_type: u8,
+    /// Which bind opcode stream this bind came from
+    pub kind: BindKind,
+    /// The offset in the stream of bind opcodes of the sequence which caused this bind
+    pub start_of_sequence_offset: usize,
+}
+
+impl<'a> Bind<'a> {
+    fn new(bi: &BindInformation<'a>, kind: BindKind, start_of_sequence_offset: usize) -> Bind<'a> {
+        Bind {
+            segment_index: bi.seg_index,
+            segment_offset: bi.seg_offset,
+            library_ordinal: bi.symbol_library_ordinal,
+            symbol_name: bi.symbol_name,
+            symbol_flags: bi.symbol_flags,
+            addend: bi.addend,
+            bind_type: bi.bind_type,
+            kind: kind,
+            start_of_sequence_offset: start_of_sequence_offset,
         }
     }
+    /// Whether this bind is lazily resolved
+    pub fn is_lazy(&self) -> bool {
+        self.kind == BindKind::Lazy
+    }
+    /// Whether the symbol is a weak import
+    pub fn is_weak(&self) -> bool {
+        self.symbol_flags & bind_opcodes::BIND_SYMBOL_FLAGS_WEAK_IMPORT != 0
+    }
 }
 
 #[derive(Debug)]
@@ -75,34 +113,46 @@ pub struct Import<'a> {
     pub address: u64,
     /// The addend of this import
     pub addend:  i64,
-    /// Whether this import is weak
+    /// Whether this import is weak, i.e., either a weak import, which may be missing at runtime, or a weak definition
+    /// bound by the weak bind opcodes, which dyld coalesces with the definitions of every other image
     pub is_weak: bool,
     /// The offset in the stream of bind opcodes that caused this import
     pub start_of_sequence_offset: u64
 }
 
 impl<'a> Import<'a> {
-    /// Create a new import from the import binding information in `bi`
-    fn new(bi: &BindInformation<'a>, libs: &[&'a str], segments: &[segment::Segment], start_of_sequence_offset: usize) -> Import<'a> {
-        let (offset, address) = {
-            let segment = &segments[bi.seg_index as usize];
-            (
-                segment.fileoff + bi.seg_offset,
-                segment.vmaddr + bi.seg_offset
-            )
+    /// Create a new import from the `bind`, resolving its segment in `segments` and its library in `libs`
+    fn new(bind: &Bind<'a>, libs: &[&'a str], segments: &[segment::Segment]) -> error::Result<Import<'a>> {
+        let (offset, address) = match segments.get(bind.segment_index as usize) {
+            Some(segment) => (
+                segment.fileoff.wrapping_add(bind.segment_offset),
+                segment.vmaddr.wrapping_add(bind.segment_offset)
+            ),
+            None => return Err(error::Error::Malformed(format!("bind of {} is in segment {}, but there are only {} segments", bind.symbol_name, bind.segment_index, segments.len()))),
         };
-        let size = if bi.is_lazy { 8 } else { 0 };
-        Import {
-            name: bi.symbol_name,
-            dylib: libs[bi.symbol_library_ordinal as usize],
-            is_lazy: bi.is_lazy,
+        let dylib = match (bind.kind, bind.library_ordinal) {
+            // weak binds have no library; they are coalesced with the definitions in every image
+            (BindKind::Weak, _) => "flat-namespace",
+            (_, -1) => "main-executable",
+            (_, -2) | (_, -3) => "flat-namespace",
+            (_, ordinal) => match if ordinal >= 0 { libs.get(ordinal as usize) } else { None } {
+                Some(dylib) => *dylib,
+                None => return Err(error::Error::Malformed(format!("bind of {} has library ordinal {}, but there are only {} libraries", bind.symbol_name, bind.library_ordinal, libs.len()))),
+            },
+        };
+        let is_lazy = bind.is_lazy();
+        let size = if is_lazy { 8 } else { 0 };
+        Ok(Import {
+            name: bind.symbol_name,
+            dylib: dylib,
+            is_lazy: is_lazy,
             offset: offset,
             size: size,
             address: address,
-            addend: bi.addend,
-            is_weak: bi.is_weak(),
-            start_of_sequence_offset: start_of_sequence_offset as u64
-        }
+            addend: bind.addend,
+            is_weak: bind.is_weak() || bind.kind == BindKind::Weak,
+            start_of_sequence_offset: bind.start_of_sequence_offset as u64
+        })
     }
 }
 
@@ -112,6 +162,7 @@ impl<'a> Import<'a> {
 pub struct BindInterpreter<'a> {
     data: &'a [u8],
     location: Range<usize>,
+    weak_location: Range<usize>,
     lazy_location: Range<usize>,
 }
 
@@ -119,6 +170,7 @@ impl<'a> Debug for BindInterpreter<'a> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         writeln!(fmt, "BindInterpreter {{")?;
         writeln!(fmt, "  Location: {:#x}..{:#x}", self.location.start, self.location.end)?;
+        writeln!(fmt, "  Weak Location: {:#x}..{:#x}", self.weak_location.start, self.weak_location.end)?;
         writeln!(fmt, "  Lazy Location: {:#x}..{:#x}", self.lazy_location.start, self.lazy_location.end)?;
         writeln!(fmt, "}}")
     }
@@ -129,33 +181,51 @@ impl<'a> BindInterpreter<'a> {
     /// Construct a new import binding interpreter from `bytes` and the load `command`
     pub fn new(bytes: &'a [u8], command: &load_command::DyldInfoCommand) -> Self {
         let get_pos = |off: u32, size: u32| -> Range<usize> {
-            off as usize..(off + size) as usize
+            off as usize..off as usize + size as usize
         };
         let location = get_pos(command.bind_off, command.bind_size);
+        let weak_location = get_pos(command.weak_bind_off, command.weak_bind_size);
         let lazy_location = get_pos(command.lazy_bind_off, command.lazy_bind_size);
         BindInterpreter {
             data: bytes.as_ref(),
             location: location,
+            weak_location: weak_location,
             lazy_location: lazy_location,
         }
     }
-    /// Return the imports in this binary
+    /// Return the imports described by the regular, weak, and lazy bind opcode streams of this binary, in that order
     pub fn imports(&self, libs: &[&'a str], segments: &[segment::Segment], ctx: &container::Ctx) -> error::Result<Vec<Import<'a>>>{
-        let mut imports = Vec::new();
-        self.run(false, libs, segments, ctx, &mut imports)?;
-        self.run( true, libs, segments, ctx, &mut imports)?;
-        Ok(imports)
+        self.binds(segments, ctx)?.iter().map(|bind| Import::new(bind, libs, segments)).collect()
+    }
+    /// Return every bind described by the regular, weak, and lazy bind opcode streams, in that order, erroring if one
+    /// is in a segment which isn't one of the binary's `segments`, or past the end of its file contents
+    pub fn binds(&self, segments: &[segment::Segment], ctx: &container::Ctx) -> error::Result<Vec<Bind<'a>>> {
+        let mut binds = Vec::new();
+        self.run(BindKind::Regular, segments, ctx, &mut binds)?;
+        self.run(BindKind::Weak, segments, ctx, &mut binds)?;
+        self.run(BindKind::Lazy, segments, ctx, &mut binds)?;
+        Ok(binds)
     }
-    fn run(&self, is_lazy: bool, libs: &[&'a str], segments: &[segment::Segment], ctx: &container::Ctx, imports: &mut Vec<Import<'a>>) -> error::Result<()>{
+    fn run(&self, kind: BindKind, segments: &[segment::Segment], ctx: &container::Ctx, binds: &mut Vec<Bind<'a>>) -> error::Result<()>{
         use mach::bind_opcodes::*;
-        let location = if is_lazy {
-            &self.lazy_location
-        } else {
-            &self.location
+        let is_lazy = kind == BindKind::Lazy;
+        let location = match kind {
+            BindKind::Regular => &self.location,
+            BindKind::Weak => &self.weak_location,
+            BindKind::Lazy => &self.lazy_location,
         };
         let mut bind_info = BindInformation::new(is_lazy);
         let mut offset = &mut location.start.clone();
         let mut start_of_sequence: usize = 0;
+        let size = ctx.size() as u64;
+        // the binds of the other streams already recorded
+        let start = binds.len();
+        // checks that `count` binds `stride` apart from the current one are within its segment, and that there are
+        // pointers enough for them on top of the `recorded` ones of this stream
+        let check = |bind_info: &BindInformation, recorded: usize, count: u64, stride: u64| -> error::Result<()> {
+            check_pointers(segments, bind_info.seg_index, bind_info.seg_offset, count, stride, "bind")?;
+            check_pointer_count(segments, size, recorded, count, "bind")
+        };
         while *offset < location.end {
             let opcode = self.data.gread::<i8>(offset)? as bind_opcodes::Opcode;
             // let mut input = String::new();
@@ -164,22 +234,26 @@ impl<'a> BindInterpreter<'a> {
             match opcode & BIND_OPCODE_MASK {
                 // we do nothing, don't update our records, and add a new, fresh record
                 BIND_OPCODE_DONE => {
+                    // only the lazy binds are a sequence of programs, one per symbol; the others end here
+                    if !is_lazy {
+                        break;
+                    }
                     bind_info = BindInformation::new(is_lazy);
                     start_of_sequence = *offset - location.start;
                 },
                 BIND_OPCODE_SET_DYLIB_ORDINAL_IMM => {
 	            let symbol_library_ordinal = opcode & BIND_IMMEDIATE_MASK;
-	            bind_info.symbol_library_ordinal = symbol_library_ordinal;
+	            bind_info.symbol_library_ordinal = symbol_library_ordinal as i64;
                 },
                 BIND_OPCODE_SET_DYLIB_ORDINAL_ULEB => {
-	            let symbol_library_ordinal = Uleb128::read(&self.data, offset)?;
-	            bind_info.symbol_library_ordinal = symbol_library_ordinal as u8;
+	            let symbol_library_ordinal = leb::read_uleb128(&self.data, offset)?;
+	            bind_info.symbol_library_ordinal = symbol_library_ordinal as i64;
                 },
                 BIND_OPCODE_SET_DYLIB_SPECIAL_IMM => {
-                    // dyld puts the immediate into the symbol_library_ordinal field...
+                    // dyld puts the (sign extended) immediate into the symbol_library_ordinal field...
                     let special_dylib = opcode & BIND_IMMEDIATE_MASK;
                     // Printf.printf "special_dylib: 0x%x\n" special_dylib
-                    bind_info.special_dylib = special_dylib;
+                    bind_info.symbol_library_ordinal = if special_dylib == 0 { 0 } else { (BIND_OPCODE_MASK | special_dylib) as i8 as i64 };
                 },
                 BIND_OPCODE_SET_SYMBOL_TRAILING_FLAGS_IMM => {
 	            let symbol_flags = opcode & BIND_IMMEDIATE_MASK;
@@ -193,19 +267,22 @@ impl<'a> BindInterpreter<'a> {
 	            bind_info.bind_type = bind_type;
                 },
                 BIND_OPCODE_SET_ADDEND_SLEB => {
-                    let addend = Sleb128::read(&self.data, offset)?;
+                    let addend = leb::read_sleb128(&self.data, offset)?;
                     bind_info.addend = addend;
                 },
                 BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB => {
 	            let seg_index = opcode & BIND_IMMEDIATE_MASK;
+                    if seg_index as usize >= segments.len() {
+                        return Err(error::Error::Malformed(format!("bind opcode at {:#x} sets segment {}, but there are only {} segments", *offset - 1, seg_index, segments.len())));
+                    }
                     // dyld sets the address to the segActualLoadAddress(segIndex) + uleb128
                     // address = segActualLoadAddress(segmentIndex) + read_uleb128(p, end);
-	            let seg_offset = Uleb128::read(&self.data, offset)?;
+	            let seg_offset = leb::read_uleb128(&self.data, offset)?;
 	            bind_info.seg_index = seg_index;
                     bind_info.seg_offset = seg_offset;
                 },
                 BIND_OPCODE_ADD_ADDR_ULEB => {
-	            let addr = Uleb128::read(&self.data, offset)?;
+	            let addr = leb::read_uleb128(&self.data, offset)?;
 	            let seg_offset = bind_info.seg_offset.wrapping_add(addr);
 	            bind_info.seg_offset = seg_offset;
                 },
@@ -216,8 +293,9 @@ impl<'a> BindInterpreter<'a> {
 	            // throwBadBindingAddress(address, segmentEndAddress, segmentIndex, start, end, p);
 	            // (this->*handler)(context, address, type, symbolName, symboFlags, addend, libraryOrdinal, "", &last);
 	            // address += sizeof(intptr_t);
-                    imports.push(Import::new(&bind_info, libs, segments, start_of_sequence));
-                    let seg_offset = bind_info.seg_offset.wrapping_add(ctx.size() as u64);
+                    check(&bind_info, binds.len() - start, 1, size)?;
+                    binds.push(Bind::new(&bind_info, kind, start_of_sequence));
+                    let seg_offset = bind_info.seg_offset.wrapping_add(size);
                     bind_info.seg_offset = seg_offset;
                 },
                 BIND_OPCODE_DO_BIND_ADD_ADDR_ULEB => {
@@ -227,9 +305,10 @@ impl<'a> BindInterpreter<'a> {
 	            // (this->*handler)(context, address, type, symbolName, symboFlags, addend, libraryOrdinal, "", &last);
 	            // address += read_uleb128(p, end) + sizeof(intptr_t);
                     // we bind the old record, then increment bind info address for the next guy, plus the ptr offset *)
-                    imports.push(Import::new(&bind_info, libs, segments, start_of_sequence));
-                    let addr = Uleb128::read(&self.data, offset)?;
-                    let seg_offset = bind_info.seg_offset.wrapping_add(addr).wrapping_add(ctx.size() as u64);
+                    check(&bind_info, binds.len() - start, 1, size)?;
+                    binds.push(Bind::new(&bind_info, kind, start_of_sequence));
+                    let addr = leb::read_uleb128(&self.data, offset)?;
+                    let seg_offset = bind_info.seg_offset.wrapping_add(addr).wrapping_add(size);
                     bind_info.seg_offset = seg_offset;
                 },
                 BIND_OPCODE_DO_BIND_ADD_ADDR_IMM_SCALED => {
@@ -240,9 +319,9 @@ impl<'a> BindInterpreter<'a> {
 	            // address += immediate*sizeof(intptr_t) + sizeof(intptr_t);
 	            // break;
                     // similarly, we bind the old record, then perform address manipulation for the next record
-                    imports.push(Import::new(&bind_info, libs, segments, start_of_sequence));
+                    check(&bind_info, binds.len() - start, 1, size)?;
+                    binds.push(Bind::new(&bind_info, kind, start_of_sequence));
 	            let scale = opcode & BIND_IMMEDIATE_MASK;
-                    let size = ctx.size() as u64;
                     let seg_offset = bind_info.seg_offset.wrapping_add(scale as u64 * size).wrapping_add(size);
                     bind_info.seg_offset = seg_offset;
                 },
@@ -257,11 +336,16 @@ impl<'a> BindInterpreter<'a> {
 	            // address += skip + sizeof(intptr_t);
 	            // }
 	            // break;
-                    let count = Uleb128::read(&self.data, offset)?;
-                    let skip =  Uleb128::read(&self.data, offset)?;
-                    let skip_plus_size = skip + ctx.size() as u64;
-                    for _i  in 0..count {
-                        imports.push(Import::new(&bind_info, libs, segments, start_of_sequence));
+                    let count = leb::read_uleb128(&self.data, offset)?;
+                    let skip =  leb::read_uleb128(&self.data, offset)?;
+                    let skip_plus_size = match skip.checked_add(size) {
+                        Some(skip_plus_size) => skip_plus_size,
+                        None => return Err(error::Error::Malformed(format!("bind skip {:#x} at {:#x} overflows", skip, *offset))),
+                    };
+                    // validate the whole run before recording any of it, so a bogus count can't exhaust memory
+                    check(&bind_info, binds.len() - start, count, skip_plus_size)?;
+                    for _i in 0..count {
+                        binds.push(Bind::new(&bind_info, kind, start_of_sequence));
                         let seg_offset = bind_info.seg_offset.wrapping_add(skip_plus_size);
                         bind_info.seg_offset = seg_offset;
                     }
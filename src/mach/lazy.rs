@@ -0,0 +1,165 @@
+//! Lazy parsing of Mach-o binaries and fat containers from a `Read + Seek` source, e.g., a `File`
+//!
+//! Unlike `MachO::parse` and `MultiArch::new`, which need the whole file in memory, these only read what is asked for:
+//! the fat header and architectures, the Mach-o header and load commands, and whatever tables are then read with
+//! `LazyMachO::read_at`. This is useful for universal binaries and dyld shared caches that are gigabytes in size.
+
+use std::io::{Read, Seek, SeekFrom};
+
+use scroll::{self, Pread};
+
+use container;
+use error;
+use mach::fat::{self, FatArch64, FatHeader, SIZEOF_FAT_HEADER};
+use mach::header::{Header, Header32, Header64, SIZEOF_HEADER_32, SIZEOF_HEADER_64};
+use mach::load_command::LoadCommandIterator;
+
+/// The size of the seekable `reader`, leaving it at its end
+fn stream_len<R: Seek>(reader: &mut R) -> error::Result<u64> {
+    Ok(reader.seek(SeekFrom::End(0))?)
+}
+
+/// Reads the `len` bytes at `offset` from `reader`, erroring rather than allocating when they aren't all before `end`
+fn read_at<R: Read + Seek>(reader: &mut R, offset: u64, len: usize, end: u64) -> error::Result<Vec<u8>> {
+    match offset.checked_add(len as u64) {
+        Some(last) if last <= end => (),
+        _ => return Err(error::Error::Malformed(format!("{:#x} bytes at {:#x} are past the end of the data ({:#x})", len, offset, end))),
+    }
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// A Mach-o binary whose header and load commands have been read from `reader`, and nothing else
+pub struct LazyMachO<R> {
+    reader: R,
+    /// The offset of the binary in `reader`
+    start: u64,
+    /// The offset of the end of the binary in `reader`, past which nothing is read
+    end: u64,
+    /// The header and load commands
+    data: Vec<u8>,
+    pub header: Header,
+    ctx: container::Ctx,
+}
+
+impl<R: Read + Seek> LazyMachO<R> {
+    /// Reads the header and load commands of the Mach-o binary at `offset` in `reader`, which extends to its end
+    pub fn parse(mut reader: R, offset: u64) -> error::Result<Self> {
+        let size = stream_len(&mut reader)?;
+        LazyMachO::parse_bounded(reader, offset, size)
+    }
+    /// Reads the header and load commands of the Mach-o binary at `offset` in `reader`, which ends at `end`
+    fn parse_bounded(mut reader: R, offset: u64, end: u64) -> error::Result<Self> {
+        let magic = read_at(&mut reader, offset, 4, end)?.pread_with::<u32>(0, scroll::BE)?;
+        let ctx = match container::Ctx::from_mach_magic(magic) {
+            Some(ctx) => ctx,
+            None => return Err(error::Error::BadMagic(magic as u64)),
+        };
+        let header_size = if ctx.is_big() { SIZEOF_HEADER_64 } else { SIZEOF_HEADER_32 };
+        let mut data = read_at(&mut reader, offset, header_size, end)?;
+        let header = if ctx.is_big() {
+            Header::from(data.pread_with::<Header64>(0, ctx.le)?)
+        } else {
+            Header::from(data.pread_with::<Header32>(0, ctx.le)?)
+        };
+        let commands = read_at(&mut reader, offset + header_size as u64, header.sizeofcmds as usize, end)?;
+        data.extend_from_slice(&commands);
+        Ok(LazyMachO {
+            reader: reader,
+            start: offset,
+            end: end,
+            data: data,
+            header: header,
+            ctx: ctx,
+        })
+    }
+    /// The container and endianness of this binary
+    pub fn ctx(&self) -> container::Ctx {
+        self.ctx
+    }
+    /// The bytes of the header and load commands; offsets into it are the same as offsets into the binary, so e.g.
+    /// `lc_str`s of a `LoadCommand` can be read from `&data()[cmd.offset..]`
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+    /// Iterate the load commands, which are parsed from the bytes read by `parse`
+    pub fn load_commands<'a>(&'a self) -> LoadCommandIterator<'a> {
+        LoadCommandIterator::new(&self.data, self.header.size(), self.header.ncmds, self.ctx.le)
+    }
+    /// Reads the `len` bytes at `offset` from the start of this binary, e.g., the `dataoff` and `datasize` of an
+    /// `LC_CODE_SIGNATURE`, or the `symoff` of an `LC_SYMTAB`, erroring if they aren't all within the binary
+    pub fn read_at(&mut self, offset: u64, len: usize) -> error::Result<Vec<u8>> {
+        let offset = match self.start.checked_add(offset) {
+            Some(offset) => offset,
+            None => return Err(error::Error::Malformed(format!("offset {:#x} from {:#x} overflows", offset, self.start))),
+        };
+        read_at(&mut self.reader, offset, len, self.end)
+    }
+    /// Gets the underlying reader back
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+/// A fat container whose architectures have been read from `reader`, and whose binaries are read one at a time on demand
+pub struct LazyMultiArch<R> {
+    reader: R,
+    size: u64,
+    arches: Vec<FatArch64>,
+}
+
+impl<R: Read + Seek> LazyMultiArch<R> {
+    /// Reads the fat header and architectures at the start of `reader`
+    pub fn new(mut reader: R) -> error::Result<Self> {
+        let size = stream_len(&mut reader)?;
+        let header = FatHeader::parse(&read_at(&mut reader, 0, SIZEOF_FAT_HEADER, size)?)?;
+        let ctx = match fat::arch_ctx(header.magic) {
+            Some(ctx) => ctx,
+            None => return Err(error::Error::BadMagic(header.magic as u64)),
+        };
+        let narches = if ctx.le.is_little() { header.nfat_arch.swap_bytes() } else { header.nfat_arch } as usize;
+        // read_at bounds the arches by the size of the file, so a bogus count can't make us allocate much
+        let len = narches.saturating_mul(fat::arch_size(ctx));
+        let bytes = read_at(&mut reader, SIZEOF_FAT_HEADER as u64, len, size)?;
+        let mut arches = Vec::with_capacity(narches);
+        for i in 0..narches {
+            arches.push(FatArch64::parse(&bytes, i * fat::arch_size(ctx), ctx)?);
+        }
+        Ok(LazyMultiArch {
+            reader: reader,
+            size: size,
+            arches: arches,
+        })
+    }
+    /// The fat architecture headers of this container, whether they are 32 or 64-bit
+    pub fn arches(&self) -> &[FatArch64] {
+        &self.arches
+    }
+    fn arch(&self, index: usize) -> error::Result<FatArch64> {
+        match self.arches.get(index) {
+            Some(arch) => Ok(*arch),
+            None => Err(error::Error::Malformed(format!("Requested the {}-th binary, but there are only {} architectures in this container", index, self.arches.len()))),
+        }
+    }
+    /// Reads all the bytes of the `index`-th binary, which can then be parsed with `MachO::parse`
+    pub fn read_arch(&mut self, index: usize) -> error::Result<Vec<u8>> {
+        let arch = self.arch(index)?;
+        read_at(&mut self.reader, arch.offset, arch.size as usize, self.size)
+    }
+    /// Reads just the header and load commands of the `index`-th binary; its reads are bounded by its architecture, so
+    /// they can't reach into the next one
+    pub fn macho(&mut self, index: usize) -> error::Result<LazyMachO<&mut R>> {
+        let arch = self.arch(index)?;
+        let end = match arch.offset.checked_add(arch.size) {
+            Some(end) if end <= self.size => end,
+            _ => return Err(error::Error::Malformed(format!("{:#x} bytes at {:#x} of the {}-th binary are past the end of the data ({:#x})", arch.size, arch.offset, index, self.size))),
+        };
+        LazyMachO::parse_bounded(&mut self.reader, arch.offset, end)
+    }
+    /// Gets the underlying reader back
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
@@ -0,0 +1,68 @@
+//! Checked LEB128 readers for the variable length integers of the dyld info, export trie and function starts.
+//!
+//! Both readers advance `offset` past the integer, and error rather than silently truncate when the integer doesn't fit in
+//! 64 bits, takes more than the 10 bytes a 64-bit integer needs, or runs past the end of `bytes`.
+
+use error;
+
+/// The most bytes a LEB128 encoded 64-bit integer can take
+pub const MAX_LEB128_LEN: usize = 10;
+
+fn next_byte(bytes: &[u8], offset: &mut usize, start: usize, name: &str) -> error::Result<u8> {
+    match bytes.get(*offset) {
+        Some(&byte) => {
+            *offset += 1;
+            Ok(byte)
+        },
+        None => Err(error::Error::Malformed(format!("{} at {:#x} runs past the end of the data ({:#x})", name, start, bytes.len()))),
+    }
+}
+
+/// Reads the unsigned LEB128 at `offset` in `bytes`
+pub fn read_uleb128(bytes: &[u8], offset: &mut usize) -> error::Result<u64> {
+    let start = *offset;
+    let mut result = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = next_byte(bytes, offset, start, "uleb128")?;
+        let payload = (byte & 0x7f) as u64;
+        // the tenth byte only has room for the top bit
+        if shift == 63 && payload > 1 {
+            return Err(error::Error::Malformed(format!("uleb128 at {:#x} overflows 64 bits", start)));
+        }
+        result |= payload << shift;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+        shift += 7;
+        if shift > 63 {
+            return Err(error::Error::Malformed(format!("uleb128 at {:#x} is longer than {} bytes", start, MAX_LEB128_LEN)));
+        }
+    }
+}
+
+/// Reads the signed LEB128 at `offset` in `bytes`
+pub fn read_sleb128(bytes: &[u8], offset: &mut usize) -> error::Result<i64> {
+    let start = *offset;
+    let mut result = 0i64;
+    let mut shift = 0;
+    loop {
+        let byte = next_byte(bytes, offset, start, "sleb128")?;
+        let payload = (byte & 0x7f) as i64;
+        // the tenth byte only has room for the sign bit, which it must repeat
+        if shift == 63 && payload != 0 && payload != 0x7f {
+            return Err(error::Error::Malformed(format!("sleb128 at {:#x} overflows 64 bits", start)));
+        }
+        result |= payload << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if shift < 64 && byte & 0x40 != 0 {
+                result |= -1i64 << shift;
+            }
+            return Ok(result);
+        }
+        if shift > 63 {
+            return Err(error::Error::Malformed(format!("sleb128 at {:#x} is longer than {} bytes", start, MAX_LEB128_LEN)));
+        }
+    }
+}
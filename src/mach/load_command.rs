@@ -1,6 +1,16 @@
 //! Load commands tell the kernel and dynamic linker anything from how to load this binary into memory, what the entry point is, apple specific information, to which libraries it requires for dynamic linking
 
 use error;
+use container;
+use mach::chained_fixups;
+use mach::codesign;
+use mach::constants::cputype;
+use mach::exports;
+use mach::imports;
+use mach::leb;
+use mach::rebases;
+use mach::segment::{self, RelocationIterator};
+use core::ops::{Deref, Range};
 use std::fmt::{self, Display};
 use scroll::{self, ctx, Endian, Pread};
 
@@ -25,10 +35,75 @@ impl Display for LoadCommandHeader {
 
 pub const SIZEOF_LOAD_COMMAND: usize = 8;
 
-pub type LcStr = u32;
+/// The raw `union lc_str` of a load command: the offset of a null-terminated string, relative to the start of the load command
+pub type LcStrOffset = u32;
 
 pub const SIZEOF_LC_STR: usize = 4;
 
+/// A load command string, resolved lazily against the bytes of the load command which owns it.
+///
+/// It derefs to (and displays as) the string, or the empty string if the offset is outside of the command;
+/// use `to_str` to get an error instead.
+#[derive(Clone, Copy)]
+pub struct LcStr<'a> {
+    /// The offset of the string, relative to the start of the load command
+    pub offset: LcStrOffset,
+    cmdsize: u32,
+    cmd_bytes: &'a [u8],
+}
+
+impl<'a> LcStr<'a> {
+    /// Creates the string at `offset` in the load command of size `cmdsize` beginning at the start of `cmd_bytes`
+    pub fn new(cmd_bytes: &'a [u8], cmdsize: u32, offset: LcStrOffset) -> Self {
+        LcStr { offset: offset, cmdsize: cmdsize, cmd_bytes: cmd_bytes }
+    }
+    /// Reads the null-terminated string; it may not begin or extend past `cmdsize`
+    pub fn to_str(&self) -> error::Result<&'a str> {
+        let cmdsize = self.cmdsize as usize;
+        let offset = self.offset as usize;
+        if cmdsize > self.cmd_bytes.len() || offset >= cmdsize {
+            return Err(error::Error::Malformed(format!("lc_str offset {:#x} is outside of load command of size {:#x} ({} bytes available)", offset, cmdsize, self.cmd_bytes.len())));
+        }
+        Ok(self.cmd_bytes[..cmdsize].pread::<&str>(offset)?)
+    }
+    /// The string, or `""` if it is malformed
+    pub fn as_str(&self) -> &'a str {
+        self.to_str().unwrap_or("")
+    }
+}
+
+impl<'a> Deref for LcStr<'a> {
+    type Target = str;
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'a> Display for LcStr<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+impl<'a> fmt::Debug for LcStr<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_str() {
+            Ok(string) => write!(fmt, "LcStr {{ offset: {:#x}, {:?} }}", self.offset, string),
+            Err(_) => write!(fmt, "LcStr {{ offset: {:#x}, <out of bounds> }}", self.offset),
+        }
+    }
+}
+
+/// Reads a fixed-size name, like a segment's or section's 16-byte name, which is NUL padded but not NUL terminated
+/// when the name uses every byte
+pub fn fixed_str(bytes: &[u8]) -> error::Result<&str> {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    match ::core::str::from_utf8(&bytes[..len]) {
+        Ok(name) => Ok(name),
+        Err(_) => Err(error::Error::Malformed(format!("fixed-size name {:?} is not valid UTF-8", &bytes[..len]))),
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
 pub struct Section32 {
@@ -110,7 +185,7 @@ pub const SIZEOF_SEGMENT_COMMAND_32: usize = 56;
 
 impl SegmentCommand32 {
     pub fn name(&self) -> error::Result<&str> {
-        Ok(self.segname.pread::<&str>(0)?)
+        fixed_str(&self.segname)
     }
 }
 
@@ -134,7 +209,7 @@ pub const SIZEOF_SEGMENT_COMMAND_64: usize = 72;
 
 impl SegmentCommand64 {
     pub fn name(&self) -> error::Result<&str> {
-        Ok(self.segname.pread::<&str>(0)?)
+        fixed_str(&self.segname)
     }
 }
 /// Fixed virtual memory shared libraries are identified by two things.  The
@@ -195,7 +270,7 @@ pub const SIZEOF_FVMLIB_COMMAND: usize = 20;
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
 pub struct Dylib {
     /// library's path name
-    pub name: LcStr,
+    pub name: LcStrOffset,
     /// library's build time stamp
     pub timestamp: u32,
     /// library's current version number
@@ -219,6 +294,17 @@ pub struct DylibCommand {
 
 pub const SIZEOF_DYLIB_COMMAND: usize = 20;
 
+impl DylibCommand {
+    /// Returns the library's path name this command refers to; `cmd_bytes` must begin at the start of this load command
+    pub fn name<'a>(&self, cmd_bytes: &'a [u8]) -> error::Result<&'a str> {
+        self.lc_str(cmd_bytes).to_str()
+    }
+    /// Returns the lazily resolved library's path name; `cmd_bytes` must begin at the start of this load command
+    pub fn lc_str<'a>(&self, cmd_bytes: &'a [u8]) -> LcStr<'a> {
+        LcStr::new(cmd_bytes, self.cmdsize, self.dylib.name)
+    }
+}
+
 /// A dynamically linked shared library may be a subframework of an umbrella
 /// framework.  If so it will be linked with "-umbrella umbrella_name" where
 /// Where "umbrella_name" is the name of the umbrella framework. A subframework
@@ -235,11 +321,22 @@ pub struct SubFrameworkCommand {
     /// includes umbrella string
     pub cmdsize: u32,
     /// the umbrella framework name
-    pub umbrella: u32,
+    pub umbrella: LcStrOffset,
 }
 
 pub const SIZEOF_SUB_FRAMEWORK_COMMAND: usize = 12;
 
+impl SubFrameworkCommand {
+    /// Returns the umbrella framework name this command refers to; `cmd_bytes` must begin at the start of this load command
+    pub fn name<'a>(&self, cmd_bytes: &'a [u8]) -> error::Result<&'a str> {
+        self.lc_str(cmd_bytes).to_str()
+    }
+    /// Returns the lazily resolved umbrella framework name; `cmd_bytes` must begin at the start of this load command
+    pub fn lc_str<'a>(&self, cmd_bytes: &'a [u8]) -> LcStr<'a> {
+        LcStr::new(cmd_bytes, self.cmdsize, self.umbrella)
+    }
+}
+
 /// For dynamically linked shared libraries that are subframework of an umbrella
 /// framework they can allow clients other than the umbrella framework or other
 /// subframeworks in the same umbrella framework.  To do this the subframework
@@ -255,11 +352,22 @@ pub struct SubClientCommand {
     /// includes client string
     pub cmdsize: u32,
     /// the client name
-    pub client: LcStr,
+    pub client: LcStrOffset,
 }
 
 pub const SIZEOF_SUB_CLIENT_COMMAND: usize = 12;
 
+impl SubClientCommand {
+    /// Returns the client name this command refers to; `cmd_bytes` must begin at the start of this load command
+    pub fn name<'a>(&self, cmd_bytes: &'a [u8]) -> error::Result<&'a str> {
+        self.lc_str(cmd_bytes).to_str()
+    }
+    /// Returns the lazily resolved client name; `cmd_bytes` must begin at the start of this load command
+    pub fn lc_str<'a>(&self, cmd_bytes: &'a [u8]) -> LcStr<'a> {
+        LcStr::new(cmd_bytes, self.cmdsize, self.client)
+    }
+}
+
 /// A dynamically linked shared library may be a sub_umbrella of an umbrella
 /// framework.  If so it will be linked with "-sub_umbrella umbrella_name" where
 /// Where "umbrella_name" is the name of the sub_umbrella framework.  When
@@ -279,7 +387,7 @@ pub struct SubUmbrellaCommand {
     /// includes sub_umbrella string
     pub cmdsize: u32,
     /// the sub_umbrella framework name
-    pub sub_umbrella: LcStr,
+    pub sub_umbrella: LcStrOffset,
 }
 
 pub const SIZEOF_SUB_UMBRELLA_COMMAND: usize = 12;
@@ -305,7 +413,7 @@ pub struct SubLibraryCommand {
     /// includes sub_library string
     pub cmdsize: u32,
     /// the sub_library name
-    pub sub_library: LcStr,
+    pub sub_library: LcStrOffset,
 }
 
 pub const SIZEOF_SUB_LIBRARY_COMMAND: usize = 12;
@@ -325,27 +433,70 @@ pub struct PreboundDylibCommand {
     /// includes strings
     pub cmdsize: u32,
     /// library's path name
-    pub name: LcStr,
+    pub name: LcStrOffset,
     /// number of modules in library
     pub nmodules: u32,
-    /// bit vector of linked modules
-    // TODO: fixme
-    pub linked_modules: LcStr,
+    /// bit vector of linked modules: the offset, like an lc_str, of `ceil(nmodules / 8)` bytes
+    pub linked_modules: LcStrOffset,
 }
 
 pub const SIZEOF_PREBOUND_DYLIB_COMMAND: usize = 20;
 
+impl PreboundDylibCommand {
+    /// Returns the library's path name this command refers to; `cmd_bytes` must begin at the start of this load command
+    pub fn name<'a>(&self, cmd_bytes: &'a [u8]) -> error::Result<&'a str> {
+        self.lc_str(cmd_bytes).to_str()
+    }
+    /// Returns the lazily resolved library's path name; `cmd_bytes` must begin at the start of this load command
+    pub fn lc_str<'a>(&self, cmd_bytes: &'a [u8]) -> LcStr<'a> {
+        LcStr::new(cmd_bytes, self.cmdsize, self.name)
+    }
+    /// Returns the `ceil(nmodules / 8)` bytes of the bit vector of linked modules; `cmd_bytes` must begin at the start of this load command
+    pub fn linked_modules<'a>(&self, cmd_bytes: &'a [u8]) -> error::Result<&'a [u8]> {
+        let cmdsize = self.cmdsize as usize;
+        let start = self.linked_modules as usize;
+        // ceil(nmodules / 8), without overflowing
+        let end = start + ((self.nmodules as u64 + 7) >> 3) as usize;
+        if cmdsize > cmd_bytes.len() || end > cmdsize {
+            return Err(error::Error::Malformed(format!("linked modules {:#x}..{:#x} are outside of load command of size {:#x} ({} bytes available)", start, end, cmdsize, cmd_bytes.len())));
+        }
+        Ok(&cmd_bytes[start..end])
+    }
+    /// Whether module `n` of the library is bound, i.e., `(linked_modules[n / 8] >> n % 8) & 1`; modules past `nmodules`,
+    /// or whose bits are outside of the command, are not
+    pub fn is_module_linked(&self, cmd_bytes: &[u8], n: u32) -> bool {
+        if n >= self.nmodules {
+            return false;
+        }
+        match self.linked_modules(cmd_bytes) {
+            Ok(bits) => (bits[n as usize / 8] >> (n % 8)) & 1 != 0,
+            Err(_) => false,
+        }
+    }
+}
+
 /// The name of the dynamic linker
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
 pub struct DylinkerCommand {
     pub cmd:     u32,
     pub cmdsize: u32,
-    pub name:    LcStr,
+    pub name:    LcStrOffset,
 }
 
 pub const SIZEOF_DYLINKER_COMMAND: usize = 12;
 
+impl DylinkerCommand {
+    /// Returns the dynamic linker's path name this command refers to; `cmd_bytes` must begin at the start of this load command
+    pub fn name<'a>(&self, cmd_bytes: &'a [u8]) -> error::Result<&'a str> {
+        self.lc_str(cmd_bytes).to_str()
+    }
+    /// Returns the lazily resolved dynamic linker's path name; `cmd_bytes` must begin at the start of this load command
+    pub fn lc_str<'a>(&self, cmd_bytes: &'a [u8]) -> LcStr<'a> {
+        LcStr::new(cmd_bytes, self.cmdsize, self.name)
+    }
+}
+
 /// Thread commands contain machine-specific data structures suitable for
 /// use in the thread state primitives.  The machine specific data structures
 /// follow the struct thread_command as follows.
@@ -365,23 +516,162 @@ pub const SIZEOF_DYLINKER_COMMAND: usize = 12;
 /// This is the same as a LC_THREAD, except that a stack is automatically
 /// created (based on the shell's limit for the stack size).  CommandVariant arguments
 /// and environment variables are copied onto that stack.
-// unimplemented, see machine/thread_status.h for rest of values:
+// see machine/thread_status.h for the rest of the flavors:
 // uint32_t flavor		   flavor of thread state
 // uint32_t count		   count of longs in thread state
 // struct XXX_thread_state state   thread state for this flavor
 // ...
-#[repr(C)]
-#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+#[derive(Debug, Clone)]
 pub struct ThreadCommand {
     /// LC_THREAD or  LC_UNIXTHREAD
     pub cmd:     u32,
     /// total size of this command
     pub cmdsize: u32,
+    /// flavor of the first thread state
+    pub flavor: u32,
+    /// count of longs in the first thread state
+    pub count: u32,
+    /// the first thread state, decoded according to its `flavor` and `count`
+    pub thread_state: ThreadState,
+    /// any flavor, count, and thread state triples which follow the first
+    pub additional_states: Vec<ThreadStateEntry>,
+}
+
+impl<'a> ctx::TryFromCtx<'a, Endian> for ThreadCommand {
+    type Error = ::error::Error;
+    type Size = usize;
+    fn try_from_ctx(bytes: &'a [u8], le: Endian) -> error::Result<(Self, Self::Size)> {
+        let lc = bytes.pread_with::<LoadCommandHeader>(0, le)?;
+        let size = lc.cmdsize as usize;
+        if size > bytes.len() { return Err(error::Error::Malformed(format!("{} has size larger than remainder of binary: {:?}", &lc, bytes.len()))) }
+        let bytes = &bytes[..size];
+        let mut offset = SIZEOF_LOAD_COMMAND;
+        let offset = &mut offset;
+        let first = ThreadStateEntry::parse(bytes, offset, le)?;
+        let mut additional_states = Vec::new();
+        while *offset < size {
+            additional_states.push(ThreadStateEntry::parse(bytes, offset, le)?);
+        }
+        Ok((ThreadCommand {
+            cmd: lc.cmd,
+            cmdsize: lc.cmdsize,
+            flavor: first.flavor,
+            count: first.count,
+            thread_state: first.state,
+            additional_states: additional_states,
+        }, size))
+    }
+}
+
+impl ThreadCommand {
+    /// Returns the instruction pointer (`eip`, `rip`, `pc`, or `srr0`) of the first thread state matching the machine `cputype`.
+    ///
+    /// For an `LC_UNIXTHREAD` this is the entry point of the binary; `None` if no state of a recognized flavor is present
+    pub fn instruction_pointer(&self, cputype: cputype::CpuType) -> Option<u64> {
+        use mach::constants::cputype::*;
+        let states = Some(&self.thread_state).into_iter().chain(self.additional_states.iter().map(|entry| &entry.state));
+        for state in states {
+            match *state {
+                ThreadState::X86(ref state)    if cputype == CPU_TYPE_X86     => return Some(state.eip as u64),
+                ThreadState::X86_64(ref state) if cputype == CPU_TYPE_X86_64  => return Some(state.rip),
+                ThreadState::Arm(ref state)    if cputype == CPU_TYPE_ARM     => return Some(state.pc as u64),
+                ThreadState::Arm64(ref state)  if cputype == CPU_TYPE_ARM64   => return Some(state.pc),
+                ThreadState::Ppc(ref state)    if cputype == CPU_TYPE_POWERPC => return Some(state.srr0 as u64),
+                _ => (),
+            }
+        }
+        None
+    }
+}
+
+/// A single flavor, count, and thread state triple from a thread command
+#[derive(Debug, Clone)]
+pub struct ThreadStateEntry {
+    /// flavor of thread state
     pub flavor: u32,
+    /// count of longs in thread state
     pub count: u32,
-    /// NOTE: this is actually, in classic mach-o style, a semi-tagged union-esque struct, and is _not_ properly handled here for arches other than i386... e.g., this is incorrect for powerpc, armv7, etc.
-    /// TODO: We need to implement a simple getter for the thread state; or even better, a method that simply returns the entry point (which is what we usually want)
-    pub thread_state: I386ThreadState,
+    /// the thread state for this flavor
+    pub state: ThreadState,
+}
+
+impl ThreadStateEntry {
+    /// Parse a flavor, count, and thread state triple from `bytes` at `offset`, which must be bounded by the command's `cmdsize`
+    pub fn parse(bytes: &[u8], offset: &mut usize, le: Endian) -> error::Result<Self> {
+        let flavor: u32 = bytes.gread_with(offset, le)?;
+        let count: u32 = bytes.gread_with(offset, le)?;
+        let start = *offset;
+        let end = (count as usize).checked_mul(4).and_then(|len| start.checked_add(len));
+        match end {
+            Some(end) if end <= bytes.len() => {
+                let state = ThreadState::parse(flavor, count, &bytes[start..end], le)?;
+                *offset = end;
+                Ok(ThreadStateEntry { flavor: flavor, count: count, state: state })
+            },
+            _ => Err(error::Error::Malformed(format!("thread state flavor {} with count {} at offset {:#x} overruns its command", flavor, count, start)))
+        }
+    }
+}
+
+// Thread state flavors, see machine/thread_status.h; note that the flavor values overlap between architectures
+pub const X86_THREAD_STATE32: u32 = 1;
+pub const X86_THREAD_STATE64: u32 = 4;
+pub const X86_THREAD_STATE: u32 = 7;
+pub const ARM_THREAD_STATE: u32 = 1;
+pub const ARM_THREAD_STATE64: u32 = 6;
+pub const PPC_THREAD_STATE: u32 = 1;
+
+// The number of longs in each thread state
+pub const X86_THREAD_STATE32_COUNT: u32 = 16;
+pub const X86_THREAD_STATE64_COUNT: u32 = 42;
+pub const ARM_THREAD_STATE_COUNT: u32 = 17;
+pub const ARM_THREAD_STATE64_COUNT: u32 = 68;
+pub const PPC_THREAD_STATE_COUNT: u32 = 40;
+
+/// A thread state, decoded from its flavor and count
+#[derive(Debug, Clone)]
+pub enum ThreadState {
+    X86(I386ThreadState),
+    X86_64(X86_64ThreadState),
+    Arm(ArmThreadState),
+    Arm64(Arm64ThreadState),
+    Ppc(PpcThreadState),
+    /// A flavor we don't know about, as its raw longs
+    Unknown(Vec<u32>),
+}
+
+impl ThreadState {
+    /// Decode the thread state in `bytes` (exactly `count` longs) using `flavor`.
+    ///
+    /// Since the flavor constants overlap between architectures, the `count` is used to disambiguate them
+    pub fn parse(flavor: u32, count: u32, bytes: &[u8], le: Endian) -> error::Result<Self> {
+        match (flavor, count) {
+            (X86_THREAD_STATE32, X86_THREAD_STATE32_COUNT) => Ok(ThreadState::X86(bytes.pread_with(0, le)?)),
+            (X86_THREAD_STATE64, X86_THREAD_STATE64_COUNT) => Ok(ThreadState::X86_64(bytes.pread_with(0, le)?)),
+            (ARM_THREAD_STATE, ARM_THREAD_STATE_COUNT) => Ok(ThreadState::Arm(bytes.pread_with(0, le)?)),
+            (ARM_THREAD_STATE64, ARM_THREAD_STATE64_COUNT) => Ok(ThreadState::Arm64(bytes.pread_with(0, le)?)),
+            (PPC_THREAD_STATE, PPC_THREAD_STATE_COUNT) => Ok(ThreadState::Ppc(bytes.pread_with(0, le)?)),
+            // the generic x86 flavor is itself prefixed with the flavor and count of the state it contains
+            (X86_THREAD_STATE, _) if count >= 2 => {
+                let offset = &mut 0;
+                let flavor: u32 = bytes.gread_with(offset, le)?;
+                let count: u32 = bytes.gread_with(offset, le)?;
+                match (flavor, count) {
+                    (X86_THREAD_STATE32, X86_THREAD_STATE32_COUNT) | (X86_THREAD_STATE64, X86_THREAD_STATE64_COUNT) => ThreadState::parse(flavor, count, &bytes[*offset..], le),
+                    _ => ThreadState::parse_unknown(bytes, le),
+                }
+            },
+            _ => ThreadState::parse_unknown(bytes, le),
+        }
+    }
+    fn parse_unknown(bytes: &[u8], le: Endian) -> error::Result<Self> {
+        let offset = &mut 0;
+        let mut words = Vec::with_capacity(bytes.len() / 4);
+        while *offset + 4 <= bytes.len() {
+            words.push(bytes.gread_with(offset, le)?);
+        }
+        Ok(ThreadState::Unknown(words))
+    }
 }
 
 /// Main thread state consists of
@@ -409,6 +699,72 @@ pub struct I386ThreadState {
     pub gs: u32,
 }
 
+/// The x86_64 general purpose registers, rip, rflags, and segment registers
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct X86_64ThreadState {
+    pub rax: u64,
+    pub rbx: u64,
+    pub rcx: u64,
+    pub rdx: u64,
+    pub rdi: u64,
+    pub rsi: u64,
+    pub rbp: u64,
+    pub rsp: u64,
+    pub r8: u64,
+    pub r9: u64,
+    pub r10: u64,
+    pub r11: u64,
+    pub r12: u64,
+    pub r13: u64,
+    pub r14: u64,
+    pub r15: u64,
+    pub rip: u64,
+    pub rflags: u64,
+    pub cs: u64,
+    pub fs: u64,
+    pub gs: u64,
+}
+
+/// The 32-bit arm general purpose registers r0-r12, sp, lr, pc, and cpsr
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct ArmThreadState {
+    pub r: [u32; 13],
+    pub sp: u32,
+    pub lr: u32,
+    pub pc: u32,
+    pub cpsr: u32,
+}
+
+/// The arm64 general purpose registers x0-x28, fp, lr, sp, pc, and cpsr
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct Arm64ThreadState {
+    pub x: [u64; 29],
+    pub fp: u64,
+    pub lr: u64,
+    pub sp: u64,
+    pub pc: u64,
+    pub cpsr: u32,
+    pub pad: u32,
+}
+
+/// The 32-bit powerpc srr0 (the program counter), srr1, general purpose, and special purpose registers
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct PpcThreadState {
+    pub srr0: u32,
+    pub srr1: u32,
+    pub r: [u32; 32],
+    pub cr: u32,
+    pub xer: u32,
+    pub lr: u32,
+    pub ctr: u32,
+    pub mq: u32,
+    pub vrsave: u32,
+}
+
 /// The routines command contains the address of the dynamic shared library
 /// initialization routine and an index into the module table for the module
 /// that defines the routine.  Before any modules are used from the library the
@@ -434,6 +790,8 @@ pub struct RoutinesCommand32 {
     pub reserved6:   u32,
 }
 
+pub const SIZEOF_ROUTINES_COMMAND_32: usize = 40;
+
 /// The 64-bit routines command.  Same use as above.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
@@ -454,6 +812,43 @@ pub struct RoutinesCommand64 {
     pub reserved6:    u64,
 }
 
+pub const SIZEOF_ROUTINES_COMMAND_64: usize = 72;
+
+/// Generalized 32/64 bit routines command
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Routines {
+    /// LC_ROUTINES or LC_ROUTINES_64
+    pub cmd:          u32,
+    /// total size of this command
+    pub cmdsize:      u32,
+    /// address of initialization routine
+    pub init_address: u64,
+    /// index into the module table that the init routine is defined in
+    pub init_module:  u64,
+}
+
+impl From<RoutinesCommand32> for Routines {
+    fn from(routines: RoutinesCommand32) -> Self {
+        Routines {
+            cmd:          routines.cmd,
+            cmdsize:      routines.cmdsize,
+            init_address: routines.init_address as u64,
+            init_module:  routines.init_module as u64,
+        }
+    }
+}
+
+impl From<RoutinesCommand64> for Routines {
+    fn from(routines: RoutinesCommand64) -> Self {
+        Routines {
+            cmd:          routines.cmd,
+            cmdsize:      routines.cmdsize,
+            init_address: routines.init_address,
+            init_module:  routines.init_module,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
 pub struct SymtabCommand {
@@ -586,11 +981,78 @@ impl DysymtabCommand {
             nlocrel:        0,
         }
     }
+    /// Returns an iterator over the `nmodtab` entries of the module table in `bytes`
+    pub fn modules<'a>(&self, bytes: &'a [u8], ctx: container::Ctx) -> ModuleIterator<'a> {
+        ModuleIterator {
+            data: bytes,
+            offset: self.modtaboff as usize,
+            count: 0,
+            nmodules: self.nmodtab as usize,
+            ctx: ctx,
+        }
+    }
+    /// Returns an iterator over the `ntoc` entries of the table of contents in `bytes`
+    pub fn table_of_contents<'a>(&self, bytes: &'a [u8], ctx: container::Ctx) -> TableOfContentsIterator<'a> {
+        TableOfContentsIterator {
+            data: bytes,
+            offset: self.tocoff as usize,
+            count: 0,
+            ntoc: self.ntoc as usize,
+            ctx: ctx.le,
+        }
+    }
+    /// Returns an iterator over the `nindirectsyms` symbol table indices of the indirect symbol table in `bytes`;
+    /// an index may instead be `INDIRECT_SYMBOL_LOCAL` and/or `INDIRECT_SYMBOL_ABS`
+    pub fn indirect_symbols<'a>(&self, bytes: &'a [u8], ctx: container::Ctx) -> IndirectSymbolIterator<'a> {
+        IndirectSymbolIterator {
+            data: bytes,
+            offset: self.indirectsymoff as usize,
+            count: 0,
+            nsyms: self.nindirectsyms as usize,
+            ctx: ctx.le,
+        }
+    }
+    /// Returns an iterator over the `nextrel` external relocation entries in `bytes`
+    pub fn external_relocations<'a>(&self, bytes: &'a [u8], ctx: container::Ctx) -> RelocationIterator<'a> {
+        RelocationIterator::new(bytes, self.extreloff as usize, self.nextrel as usize, ctx)
+    }
+    /// Returns an iterator over the `nlocrel` local relocation entries in `bytes`
+    pub fn local_relocations<'a>(&self, bytes: &'a [u8], ctx: container::Ctx) -> RelocationIterator<'a> {
+        RelocationIterator::new(bytes, self.locreloff as usize, self.nlocrel as usize, ctx)
+    }
 }
 
 pub const SIZEOF_DYSYMTAB_COMMAND: usize = 80;
 
-// TODO: unimplemented
+/// An indirect symbol table entry for a defined symbol which was made private
+pub const INDIRECT_SYMBOL_LOCAL: u32 = 0x80000000;
+/// An indirect symbol table entry for an absolute symbol
+pub const INDIRECT_SYMBOL_ABS: u32 = 0x40000000;
+
+/// Iterator over the symbol table indices of the indirect symbol table
+pub struct IndirectSymbolIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    count: usize,
+    nsyms: usize,
+    ctx: scroll::Endian,
+}
+
+impl<'a> Iterator for IndirectSymbolIterator<'a> {
+    type Item = error::Result<u32>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count >= self.nsyms {
+            None
+        } else {
+            self.count += 1;
+            match self.data.gread_with(&mut self.offset, self.ctx) {
+                Ok(res) => Some(Ok(res)),
+                Err(e) => Some(Err(e.into()))
+            }
+        }
+    }
+}
+
 /// a table of contents entry
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
@@ -601,7 +1063,8 @@ pub struct DylibTableOfContents {
     pub module_index: u32,
 }
 
-// TODO: unimplemented
+pub const SIZEOF_DYLIB_TABLE_OF_CONTENTS: usize = 8;
+
 /// a module table entry
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
@@ -636,7 +1099,8 @@ pub struct DylibModule {
     pub objc_module_info_size: u32,
 }
 
-// TODO: unimplemented
+pub const SIZEOF_DYLIB_MODULE: usize = 52;
+
 /// a 64-bit module table entry
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
@@ -673,6 +1137,81 @@ pub struct DylibModule64 {
     pub objc_module_info_addr: u64,
 }
 
+pub const SIZEOF_DYLIB_MODULE_64: usize = 56;
+
+impl From<DylibModule> for DylibModule64 {
+    fn from(module: DylibModule) -> Self {
+        DylibModule64 {
+            module_name: module.module_name,
+            iextdefsym: module.iextdefsym,
+            nextdefsym: module.nextdefsym,
+            irefsym: module.irefsym,
+            nrefsym: module.nrefsym,
+            ilocalsym: module.ilocalsym,
+            nlocalsym: module.nlocalsym,
+            iextrel: module.iextrel,
+            nextrel: module.nextrel,
+            iinit_iterm: module.iinit_iterm,
+            ninit_nterm: module.ninit_nterm,
+            objc_module_info_size: module.objc_module_info_size,
+            objc_module_info_addr: module.objc_module_info_addr as u64,
+        }
+    }
+}
+
+/// Iterator over the module table of a dylib; 32-bit modules are widened to `DylibModule64`
+pub struct ModuleIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    count: usize,
+    nmodules: usize,
+    ctx: container::Ctx,
+}
+
+impl<'a> Iterator for ModuleIterator<'a> {
+    type Item = error::Result<DylibModule64>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count >= self.nmodules {
+            None
+        } else {
+            self.count += 1;
+            let module = if self.ctx.is_big() {
+                self.data.gread_with::<DylibModule64>(&mut self.offset, self.ctx.le)
+            } else {
+                self.data.gread_with::<DylibModule>(&mut self.offset, self.ctx.le).map(DylibModule64::from)
+            };
+            match module {
+                Ok(res) => Some(Ok(res)),
+                Err(e) => Some(Err(e.into()))
+            }
+        }
+    }
+}
+
+/// Iterator over the table of contents of a dylib
+pub struct TableOfContentsIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    count: usize,
+    ntoc: usize,
+    ctx: scroll::Endian,
+}
+
+impl<'a> Iterator for TableOfContentsIterator<'a> {
+    type Item = error::Result<DylibTableOfContents>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count >= self.ntoc {
+            None
+        } else {
+            self.count += 1;
+            match self.data.gread_with(&mut self.offset, self.ctx) {
+                Ok(res) => Some(Ok(res)),
+                Err(e) => Some(Err(e.into()))
+            }
+        }
+    }
+}
+
 /// The entries in the reference symbol table are used when loading the module
 /// (both by the static and dynamic link editors) and if the module is unloaded
 /// or replaced.  Therefore all external symbols (defined and undefined) are
@@ -682,10 +1221,30 @@ pub struct DylibModule64 {
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
 pub struct DylibReference {
-    /// 24 bits bit-field index into the symbol table
-    pub isym: [u8; 24],
+    /// the 24 bit index into the symbol table (low bits), and 8 bits of flags to indicate the type of reference (high bits)
+    pub isym_flags: u32,
+}
+
+pub const SIZEOF_DYLIB_REFERENCE: usize = 4;
+
+impl DylibReference {
+    /// index into the symbol table
+    pub fn isym(&self) -> u32 {
+        self.isym_flags & 0x00ffffff
+    }
     /// flags to indicate the type of reference
-    pub flags: u64,
+    pub fn flags(&self) -> u8 {
+        (self.isym_flags >> 24) as u8
+    }
+    /// Converts this reference from a big-endian binary, where the `isym_flags` bitfields are allocated starting from
+    /// the most significant bit, to the little-endian layout the accessors of this struct decode
+    pub fn to_little_endian_bitfields(self) -> Self {
+        let isym = self.isym_flags >> 8;
+        let flags = self.isym_flags & 0xff;
+        DylibReference {
+            isym_flags: isym | flags << 24,
+        }
+    }
 }
 
 /// The twolevel_hints_command contains the offset and number of hints in the
@@ -703,6 +1262,20 @@ pub struct TwolevelHintsCommand {
     pub nhints: u32,
 }
 
+impl TwolevelHintsCommand {
+    /// Returns an iterator over the `nhints` hints of the hint table in `bytes`; the hints of a big-endian binary are
+    /// converted to the little-endian bitfield layout
+    pub fn hints<'a>(&self, bytes: &'a [u8], ctx: container::Ctx) -> TwolevelHintIterator<'a> {
+        TwolevelHintIterator {
+            data: bytes,
+            offset: self.offset as usize,
+            count: 0,
+            nhints: self.nhints as usize,
+            ctx: ctx.le,
+        }
+    }
+}
+
 /// The entries in the two-level namespace lookup hints table are twolevel_hint
 /// structs.  These provide hints to the dynamic link editor where to start
 /// looking for an undefined symbol in a two-level namespace image.  The
@@ -720,10 +1293,55 @@ pub struct TwolevelHintsCommand {
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
 pub struct TwolevelHint {
+    /// the 8 bit index into the sub images (low bits), and the 24 bit index into the table of contents (high bits)
+    pub isub_image_itoc: u32,
+}
+
+pub const SIZEOF_TWOLEVEL_HINT: usize = 4;
+
+impl TwolevelHint {
     /// index into the sub images
-    pub isub_image: u64,
-    /// 24 bit field index into the table of contents
-    pub itoc: [u8; 24],
+    pub fn isub_image(&self) -> u8 {
+        self.isub_image_itoc as u8
+    }
+    /// index into the table of contents
+    pub fn itoc(&self) -> u32 {
+        self.isub_image_itoc >> 8
+    }
+    /// Converts this hint from a big-endian binary, where the `isub_image_itoc` bitfields are allocated starting from
+    /// the most significant bit, to the little-endian layout the accessors of this struct decode
+    pub fn to_little_endian_bitfields(self) -> Self {
+        let isub_image = self.isub_image_itoc >> 24;
+        let itoc = self.isub_image_itoc & 0x00ffffff;
+        TwolevelHint {
+            isub_image_itoc: isub_image | itoc << 8,
+        }
+    }
+}
+
+/// Iterator over the hints of a two-level namespace lookup hints table
+pub struct TwolevelHintIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    count: usize,
+    nhints: usize,
+    ctx: scroll::Endian,
+}
+
+impl<'a> Iterator for TwolevelHintIterator<'a> {
+    type Item = error::Result<TwolevelHint>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count >= self.nhints {
+            None
+        } else {
+            self.count += 1;
+            match self.data.gread_with::<TwolevelHint>(&mut self.offset, self.ctx) {
+                Ok(hint) if self.ctx.is_little() => Some(Ok(hint)),
+                Ok(hint) => Some(Ok(hint.to_little_endian_bitfields())),
+                Err(e) => Some(Err(e.into()))
+            }
+        }
+    }
 }
 
 /// The prebind_cksum_command contains the value of the original check sum for
@@ -761,6 +1379,19 @@ pub struct UuidCommand {
 
 pub const SIZEOF_UUID_COMMAND: usize = 24;
 
+/// Prints the uuid in its canonical, uppercase `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX` form, as `dwarfdump --uuid` does
+impl Display for UuidCommand {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, byte) in self.uuid.iter().enumerate() {
+            if i == 4 || i == 6 || i == 8 || i == 10 {
+                write!(fmt, "-")?;
+            }
+            write!(fmt, "{:02X}", byte)?;
+        }
+        Ok(())
+    }
+}
+
 /// The rpath_command contains a path which at runtime should be added to
 /// the current run path used to find @rpath prefixed dylibs.
 #[repr(C)]
@@ -771,11 +1402,22 @@ pub struct RpathCommand {
     /// includes string
     pub cmdsize: u32,
     /// path to add to run path
-    pub path: LcStr,
+    pub path: LcStrOffset,
 }
 
 pub const SIZEOF_RPATH_COMMAND: usize = 12;
 
+impl RpathCommand {
+    /// Returns the run path this command refers to; `cmd_bytes` must begin at the start of this load command
+    pub fn name<'a>(&self, cmd_bytes: &'a [u8]) -> error::Result<&'a str> {
+        self.lc_str(cmd_bytes).to_str()
+    }
+    /// Returns the lazily resolved run path; `cmd_bytes` must begin at the start of this load command
+    pub fn lc_str<'a>(&self, cmd_bytes: &'a [u8]) -> LcStr<'a> {
+        LcStr::new(cmd_bytes, self.cmdsize, self.path)
+    }
+}
+
 /// The linkedit_data_command contains the offsets and sizes of a blob
 /// of data in the __LINKEDIT segment.
 #[repr(C)]
@@ -793,6 +1435,83 @@ pub struct LinkeditDataCommand {
 
 pub const SIZEOF_LINKEDIT_DATA_COMMAND: usize = 16;
 
+/// Iterator over the function start addresses of an `LC_FUNCTION_STARTS` command
+pub struct FunctionStartsIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    end: usize,
+    address: u64,
+    done: bool,
+}
+
+impl<'a> Iterator for FunctionStartsIterator<'a> {
+    type Item = error::Result<u64>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.offset >= self.end {
+            return None;
+        }
+        if self.end > self.data.len() {
+            self.done = true;
+            return Some(Err(error::Error::Malformed(format!("function starts data ends at {:#x}, past the end of the binary ({:#x})", self.end, self.data.len()))));
+        }
+        match leb::read_uleb128(&self.data[..self.end], &mut self.offset) {
+            Ok(0) => {
+                self.done = true;
+                None
+            },
+            Ok(delta) => {
+                self.address = self.address.wrapping_add(delta);
+                Some(Ok(self.address))
+            },
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl LinkeditDataCommand {
+    /// Returns an iterator over the `DataInCodeEntry`s this `LC_DATA_IN_CODE` command points to in `bytes`
+    pub fn data_in_code<'a>(&self, bytes: &'a [u8], ctx: container::Ctx) -> DataInCodeIterator<'a> {
+        DataInCodeIterator {
+            data: bytes,
+            offset: self.dataoff as usize,
+            count: 0,
+            nentries: self.datasize as usize / SIZEOF_DATA_IN_CODE_ENTRY,
+            ctx: ctx.le,
+        }
+    }
+    /// Returns an iterator over the absolute addresses of the functions this `LC_FUNCTION_STARTS` command
+    /// encodes in `bytes`, as ULEB128 deltas starting from the `__TEXT` segment's `text_vmaddr`
+    pub fn function_starts<'a>(&self, bytes: &'a [u8], text_vmaddr: u64) -> FunctionStartsIterator<'a> {
+        FunctionStartsIterator {
+            data: bytes,
+            offset: self.dataoff as usize,
+            end: self.dataoff as usize + self.datasize as usize,
+            address: text_vmaddr,
+            done: false,
+        }
+    }
+    /// Parses the header of the chained fixups this `LC_DYLD_CHAINED_FIXUPS` command points to in `bytes`
+    pub fn chained_fixups<'a>(&self, bytes: &'a [u8], le: Endian) -> error::Result<chained_fixups::ChainedFixups<'a>> {
+        chained_fixups::ChainedFixups::parse(bytes, self, le)
+    }
+    /// Returns the `datasize` bytes at `dataoff` in `bytes` this command points to, erroring if they are not all in `bytes`
+    pub fn data<'a>(&self, bytes: &'a [u8]) -> error::Result<&'a [u8]> {
+        let start = self.dataoff as usize;
+        let data = start.checked_add(self.datasize as usize).and_then(|end| bytes.get(start..end));
+        match data {
+            Some(data) => Ok(data),
+            None => Err(error::Error::Malformed(format!("{} data {:#x} + {:#x} is outside of the binary (size {:#x})", cmd_to_str(self.cmd), self.dataoff, self.datasize, bytes.len()))),
+        }
+    }
+    /// Parses the embedded code signature this `LC_CODE_SIGNATURE` command points to in `bytes`
+    pub fn code_signature<'a>(&self, bytes: &'a [u8]) -> error::Result<codesign::CodeSignature<'a>> {
+        codesign::CodeSignature::parse(self.data(bytes)?)
+    }
+}
+
 /// The encryption_info_command contains the file offset and size of an
 /// of an encrypted segment.
 #[repr(C)]
@@ -812,6 +1531,17 @@ pub struct EncryptionInfoCommand32 {
 
 pub const SIZEOF_ENCRYPTION_INFO_COMMAND_32: usize = 20;
 
+impl EncryptionInfoCommand32 {
+    /// Whether the range this command describes is encrypted, i.e., whether `cryptid` is non-zero
+    pub fn is_encrypted(&self) -> bool {
+        self.cryptid != 0
+    }
+    /// The file range this command describes
+    pub fn encrypted_range(&self) -> Range<u64> {
+        self.cryptoff as u64..self.cryptoff as u64 + self.cryptsize as u64
+    }
+}
+
 /// The encryption_info_command_64 contains the file offset and size of an
 /// of an encrypted segment (for use in x86_64 targets).
 #[repr(C)]
@@ -833,10 +1563,21 @@ pub struct EncryptionInfoCommand64 {
 
 pub const SIZEOF_ENCRYPTION_INFO_COMMAND_64: usize = 24;
 
+impl EncryptionInfoCommand64 {
+    /// Whether the range this command describes is encrypted, i.e., whether `cryptid` is non-zero
+    pub fn is_encrypted(&self) -> bool {
+        self.cryptid != 0
+    }
+    /// The file range this command describes
+    pub fn encrypted_range(&self) -> Range<u64> {
+        self.cryptoff as u64..self.cryptoff as u64 + self.cryptsize as u64
+    }
+}
+
 /// The version_min_command contains the min OS version on which this
 /// binary was built to run.
 ///
-/// LC_VERSION_MIN_MACOSX or LC_VERSION_MIN_IPHONEOS
+/// LC_VERSION_MIN_MACOSX, LC_VERSION_MIN_IPHONEOS, LC_VERSION_MIN_TVOS or LC_VERSION_MIN_WATCHOS
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
 pub struct VersionMinCommand {
@@ -857,10 +1598,241 @@ impl VersionMinCommand {
             sdk: 0,
         }
     }
+    /// The platform this command's `cmd` is for
+    pub fn platform(&self) -> Platform {
+        match self.cmd {
+            LC_VERSION_MIN_MACOSX => Platform::MacOS,
+            LC_VERSION_MIN_IPHONEOS => Platform::IOS,
+            LC_VERSION_MIN_TVOS => Platform::TvOS,
+            LC_VERSION_MIN_WATCHOS => Platform::WatchOS,
+            cmd => Platform::Unknown(cmd),
+        }
+    }
+    /// The minimum OS version as the tuple (X, Y, Z)
+    pub fn version_tuple(&self) -> (u16, u8, u8) {
+        unpack_version(self.version)
+    }
+    /// The SDK version as the tuple (X, Y, Z)
+    pub fn sdk_tuple(&self) -> (u16, u8, u8) {
+        unpack_version(self.sdk)
+    }
+}
+
+impl Display for VersionMinCommand {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let (x, y, z) = self.version_tuple();
+        let (sdk_x, sdk_y, sdk_z) = self.sdk_tuple();
+        write!(fmt, "{}.{}.{} (sdk {}.{}.{})", x, y, z, sdk_x, sdk_y, sdk_z)
+    }
 }
 
 pub const SIZEOF_VERSION_MIN_COMMAND: usize = 16;
 
+/// The build_version_command contains the min OS version on which this
+/// binary was built to run for its platform, and is followed by `ntools`
+/// build_tool_version entries.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct BuildVersionCommand {
+    /// LC_BUILD_VERSION
+    pub cmd: u32,
+    /// sizeof(struct build_version_command) plus ntools * sizeof(struct build_tool_version)
+    pub cmdsize: u32,
+    /// platform
+    pub platform: u32,
+    /// X.Y.Z is encoded in nibbles xxxx.yy.zz
+    pub minos: u32,
+    /// X.Y.Z is encoded in nibbles xxxx.yy.zz
+    pub sdk: u32,
+    /// number of tool entries following this
+    pub ntools: u32,
+}
+
+pub const SIZEOF_BUILD_VERSION_COMMAND: usize = 24;
+
+impl BuildVersionCommand {
+    /// The platform this binary was built for
+    pub fn platform(&self) -> Platform {
+        Platform::from(self.platform)
+    }
+    /// The minimum OS version as the tuple (X, Y, Z)
+    pub fn minos_tuple(&self) -> (u16, u8, u8) {
+        unpack_version(self.minos)
+    }
+    /// The SDK version as the tuple (X, Y, Z)
+    pub fn sdk_tuple(&self) -> (u16, u8, u8) {
+        unpack_version(self.sdk)
+    }
+    /// Returns an iterator over the `ntools` tool entries following this command; `cmd_bytes` must begin at the start of this load command
+    pub fn tools<'a>(&self, cmd_bytes: &'a [u8], le: Endian) -> BuildToolIterator<'a> {
+        let cmdsize = ::std::cmp::min(self.cmdsize as usize, cmd_bytes.len());
+        BuildToolIterator {
+            data: &cmd_bytes[..cmdsize],
+            offset: SIZEOF_BUILD_VERSION_COMMAND,
+            count: 0,
+            ntools: self.ntools as usize,
+            ctx: le,
+        }
+    }
+}
+
+/// A tool, and the version of it, used to build the binary
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct BuildToolVersion {
+    /// enum for the tool
+    pub tool: u32,
+    /// version number of the tool
+    pub version: u32,
+}
+
+impl BuildToolVersion {
+    /// The tool this is the version of
+    pub fn tool_kind(&self) -> Tool {
+        Tool::from(self.tool)
+    }
+}
+
+pub const SIZEOF_BUILD_TOOL_VERSION: usize = 8;
+
+pub const TOOL_CLANG: u32 = 1;
+pub const TOOL_SWIFT: u32 = 2;
+pub const TOOL_LD: u32 = 3;
+pub const TOOL_LLD: u32 = 4;
+
+/// A tool used to build a binary, from the `tool` of a `BuildToolVersion`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Clang,
+    Swift,
+    Ld,
+    Lld,
+    Unknown(u32),
+}
+
+impl From<u32> for Tool {
+    fn from(tool: u32) -> Self {
+        match tool {
+            TOOL_CLANG => Tool::Clang,
+            TOOL_SWIFT => Tool::Swift,
+            TOOL_LD => Tool::Ld,
+            TOOL_LLD => Tool::Lld,
+            _ => Tool::Unknown(tool),
+        }
+    }
+}
+
+impl Display for Tool {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Tool::Clang => write!(fmt, "clang"),
+            Tool::Swift => write!(fmt, "swift"),
+            Tool::Ld => write!(fmt, "ld"),
+            Tool::Lld => write!(fmt, "lld"),
+            Tool::Unknown(tool) => write!(fmt, "unknown tool {}", tool),
+        }
+    }
+}
+
+/// Iterator over the `BuildToolVersion`s of an `LC_BUILD_VERSION` command
+pub struct BuildToolIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    count: usize,
+    ntools: usize,
+    ctx: scroll::Endian,
+}
+
+impl<'a> Iterator for BuildToolIterator<'a> {
+    type Item = error::Result<BuildToolVersion>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count >= self.ntools {
+            None
+        } else {
+            self.count += 1;
+            match self.data.gread_with(&mut self.offset, self.ctx) {
+                Ok(res) => Some(Ok(res)),
+                Err(e) => Some(Err(e.into()))
+            }
+        }
+    }
+}
+
+pub const PLATFORM_MACOS: u32 = 1;
+pub const PLATFORM_IOS: u32 = 2;
+pub const PLATFORM_TVOS: u32 = 3;
+pub const PLATFORM_WATCHOS: u32 = 4;
+pub const PLATFORM_BRIDGEOS: u32 = 5;
+pub const PLATFORM_MACCATALYST: u32 = 6;
+pub const PLATFORM_IOSSIMULATOR: u32 = 7;
+pub const PLATFORM_TVOSSIMULATOR: u32 = 8;
+pub const PLATFORM_WATCHOSSIMULATOR: u32 = 9;
+pub const PLATFORM_DRIVERKIT: u32 = 10;
+
+/// The platform a binary was built for, from the `platform` of an `LC_BUILD_VERSION`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    MacOS,
+    IOS,
+    TvOS,
+    WatchOS,
+    BridgeOS,
+    MacCatalyst,
+    IOSSimulator,
+    TvOSSimulator,
+    WatchOSSimulator,
+    DriverKit,
+    Unknown(u32),
+}
+
+impl Platform {
+    /// Whether this is one of the simulator platforms
+    pub fn is_simulator(&self) -> bool {
+        *self == Platform::IOSSimulator || *self == Platform::TvOSSimulator || *self == Platform::WatchOSSimulator
+    }
+}
+
+impl From<u32> for Platform {
+    fn from(platform: u32) -> Self {
+        match platform {
+            PLATFORM_MACOS => Platform::MacOS,
+            PLATFORM_IOS => Platform::IOS,
+            PLATFORM_TVOS => Platform::TvOS,
+            PLATFORM_WATCHOS => Platform::WatchOS,
+            PLATFORM_BRIDGEOS => Platform::BridgeOS,
+            PLATFORM_MACCATALYST => Platform::MacCatalyst,
+            PLATFORM_IOSSIMULATOR => Platform::IOSSimulator,
+            PLATFORM_TVOSSIMULATOR => Platform::TvOSSimulator,
+            PLATFORM_WATCHOSSIMULATOR => Platform::WatchOSSimulator,
+            PLATFORM_DRIVERKIT => Platform::DriverKit,
+            _ => Platform::Unknown(platform),
+        }
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Platform::MacOS => write!(fmt, "macOS"),
+            Platform::IOS => write!(fmt, "iOS"),
+            Platform::TvOS => write!(fmt, "tvOS"),
+            Platform::WatchOS => write!(fmt, "watchOS"),
+            Platform::BridgeOS => write!(fmt, "bridgeOS"),
+            Platform::MacCatalyst => write!(fmt, "Mac Catalyst"),
+            Platform::IOSSimulator => write!(fmt, "iOS Simulator"),
+            Platform::TvOSSimulator => write!(fmt, "tvOS Simulator"),
+            Platform::WatchOSSimulator => write!(fmt, "watchOS Simulator"),
+            Platform::DriverKit => write!(fmt, "DriverKit"),
+            Platform::Unknown(platform) => write!(fmt, "unknown platform {}", platform),
+        }
+    }
+}
+
+/// Unpacks a version X.Y.Z encoded in nibbles xxxx.yy.zz
+fn unpack_version(version: u32) -> (u16, u8, u8) {
+    ((version >> 16) as u16, (version >> 8) as u8, version as u8)
+}
+
 #[repr(C)]
 #[derive(Default, Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
 pub struct DyldInfoCommand {
@@ -892,6 +1864,23 @@ pub struct DyldInfoCommand {
 
 pub const SIZEOF_DYLIB_INFO_COMMAND: usize = 48;
 
+impl DyldInfoCommand {
+    /// Walks the export trie this command points to in `bytes`, using `libs` (indexed by library ordinal) to resolve re-exports
+    pub fn exports<'a>(&self, bytes: &'a [u8], libs: &[&'a str]) -> error::Result<Vec<exports::Export<'a>>> {
+        exports::ExportTrie::new(bytes, self).exports(libs)
+    }
+    /// Runs the regular, weak, and lazy bind opcode streams this command points to in `bytes`, checking each bind is
+    /// within the file contents of the `segments` of the binary
+    pub fn binds<'a>(&self, bytes: &'a [u8], segments: &[segment::Segment], ctx: container::Ctx) -> error::Result<Vec<imports::Bind<'a>>> {
+        imports::BindInterpreter::new(bytes, self).binds(segments, &ctx)
+    }
+    /// Runs the rebase opcode stream this command points to in `bytes`, checking each rebase is within the file contents
+    /// of the `segments` of the binary
+    pub fn rebases(&self, bytes: &[u8], segments: &[segment::Segment], ctx: container::Ctx) -> error::Result<Vec<rebases::Rebase>> {
+        rebases::RebaseInterpreter::new(bytes, self).rebases(segments, &ctx)
+    }
+}
+
 /// The linker_option_command contains linker options embedded in object files.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
@@ -905,6 +1894,85 @@ pub struct LinkerOptionCommand {
 
 pub const SIZEOF_LINKER_OPTION_COMMAND: usize = 12;
 
+impl LinkerOptionCommand {
+    /// Returns the `count` linker options following this command; `cmd_bytes` must begin at the start of this load command
+    pub fn strings<'a>(&self, cmd_bytes: &'a [u8]) -> error::Result<Vec<&'a str>> {
+        let cmdsize = self.cmdsize as usize;
+        if cmdsize > cmd_bytes.len() {
+            return Err(error::Error::Malformed(format!("linker option command size {:#x} is larger than the {} bytes available", cmdsize, cmd_bytes.len())));
+        }
+        let bytes = &cmd_bytes[..cmdsize];
+        let mut offset = SIZEOF_LINKER_OPTION_COMMAND;
+        let mut strings = Vec::with_capacity(self.count as usize);
+        for i in 0..self.count {
+            let len = bytes.get(offset..).and_then(|rest| rest.iter().position(|&b| b == 0));
+            let len = match len {
+                Some(len) => len,
+                None => return Err(error::Error::Malformed(format!("only {} of {} linker option strings fit in load command of size {:#x}", i, self.count, cmdsize))),
+            };
+            strings.push(bytes.pread::<&str>(offset)?);
+            offset += len + 1;
+        }
+        Ok(strings)
+    }
+}
+
+/// The note_command describes a region of arbitrary data included in the file, e.g. the thread and
+/// memory descriptions in a core dump; its meaning is given by its `data_owner`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct NoteCommand {
+    /// LC_NOTE
+    pub cmd: u32,
+    pub cmdsize: u32,
+    /// owner name for this LC_NOTE
+    pub data_owner: [u8; 16],
+    /// file offset of this data
+    pub offset: u64,
+    /// length of data region
+    pub size: u64,
+}
+
+pub const SIZEOF_NOTE_COMMAND: usize = 40;
+
+impl NoteCommand {
+    /// The name of the owner of this note's data
+    pub fn data_owner(&self) -> error::Result<&str> {
+        fixed_str(&self.data_owner)
+    }
+}
+
+/// The fileset_entry_command describes a Mach-O image embedded in an MH_FILESET file, e.g. a kext in a kernelcache
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pread, Pwrite, SizeWith)]
+pub struct FilesetEntryCommand {
+    /// LC_FILESET_ENTRY
+    pub cmd: u32,
+    /// includes entry_id string
+    pub cmdsize: u32,
+    /// memory address of the entry
+    pub vmaddr: u64,
+    /// file offset of the entry
+    pub fileoff: u64,
+    /// contained entry id
+    pub entry_id: LcStrOffset,
+    /// reserved
+    pub reserved: u32,
+}
+
+pub const SIZEOF_FILESET_ENTRY_COMMAND: usize = 32;
+
+impl FilesetEntryCommand {
+    /// Returns the id of the entry this command refers to; `cmd_bytes` must begin at the start of this load command
+    pub fn entry_id<'a>(&self, cmd_bytes: &'a [u8]) -> error::Result<&'a str> {
+        self.lc_str(cmd_bytes).to_str()
+    }
+    /// Returns the lazily resolved id of the entry; `cmd_bytes` must begin at the start of this load command
+    pub fn lc_str<'a>(&self, cmd_bytes: &'a [u8]) -> LcStr<'a> {
+        LcStr::new(cmd_bytes, self.cmdsize, self.entry_id)
+    }
+}
+
 /// The symseg_command contains the offset and size of the GNU style
 /// symbol table information as described in the header file <symseg.h>.
 /// The symbol roots of the symbol segments must also be aligned properly
@@ -954,7 +2022,7 @@ pub struct FvmfileCommand {
     /// includes pathname string
     pub cmdsize: u32,
     /// files pathname
-    pub name: LcStr,
+    pub name: LcStrOffset,
     /// files virtual address
     pub header_addr: u32,
 }
@@ -990,6 +2058,32 @@ pub struct SourceVersionCommand {
     pub version: u64,
 }
 
+impl SourceVersionCommand {
+    /// The source version as the tuple (A, B, C, D, E)
+    pub fn version_tuple(&self) -> (u32, u16, u16, u16, u16) {
+        let version = self.version;
+        ((version >> 40) as u32,
+         ((version >> 30) & 0x3ff) as u16,
+         ((version >> 20) & 0x3ff) as u16,
+         ((version >> 10) & 0x3ff) as u16,
+         (version & 0x3ff) as u16)
+    }
+}
+
+impl Display for SourceVersionCommand {
+    /// Prints `A.B.C.D.E`, without any trailing zero components
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let (a, b, c, d, e) = self.version_tuple();
+        let rest = [b, c, d, e];
+        let len = rest.iter().rposition(|&component| component != 0).map_or(0, |i| i + 1);
+        write!(fmt, "{}", a)?;
+        for component in &rest[..len] {
+            write!(fmt, ".{}", component)?;
+        }
+        Ok(())
+    }
+}
+
 /// The LC_DATA_IN_CODE load commands uses a linkedit_data_command
 /// to point to an array of data_in_code_entry entries. Each entry
 /// describes a range of data in a code section.
@@ -1004,6 +2098,97 @@ pub struct DataInCodeEntry {
     pub kind: u16,
 }
 
+pub const SIZEOF_DATA_IN_CODE_ENTRY: usize = 8;
+
+pub const DICE_KIND_DATA: u16 = 0x0001;
+pub const DICE_KIND_JUMP_TABLE8: u16 = 0x0002;
+pub const DICE_KIND_JUMP_TABLE16: u16 = 0x0003;
+pub const DICE_KIND_JUMP_TABLE32: u16 = 0x0004;
+pub const DICE_KIND_ABS_JUMP_TABLE32: u16 = 0x0005;
+
+/// What a `DataInCodeEntry`'s range of a code section holds, from its `kind`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceKind {
+    /// Plain data, e.g. a literal pool
+    Data,
+    /// A jump table of 1 byte entries
+    JumpTable8,
+    /// A jump table of 2 byte entries
+    JumpTable16,
+    /// A jump table of 4 byte entries
+    JumpTable32,
+    /// A jump table of 4 byte absolute addresses
+    AbsJumpTable32,
+    Unknown(u16),
+}
+
+impl From<u16> for DiceKind {
+    fn from(kind: u16) -> Self {
+        match kind {
+            DICE_KIND_DATA => DiceKind::Data,
+            DICE_KIND_JUMP_TABLE8 => DiceKind::JumpTable8,
+            DICE_KIND_JUMP_TABLE16 => DiceKind::JumpTable16,
+            DICE_KIND_JUMP_TABLE32 => DiceKind::JumpTable32,
+            DICE_KIND_ABS_JUMP_TABLE32 => DiceKind::AbsJumpTable32,
+            _ => DiceKind::Unknown(kind),
+        }
+    }
+}
+
+impl DiceKind {
+    /// The name of this kind's `DICE_KIND_*` constant
+    pub fn to_str(&self) -> &'static str {
+        match *self {
+            DiceKind::Data => "DICE_KIND_DATA",
+            DiceKind::JumpTable8 => "DICE_KIND_JUMP_TABLE8",
+            DiceKind::JumpTable16 => "DICE_KIND_JUMP_TABLE16",
+            DiceKind::JumpTable32 => "DICE_KIND_JUMP_TABLE32",
+            DiceKind::AbsJumpTable32 => "DICE_KIND_ABS_JUMP_TABLE32",
+            DiceKind::Unknown(_) => "DICE_KIND_UNKNOWN",
+        }
+    }
+    /// The size in bytes of each entry of a jump table of this kind, or `None` if this isn't a jump table
+    pub fn element_size(&self) -> Option<usize> {
+        match *self {
+            DiceKind::JumpTable8 => Some(1),
+            DiceKind::JumpTable16 => Some(2),
+            DiceKind::JumpTable32 | DiceKind::AbsJumpTable32 => Some(4),
+            DiceKind::Data | DiceKind::Unknown(_) => None,
+        }
+    }
+}
+
+impl DataInCodeEntry {
+    /// The kind of data this entry's range holds, decoded from its raw `kind`
+    pub fn kind_enum(&self) -> DiceKind {
+        DiceKind::from(self.kind)
+    }
+}
+
+/// Iterator over the `DataInCodeEntry`s of an `LC_DATA_IN_CODE` command
+pub struct DataInCodeIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    count: usize,
+    nentries: usize,
+    ctx: scroll::Endian,
+}
+
+impl<'a> Iterator for DataInCodeIterator<'a> {
+    type Item = error::Result<DataInCodeEntry>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count >= self.nentries {
+            None
+        } else {
+            self.count += 1;
+            match self.data.gread_with(&mut self.offset, self.ctx) {
+                Ok(res) => Some(Ok(res)),
+                Err(e) => Some(Err(e.into()))
+            }
+        }
+    }
+}
+
 ///////////////////////////////////////
 // Constants, et. al
 ///////////////////////////////////////
@@ -1015,6 +2200,9 @@ pub const LC_REEXPORT_DYLIB: u32 = 0x1f | LC_REQ_DYLD;
 pub const LC_DYLD_INFO_ONLY: u32 = 0x22 | LC_REQ_DYLD;
 pub const LC_LOAD_UPWARD_DYLIB: u32 = 0x23 | LC_REQ_DYLD;
 pub const LC_MAIN: u32 = 0x28 | LC_REQ_DYLD;
+pub const LC_DYLD_EXPORTS_TRIE: u32 = 0x33 | LC_REQ_DYLD;
+pub const LC_DYLD_CHAINED_FIXUPS: u32 = 0x34 | LC_REQ_DYLD;
+pub const LC_FILESET_ENTRY: u32 = 0x35 | LC_REQ_DYLD;
 pub const LC_SEGMENT: u32 = 0x1;
 pub const LC_SYMTAB: u32 = 0x2;
 pub const LC_SYMSEG: u32 = 0x3;
@@ -1056,6 +2244,10 @@ pub const LC_DYLIB_CODE_SIGN_DRS: u32 = 0x2B;
 pub const LC_ENCRYPTION_INFO_64: u32 = 0x2C;
 pub const LC_LINKER_OPTION: u32 = 0x2D;
 pub const LC_LINKER_OPTIMIZATION_HINT: u32 = 0x2E;
+pub const LC_VERSION_MIN_TVOS: u32 = 0x2F;
+pub const LC_VERSION_MIN_WATCHOS: u32 = 0x30;
+pub const LC_NOTE: u32 = 0x31;
+pub const LC_BUILD_VERSION: u32 = 0x32;
 
 pub fn cmd_to_str(cmd: u32) -> &'static str {
     match cmd {
@@ -1106,6 +2298,13 @@ pub fn cmd_to_str(cmd: u32) -> &'static str {
         LC_ENCRYPTION_INFO_64 => "LC_ENCRYPTION_INFO_64",
         LC_LINKER_OPTION => "LC_LINKER_OPTION",
         LC_LINKER_OPTIMIZATION_HINT => "LC_LINKER_OPTIMIZATION_HINT",
+        LC_VERSION_MIN_TVOS => "LC_VERSION_MIN_TVOS",
+        LC_VERSION_MIN_WATCHOS => "LC_VERSION_MIN_WATCHOS",
+        LC_NOTE => "LC_NOTE",
+        LC_BUILD_VERSION => "LC_BUILD_VERSION",
+        LC_DYLD_EXPORTS_TRIE => "LC_DYLD_EXPORTS_TRIE",
+        LC_DYLD_CHAINED_FIXUPS => "LC_DYLD_CHAINED_FIXUPS",
+        LC_FILESET_ENTRY => "LC_FILESET_ENTRY",
         _ => "LC_UNKNOWN",
     }
 }
@@ -1156,15 +2355,24 @@ pub enum CommandVariant {
     LoadUpwardDylib        (DylibCommand),
     VersionMinMacosx       (VersionMinCommand),
     VersionMinIphoneos     (VersionMinCommand),
+    VersionMinTvos         (VersionMinCommand),
+    VersionMinWatchos      (VersionMinCommand),
     FunctionStarts         (LinkeditDataCommand),
     DyldEnvironment        (DylinkerCommand),
     Main                   (EntryPointCommand),
     DataInCode             (LinkeditDataCommand),
     SourceVersion          (SourceVersionCommand),
     DylibCodeSignDrs       (LinkeditDataCommand),
-    LinkerOption           (LinkeditDataCommand),
+    LinkerOption           (LinkerOptionCommand),
     LinkerOptimizationHint (LinkeditDataCommand),
-    Unimplemented          (LoadCommandHeader),
+    BuildVersion           (BuildVersionCommand),
+    Note                   (NoteCommand),
+    DyldExportsTrie        (LinkeditDataCommand),
+    DyldChainedFixups      (LinkeditDataCommand),
+    FilesetEntry           (FilesetEntryCommand),
+    /// A load command goblin doesn't know, with the `cmdsize - SIZEOF_LOAD_COMMAND` bytes of its body, so that it can
+    /// be written back verbatim
+    Unimplemented          (LoadCommandHeader, Vec<u8>),
 }
 
 impl<'a> ctx::TryFromCtx<'a, Endian> for CommandVariant {
@@ -1177,7 +2385,11 @@ impl<'a> ctx::TryFromCtx<'a, Endian> for CommandVariant {
         let size = lc.cmdsize as usize;
         //println!("offset {:#x} cmd: {:#x} size: {:?} ctx: {:?}", offset, lc.cmd, size, le);
         if size > bytes.len() { return Err(error::Error::Malformed(format!("{} has size larger than remainder of binary: {:?}", &lc, bytes.len()))) }
-        match lc.cmd {
+        // a command must at least hold its header, or parsing the next one would start here again
+        if size < SIZEOF_LOAD_COMMAND { return Err(error::Error::Malformed(format!("{} has size smaller than a load command header", &lc))) }
+        // bound the reads of the command's struct by its cmdsize, so that it cannot extend into the next command
+        let bytes = &bytes[..size];
+        let command = (|| -> error::Result<(Self, Self::Size)> { match lc.cmd {
             LC_SEGMENT    => {              let comm = bytes.pread_with::<SegmentCommand32>       (0, le)?;  Ok((Segment32              (comm), size))},
             LC_SEGMENT_64 => {              let comm = bytes.pread_with::<SegmentCommand64>       (0, le)?;  Ok((Segment64              (comm), size))},
             LC_DYSYMTAB => {                let comm = bytes.pread_with::<DysymtabCommand>        (0, le)?;  Ok((Dysymtab               (comm), size))},
@@ -1217,20 +2429,101 @@ impl<'a> ctx::TryFromCtx<'a, Endian> for CommandVariant {
             LC_LOAD_UPWARD_DYLIB => {       let comm = bytes.pread_with::<DylibCommand>           (0, le)?;  Ok((LoadUpwardDylib        (comm), size))},
             LC_VERSION_MIN_MACOSX => {      let comm = bytes.pread_with::<VersionMinCommand>      (0, le)?;  Ok((VersionMinMacosx       (comm), size))},
             LC_VERSION_MIN_IPHONEOS => {    let comm = bytes.pread_with::<VersionMinCommand>      (0, le)?;  Ok((VersionMinIphoneos     (comm), size))},
+            LC_VERSION_MIN_TVOS => {        let comm = bytes.pread_with::<VersionMinCommand>      (0, le)?;  Ok((VersionMinTvos         (comm), size))},
+            LC_VERSION_MIN_WATCHOS => {     let comm = bytes.pread_with::<VersionMinCommand>      (0, le)?;  Ok((VersionMinWatchos      (comm), size))},
             LC_FUNCTION_STARTS => {         let comm = bytes.pread_with::<LinkeditDataCommand>    (0, le)?;  Ok((FunctionStarts         (comm), size))},
             LC_DYLD_ENVIRONMENT => {        let comm = bytes.pread_with::<DylinkerCommand>        (0, le)?;  Ok((DyldEnvironment        (comm), size))},
             LC_MAIN => {                    let comm = bytes.pread_with::<EntryPointCommand>      (0, le)?;  Ok((Main                   (comm), size))},
             LC_DATA_IN_CODE => {            let comm = bytes.pread_with::<LinkeditDataCommand>    (0, le)?;  Ok((DataInCode             (comm), size))},
             LC_SOURCE_VERSION => {          let comm = bytes.pread_with::<SourceVersionCommand>   (0, le)?;  Ok((SourceVersion          (comm), size))},
             LC_DYLIB_CODE_SIGN_DRS => {     let comm = bytes.pread_with::<LinkeditDataCommand>    (0, le)?;  Ok((DylibCodeSignDrs       (comm), size))},
-            LC_LINKER_OPTION => {           let comm = bytes.pread_with::<LinkeditDataCommand>    (0, le)?;  Ok((LinkerOption           (comm), size))},
+            LC_LINKER_OPTION => {           let comm = bytes.pread_with::<LinkerOptionCommand>    (0, le)?;  Ok((LinkerOption           (comm), size))},
             LC_LINKER_OPTIMIZATION_HINT => {let comm = bytes.pread_with::<LinkeditDataCommand>    (0, le)?;  Ok((LinkerOptimizationHint (comm), size))},
-            _ =>                                                                                             Ok((Unimplemented          (lc.clone()), size)),
+            LC_BUILD_VERSION => {           let comm = bytes.pread_with::<BuildVersionCommand>    (0, le)?;  Ok((BuildVersion           (comm), size))},
+            LC_NOTE => {                    let comm = bytes.pread_with::<NoteCommand>            (0, le)?;  Ok((Note                   (comm), size))},
+            LC_DYLD_EXPORTS_TRIE => {       let comm = bytes.pread_with::<LinkeditDataCommand>    (0, le)?;  Ok((DyldExportsTrie        (comm), size))},
+            LC_DYLD_CHAINED_FIXUPS => {     let comm = bytes.pread_with::<LinkeditDataCommand>    (0, le)?;  Ok((DyldChainedFixups      (comm), size))},
+            LC_FILESET_ENTRY => {           let comm = bytes.pread_with::<FilesetEntryCommand>    (0, le)?;  Ok((FilesetEntry           (comm), size))},
+            _ =>                                                                                             Ok((Unimplemented          (lc, bytes[SIZEOF_LOAD_COMMAND..].to_vec()), size)),
+        }})();
+        match command {
+            Err(error::Error::Scroll(_)) => Err(error::Error::Malformed(format!("{} has size smaller than its {} command struct", &lc, cmd_to_str(lc.cmd)))),
+            command => command,
         }
     }
 }
 
+impl ctx::TryIntoCtx<Endian> for &CommandVariant {
+    type Error = ::error::Error;
+    type Size = usize;
+    /// Writes the fixed-layout struct of this load command, returning its size. The data following some commands,
+    /// e.g. the sections of a segment, is not written; commands whose trailing strings or thread states are not modeled
+    /// (yet) are an error. `Unimplemented` commands are written whole, body and all
+    fn try_into_ctx(self, bytes: &mut [u8], le: Endian) -> error::Result<Self::Size> {
+        use scroll::Pwrite;
+        use self::CommandVariant::*;
+        let size = match *self {
+            Segment32              (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Segment64              (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Uuid                   (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Symtab                 (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Symseg                 (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Prepage                (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Dysymtab               (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Routines32             (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Routines64             (comm) => bytes.pwrite_with(comm, 0, le)?,
+            TwolevelHints          (comm) => bytes.pwrite_with(comm, 0, le)?,
+            PrebindCksum           (comm) => bytes.pwrite_with(comm, 0, le)?,
+            CodeSignature          (comm) => bytes.pwrite_with(comm, 0, le)?,
+            SegmentSplitInfo       (comm) => bytes.pwrite_with(comm, 0, le)?,
+            EncryptionInfo32       (comm) => bytes.pwrite_with(comm, 0, le)?,
+            EncryptionInfo64       (comm) => bytes.pwrite_with(comm, 0, le)?,
+            DyldInfo               (comm) => bytes.pwrite_with(comm, 0, le)?,
+            DyldInfoOnly           (comm) => bytes.pwrite_with(comm, 0, le)?,
+            VersionMinMacosx       (comm) => bytes.pwrite_with(comm, 0, le)?,
+            VersionMinIphoneos     (comm) => bytes.pwrite_with(comm, 0, le)?,
+            VersionMinTvos         (comm) => bytes.pwrite_with(comm, 0, le)?,
+            VersionMinWatchos      (comm) => bytes.pwrite_with(comm, 0, le)?,
+            FunctionStarts         (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Main                   (comm) => bytes.pwrite_with(comm, 0, le)?,
+            DataInCode             (comm) => bytes.pwrite_with(comm, 0, le)?,
+            SourceVersion          (comm) => bytes.pwrite_with(comm, 0, le)?,
+            DylibCodeSignDrs       (comm) => bytes.pwrite_with(comm, 0, le)?,
+            LinkerOptimizationHint (comm) => bytes.pwrite_with(comm, 0, le)?,
+            BuildVersion           (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Note                   (comm) => bytes.pwrite_with(comm, 0, le)?,
+            DyldExportsTrie        (comm) => bytes.pwrite_with(comm, 0, le)?,
+            DyldChainedFixups      (comm) => bytes.pwrite_with(comm, 0, le)?,
+            Unimplemented          (comm, ref body) => {
+                let size = bytes.pwrite_with(comm, 0, le)?;
+                size + bytes.pwrite(&body[..], size)?
+            },
+            _ => return Err(error::Error::Malformed(format!("Cannot write {} load command: its trailing data is not modeled", cmd_to_str(self.cmd())))),
+        };
+        Ok(size)
+    }
+}
+
+impl ctx::TryIntoCtx<Endian> for CommandVariant {
+    type Error = ::error::Error;
+    type Size = usize;
+    fn try_into_ctx(self, bytes: &mut [u8], le: Endian) -> error::Result<Self::Size> {
+        (&self).try_into_ctx(bytes, le)
+    }
+}
+
 impl CommandVariant {
+    /// Parse the load command at `offset` in `bytes` with the `le` endianness, returning it with its `cmdsize` bytes,
+    /// i.e., the `cmd_bytes` its trailing strings and tables are resolved against
+    pub fn parse_with_bytes(bytes: &[u8], offset: usize, le: Endian) -> error::Result<(CommandVariant, &[u8])> {
+        use scroll::ctx::TryFromCtx;
+        let rest = match bytes.get(offset..) {
+            Some(rest) => rest,
+            None => return Err(error::Error::Malformed(format!("load command offset {:#x} is past the end of the binary ({:#x})", offset, bytes.len()))),
+        };
+        let (command, size) = CommandVariant::try_from_ctx(rest, le)?;
+        Ok((command, &rest[..size]))
+    }
     pub fn cmdsize(&self) -> usize {
         use self::CommandVariant::*;
         let cmdsize = match *self {
@@ -1239,8 +2532,8 @@ impl CommandVariant {
             Uuid                   (comm) => comm.cmdsize,
             Symtab                 (comm) => comm.cmdsize,
             Symseg                 (comm) => comm.cmdsize,
-            Thread                 (comm) => comm.cmdsize,
-            Unixthread             (comm) => comm.cmdsize,
+            Thread                 (ref comm) => comm.cmdsize,
+            Unixthread             (ref comm) => comm.cmdsize,
             LoadFvmlib             (comm) => comm.cmdsize,
             IdFvmlib               (comm) => comm.cmdsize,
             Ident                  (comm) => comm.cmdsize,
@@ -1273,6 +2566,8 @@ impl CommandVariant {
             LoadUpwardDylib        (comm) => comm.cmdsize,
             VersionMinMacosx       (comm) => comm.cmdsize,
             VersionMinIphoneos     (comm) => comm.cmdsize,
+            VersionMinTvos         (comm) => comm.cmdsize,
+            VersionMinWatchos      (comm) => comm.cmdsize,
             FunctionStarts         (comm) => comm.cmdsize,
             DyldEnvironment        (comm) => comm.cmdsize,
             Main                   (comm) => comm.cmdsize,
@@ -1281,7 +2576,12 @@ impl CommandVariant {
             DylibCodeSignDrs       (comm) => comm.cmdsize,
             LinkerOption           (comm) => comm.cmdsize,
             LinkerOptimizationHint (comm) => comm.cmdsize,
-            Unimplemented          (comm) => comm.cmdsize,
+            BuildVersion           (comm) => comm.cmdsize,
+            Note                   (comm) => comm.cmdsize,
+            DyldExportsTrie        (comm) => comm.cmdsize,
+            DyldChainedFixups      (comm) => comm.cmdsize,
+            FilesetEntry           (comm) => comm.cmdsize,
+            Unimplemented          (comm, _) => comm.cmdsize,
         };
         cmdsize as usize
     }
@@ -1293,8 +2593,8 @@ impl CommandVariant {
             Uuid                   (comm) => comm.cmd,
             Symtab                 (comm) => comm.cmd,
             Symseg                 (comm) => comm.cmd,
-            Thread                 (comm) => comm.cmd,
-            Unixthread             (comm) => comm.cmd,
+            Thread                 (ref comm) => comm.cmd,
+            Unixthread             (ref comm) => comm.cmd,
             LoadFvmlib             (comm) => comm.cmd,
             IdFvmlib               (comm) => comm.cmd,
             Ident                  (comm) => comm.cmd,
@@ -1327,6 +2627,8 @@ impl CommandVariant {
             LoadUpwardDylib        (comm) => comm.cmd,
             VersionMinMacosx       (comm) => comm.cmd,
             VersionMinIphoneos     (comm) => comm.cmd,
+            VersionMinTvos         (comm) => comm.cmd,
+            VersionMinWatchos      (comm) => comm.cmd,
             FunctionStarts         (comm) => comm.cmd,
             DyldEnvironment        (comm) => comm.cmd,
             Main                   (comm) => comm.cmd,
@@ -1335,7 +2637,12 @@ impl CommandVariant {
             DylibCodeSignDrs       (comm) => comm.cmd,
             LinkerOption           (comm) => comm.cmd,
             LinkerOptimizationHint (comm) => comm.cmd,
-            Unimplemented          (comm) => comm.cmd,
+            BuildVersion           (comm) => comm.cmd,
+            Note                   (comm) => comm.cmd,
+            DyldExportsTrie        (comm) => comm.cmd,
+            DyldChainedFixups      (comm) => comm.cmd,
+            FilesetEntry           (comm) => comm.cmd,
+            Unimplemented          (comm, _) => comm.cmd,
         };
         cmd
     }
@@ -1359,4 +2666,283 @@ impl LoadCommand {
         *offset = start + size;
         Ok(LoadCommand { offset: start, command: command })
     }
+    /// Write the fixed-layout struct of this load command into `bytes` at `offset` with the `le` endianness, returning the number of bytes written.
+    /// See `CommandVariant`'s `TryIntoCtx` for which commands can be written
+    pub fn write(&self, bytes: &mut [u8], offset: usize, le: scroll::Endian) -> error::Result<usize> {
+        use scroll::Pwrite;
+        bytes.pwrite_with(&self.command, offset, le)
+    }
+}
+
+/// Writes an `otool -l` style `name value` line, with the name right aligned to `width`
+macro_rules! dump_field {
+    ($out:expr, $width:expr, $name:expr, $($arg:tt)*) => {
+        writeln!($out, "{:>width$} {}", $name, format_args!($($arg)*), width = $width)?
+    }
+}
+
+/// Writes the sections following a segment command at `offset` in `bytes`, as `otool -l` does
+fn dump_sections<W: fmt::Write>(out: &mut W, bytes: &[u8], offset: usize, nsects: u32, is_64: bool, le: scroll::Endian) -> fmt::Result {
+    let size = if is_64 { SIZEOF_SECTION_64 } else { SIZEOF_SECTION_32 };
+    for i in 0..nsects as usize {
+        let offset = offset + i * size;
+        let section = if is_64 {
+            bytes.pread_with::<Section64>(offset, le).ok()
+        } else {
+            bytes.pread_with::<Section32>(offset, le).ok().map(|section| Section64 {
+                sectname: section.sectname, segname: section.segname, addr: section.addr as u64, size: section.size as u64,
+                offset: section.offset, align: section.align, reloff: section.reloff, nreloc: section.nreloc,
+                flags: section.flags, reserved1: section.reserved1, reserved2: section.reserved2, reserved3: 0,
+            })
+        };
+        let section = match section {
+            Some(section) => section,
+            None => {
+                writeln!(out, "Section {} is outside of the load commands", i)?;
+                return Ok(());
+            },
+        };
+        writeln!(out, "Section")?;
+        dump_field!(out, 10, "sectname", "{}", fixed_str(&section.sectname).unwrap_or(""));
+        dump_field!(out, 10, "segname", "{}", fixed_str(&section.segname).unwrap_or(""));
+        if is_64 {
+            dump_field!(out, 10, "addr", "{:#018x}", section.addr);
+            dump_field!(out, 10, "size", "{:#018x}", section.size);
+        } else {
+            dump_field!(out, 10, "addr", "{:#010x}", section.addr);
+            dump_field!(out, 10, "size", "{:#010x}", section.size);
+        }
+        dump_field!(out, 10, "offset", "{}", section.offset);
+        dump_field!(out, 10, "align", "2^{}", section.align);
+        dump_field!(out, 10, "reloff", "{}", section.reloff);
+        dump_field!(out, 10, "nreloc", "{}", section.nreloc);
+        dump_field!(out, 10, "flags", "{:#010x}", section.flags);
+        dump_field!(out, 10, "reserved1", "{}", section.reserved1);
+        dump_field!(out, 10, "reserved2", "{}", section.reserved2);
+    }
+    Ok(())
+}
+
+fn dump_version(version: u32) -> String {
+    let (x, y, z) = unpack_version(version);
+    format!("{}.{}.{}", x, y, z)
+}
+
+impl LoadCommand {
+    /// Writes this load command, the `index`-th of its binary, in the style of `otool -l`: its name and size, followed
+    /// by its fields in declaration order, with strings resolved; `bytes` are the bytes the binary was parsed from, and
+    /// `le` its endianness, for the strings and sections that follow the command
+    pub fn dump<W: fmt::Write>(&self, index: usize, bytes: &[u8], le: scroll::Endian, out: &mut W) -> fmt::Result {
+        use self::CommandVariant::*;
+        let cmd_bytes = bytes.get(self.offset..).unwrap_or(&[]);
+        let cmd = self.command.cmd();
+        writeln!(out, "Load command {}", index)?;
+        match self.command {
+            Unimplemented(..) => dump_field!(out, 9, "cmd", "{} ({:#x})", cmd_to_str(cmd), cmd),
+            _ => dump_field!(out, 9, "cmd", "{}", cmd_to_str(cmd)),
+        }
+        dump_field!(out, 9, "cmdsize", "{}", self.command.cmdsize());
+        let lc_str = |offset: LcStrOffset| {
+            let string = LcStr::new(cmd_bytes, self.command.cmdsize() as u32, offset);
+            format!("{} (offset {})", string, offset)
+        };
+        match self.command {
+            Segment32(command) => {
+                dump_field!(out, 9, "segname", "{}", command.name().unwrap_or(""));
+                dump_field!(out, 9, "vmaddr", "{:#010x}", command.vmaddr);
+                dump_field!(out, 9, "vmsize", "{:#010x}", command.vmsize);
+                dump_field!(out, 9, "fileoff", "{}", command.fileoff);
+                dump_field!(out, 9, "filesize", "{}", command.filesize);
+                dump_field!(out, 9, "maxprot", "{:#010x}", command.maxprot);
+                dump_field!(out, 9, "initprot", "{:#010x}", command.initprot);
+                dump_field!(out, 9, "nsects", "{}", command.nsects);
+                dump_field!(out, 9, "flags", "{:#x}", command.flags);
+                dump_sections(out, bytes, self.offset + SIZEOF_SEGMENT_COMMAND_32, command.nsects, false, le)?;
+            },
+            Segment64(command) => {
+                dump_field!(out, 9, "segname", "{}", command.name().unwrap_or(""));
+                dump_field!(out, 9, "vmaddr", "{:#018x}", command.vmaddr);
+                dump_field!(out, 9, "vmsize", "{:#018x}", command.vmsize);
+                dump_field!(out, 9, "fileoff", "{}", command.fileoff);
+                dump_field!(out, 9, "filesize", "{}", command.filesize);
+                dump_field!(out, 9, "maxprot", "{:#010x}", command.maxprot);
+                dump_field!(out, 9, "initprot", "{:#010x}", command.initprot);
+                dump_field!(out, 9, "nsects", "{}", command.nsects);
+                dump_field!(out, 9, "flags", "{:#x}", command.flags);
+                dump_sections(out, bytes, self.offset + SIZEOF_SEGMENT_COMMAND_64, command.nsects, true, le)?;
+            },
+            Uuid(command) => dump_field!(out, 9, "uuid", "{}", command),
+            Symtab(command) => {
+                dump_field!(out, 9, "symoff", "{}", command.symoff);
+                dump_field!(out, 9, "nsyms", "{}", command.nsyms);
+                dump_field!(out, 9, "stroff", "{}", command.stroff);
+                dump_field!(out, 9, "strsize", "{}", command.strsize);
+            },
+            Dysymtab(command) => {
+                dump_field!(out, 9, "ilocalsym", "{}", command.ilocalsym);
+                dump_field!(out, 9, "nlocalsym", "{}", command.nlocalsym);
+                dump_field!(out, 9, "iextdefsym", "{}", command.iextdefsym);
+                dump_field!(out, 9, "nextdefsym", "{}", command.nextdefsym);
+                dump_field!(out, 9, "iundefsym", "{}", command.iundefsym);
+                dump_field!(out, 9, "nundefsym", "{}", command.nundefsym);
+                dump_field!(out, 9, "tocoff", "{}", command.tocoff);
+                dump_field!(out, 9, "ntoc", "{}", command.ntoc);
+                dump_field!(out, 9, "modtaboff", "{}", command.modtaboff);
+                dump_field!(out, 9, "nmodtab", "{}", command.nmodtab);
+                dump_field!(out, 9, "extrefsymoff", "{}", command.extrefsymoff);
+                dump_field!(out, 9, "nextrefsyms", "{}", command.nextrefsyms);
+                dump_field!(out, 9, "indirectsymoff", "{}", command.indirectsymoff);
+                dump_field!(out, 9, "nindirectsyms", "{}", command.nindirectsyms);
+                dump_field!(out, 9, "extreloff", "{}", command.extreloff);
+                dump_field!(out, 9, "nextrel", "{}", command.nextrel);
+                dump_field!(out, 9, "locreloff", "{}", command.locreloff);
+                dump_field!(out, 9, "nlocrel", "{}", command.nlocrel);
+            },
+            Thread(ref command) | Unixthread(ref command) => {
+                dump_field!(out, 9, "flavor", "{}", command.flavor);
+                dump_field!(out, 9, "count", "{}", command.count);
+            },
+            LoadDylib(command) | IdDylib(command) | LoadWeakDylib(command) | ReexportDylib(command) | LazyLoadDylib(command) | LoadUpwardDylib(command) => {
+                let dylib = command.dylib;
+                let (name, timestamp, current_version, compatibility_version) = (dylib.name, dylib.timestamp, dylib.current_version, dylib.compatibility_version);
+                dump_field!(out, 9, "name", "{}", lc_str(name));
+                dump_field!(out, 9, "time stamp", "{}", timestamp);
+                dump_field!(out, 9, "current version", "{}", dump_version(current_version));
+                dump_field!(out, 9, "compatibility version", "{}", dump_version(compatibility_version));
+            },
+            LoadDylinker(command) | IdDylinker(command) | DyldEnvironment(command) => dump_field!(out, 9, "name", "{}", lc_str(command.name)),
+            SubFramework(command) => dump_field!(out, 9, "umbrella", "{}", lc_str(command.umbrella)),
+            SubUmbrella(command) => dump_field!(out, 9, "sub_umbrella", "{}", lc_str(command.sub_umbrella)),
+            SubClient(command) => dump_field!(out, 9, "client", "{}", lc_str(command.client)),
+            SubLibrary(command) => dump_field!(out, 9, "sub_library", "{}", lc_str(command.sub_library)),
+            Rpath(command) => dump_field!(out, 9, "path", "{}", lc_str(command.path)),
+            CodeSignature(command) | SegmentSplitInfo(command) | FunctionStarts(command) | DataInCode(command) | DylibCodeSignDrs(command) | LinkerOptimizationHint(command) | DyldExportsTrie(command) | DyldChainedFixups(command) => {
+                dump_field!(out, 9, "dataoff", "{}", command.dataoff);
+                dump_field!(out, 9, "datasize", "{}", command.datasize);
+            },
+            EncryptionInfo32(command) => {
+                dump_field!(out, 9, "cryptoff", "{}", command.cryptoff);
+                dump_field!(out, 9, "cryptsize", "{}", command.cryptsize);
+                dump_field!(out, 9, "cryptid", "{}", command.cryptid);
+            },
+            EncryptionInfo64(command) => {
+                dump_field!(out, 9, "cryptoff", "{}", command.cryptoff);
+                dump_field!(out, 9, "cryptsize", "{}", command.cryptsize);
+                dump_field!(out, 9, "cryptid", "{}", command.cryptid);
+                dump_field!(out, 9, "pad", "{}", command.pad);
+            },
+            DyldInfo(command) | DyldInfoOnly(command) => {
+                dump_field!(out, 9, "rebase_off", "{}", command.rebase_off);
+                dump_field!(out, 9, "rebase_size", "{}", command.rebase_size);
+                dump_field!(out, 9, "bind_off", "{}", command.bind_off);
+                dump_field!(out, 9, "bind_size", "{}", command.bind_size);
+                dump_field!(out, 9, "weak_bind_off", "{}", command.weak_bind_off);
+                dump_field!(out, 9, "weak_bind_size", "{}", command.weak_bind_size);
+                dump_field!(out, 9, "lazy_bind_off", "{}", command.lazy_bind_off);
+                dump_field!(out, 9, "lazy_bind_size", "{}", command.lazy_bind_size);
+                dump_field!(out, 9, "export_off", "{}", command.export_off);
+                dump_field!(out, 9, "export_size", "{}", command.export_size);
+            },
+            VersionMinMacosx(command) | VersionMinIphoneos(command) | VersionMinTvos(command) | VersionMinWatchos(command) => {
+                dump_field!(out, 9, "version", "{}", dump_version(command.version));
+                dump_field!(out, 9, "sdk", "{}", dump_version(command.sdk));
+            },
+            Main(command) => {
+                dump_field!(out, 9, "entryoff", "{}", command.entryoff);
+                dump_field!(out, 9, "stacksize", "{}", command.stacksize);
+            },
+            SourceVersion(command) => dump_field!(out, 9, "version", "{}", command),
+            LinkerOption(command) => dump_field!(out, 9, "count", "{}", command.count),
+            BuildVersion(command) => {
+                dump_field!(out, 9, "platform", "{}", Platform::from(command.platform));
+                dump_field!(out, 9, "minos", "{}", dump_version(command.minos));
+                dump_field!(out, 9, "sdk", "{}", dump_version(command.sdk));
+                dump_field!(out, 9, "ntools", "{}", command.ntools);
+            },
+            Note(command) => {
+                dump_field!(out, 9, "data_owner", "{}", fixed_str(&command.data_owner).unwrap_or(""));
+                dump_field!(out, 9, "offset", "{}", command.offset);
+                dump_field!(out, 9, "size", "{}", command.size);
+            },
+            FilesetEntry(command) => {
+                dump_field!(out, 9, "vmaddr", "{:#018x}", command.vmaddr);
+                dump_field!(out, 9, "fileoff", "{}", command.fileoff);
+                dump_field!(out, 9, "entry_id", "{}", lc_str(command.entry_id));
+            },
+            Routines32(command) => {
+                dump_field!(out, 9, "init_address", "{:#010x}", command.init_address);
+                dump_field!(out, 9, "init_module", "{}", command.init_module);
+                for (i, reserved) in [command.reserved1, command.reserved2, command.reserved3, command.reserved4, command.reserved5, command.reserved6].iter().enumerate() {
+                    dump_field!(out, 9, format_args!("reserved{}", i + 1), "{}", reserved);
+                }
+            },
+            Routines64(command) => {
+                dump_field!(out, 9, "init_address", "{:#018x}", command.init_address);
+                dump_field!(out, 9, "init_module", "{}", command.init_module);
+                for (i, reserved) in [command.reserved1, command.reserved2, command.reserved3, command.reserved4, command.reserved5, command.reserved6].iter().enumerate() {
+                    dump_field!(out, 9, format_args!("reserved{}", i + 1), "{}", reserved);
+                }
+            },
+            // we don't know what the body of an unknown command means, so print its bytes
+            Unimplemented(_, ref body) => {
+                let data = body.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(" ");
+                dump_field!(out, 9, "data", "{}", data);
+            },
+            Symseg(_) | LoadFvmlib(_) | IdFvmlib(_) | Ident(_) | Fvmfile(_) | Prepage(_) | PreboundDylib(_) | TwolevelHints(_) |
+            PrebindCksum(_) => (),
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the `ncmds` load commands of a binary, which stops at the end of the load command region.
+///
+/// A command which doesn't hold its own header (e.g. a zero `cmdsize`) or which runs past the region is an error, after
+/// which the iterator is done
+pub struct LoadCommandIterator<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    ncmds: usize,
+    count: usize,
+    le: scroll::Endian,
+}
+
+impl<'a> LoadCommandIterator<'a> {
+    /// Iterate the `ncmds` load commands starting at `offset` in `bytes`, with the `le` endianness; `bytes` must end at
+    /// the end of the load commands, i.e. `sizeofcmds` past the header, and the commands' offsets are relative to its start
+    pub fn new(bytes: &'a [u8], offset: usize, ncmds: usize, le: scroll::Endian) -> Self {
+        LoadCommandIterator {
+            bytes: bytes,
+            offset: offset,
+            ncmds: ncmds,
+            count: 0,
+            le: le,
+        }
+    }
+}
+
+impl<'a> Iterator for LoadCommandIterator<'a> {
+    type Item = error::Result<LoadCommand>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count >= self.ncmds || self.offset >= self.bytes.len() {
+            return None;
+        }
+        let index = self.count;
+        self.count += 1;
+        if self.offset + SIZEOF_LOAD_COMMAND > self.bytes.len() {
+            self.count = self.ncmds;
+            return Some(Err(error::Error::Malformed(format!("load command {} of {} at {:#x} runs past the end of the load commands at {:#x}", index, self.ncmds, self.offset, self.bytes.len()))));
+        }
+        match LoadCommand::parse(self.bytes, &mut self.offset, self.le) {
+            Ok(command) => Some(Ok(command)),
+            Err(e) => {
+                self.count = self.ncmds;
+                Some(Err(e))
+            },
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.ncmds - self.count;
+        (0, Some(remaining.min(self.bytes.len().saturating_sub(self.offset) / SIZEOF_LOAD_COMMAND)))
+    }
 }
@@ -1,5 +1,7 @@
 //! The Mach-o, mostly zero-copy, binary format parser and raw struct definitions
+use core::cmp;
 use core::fmt;
+use core::ops::Range;
 
 use scroll::{self, Pread};
 
@@ -11,19 +13,63 @@ pub mod constants;
 pub mod fat;
 pub mod load_command;
 pub mod symbols;
+pub mod stab;
 pub mod exports;
 pub mod imports;
 pub mod bind_opcodes;
+pub mod rebase_opcodes;
+pub mod lazy;
+pub mod leb;
+pub mod rebases;
+pub mod chained_fixups;
 pub mod relocation;
+pub mod codesign;
+pub mod objc;
 pub mod segment;
 
 pub use self::constants::cputype as cputype;
+pub use self::load_command::Routines;
 
 /// Returns a big endian magical number
 pub fn peek(bytes: &[u8], offset: usize) -> error::Result<u32> {
     Ok(bytes.pread_with::<u32>(offset, scroll::BE)?)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What kind of Mach-o file some bytes look like, from `peek_kind`
+pub enum MachKind {
+    /// A single 32-bit binary, `MH_MAGIC` or `MH_CIGAM`
+    Thin32,
+    /// A single 64-bit binary, `MH_MAGIC_64` or `MH_CIGAM_64`
+    Thin64,
+    /// A fat binary with 32-bit architecture headers, `FAT_MAGIC`
+    Fat32,
+    /// A fat binary with 64-bit architecture headers, `FAT_MAGIC_64`
+    Fat64,
+}
+
+/// Identifies the kind of Mach-o file `bytes` is from just the magic at its start, without parsing anything else, or
+/// `None` if it isn't one; note that Java class files share `FAT_MAGIC`
+pub fn peek_kind(bytes: &[u8]) -> Option<MachKind> {
+    let magic = peek(bytes, 0).ok()?;
+    if magic == fat::FAT_MAGIC {
+        return Some(MachKind::Fat32);
+    }
+    if magic == fat::FAT_MAGIC_64 {
+        return Some(MachKind::Fat64);
+    }
+    container::Ctx::from_mach_magic(magic).map(|ctx| if ctx.is_big() { MachKind::Thin64 } else { MachKind::Thin32 })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The start of a function, as recorded by `LC_FUNCTION_STARTS`
+pub struct FunctionStart {
+    /// The virtual address of the function
+    pub address: u64,
+    /// The `segname,sectname` of the section the function is in, e.g., `__TEXT,__text`, if any
+    pub containing_section: Option<String>,
+}
+
 #[derive(Debug)]
 /// A cross-platform, zero-copy, endian-aware, 32/64 bit Mach-o binary parser
 pub struct MachO<'a> {
@@ -37,6 +83,8 @@ pub struct MachO<'a> {
     pub symbols: Option<symbols::Symbols<'a>>,
     /// The dylibs this library depends on
     pub libs: Vec<&'a str>,
+    /// The `LC_RPATH` search paths for `@rpath` prefixed dylibs, in order
+    pub rpaths: Vec<&'a str>,
     /// The entry point, 0 if none
     pub entry: u64,
     /// The name of the dylib, if any
@@ -55,6 +103,146 @@ impl<'a> MachO<'a> {
     pub fn is_object_file(&self) -> bool {
         self.header.filetype == header::MH_OBJECT
     }
+    /// The paths of the dylibs this binary loads, in load command order, without the leading `"self"` of `libs`
+    pub fn libraries(&self) -> Vec<&'a str> {
+        self.libs[1..].to_vec()
+    }
+    /// The `LC_RPATH` search paths of this binary, in order
+    pub fn rpaths(&self) -> Vec<&'a str> {
+        self.rpaths.clone()
+    }
+    /// Whether this binary is encrypted (e.g. by FairPlay, as App Store binaries are), i.e. whether it has an
+    /// `LC_ENCRYPTION_INFO` or `LC_ENCRYPTION_INFO_64` with a non-zero `cryptid`
+    pub fn is_encrypted(&self) -> bool {
+        self.load_commands.iter().any(|cmd| match cmd.command {
+            load_command::CommandVariant::EncryptionInfo32(command) => command.is_encrypted(),
+            load_command::CommandVariant::EncryptionInfo64(command) => command.is_encrypted(),
+            _ => false,
+        })
+    }
+    /// Renders every load command in order in the style of `otool -l`, for inspecting a binary or comparing against a
+    /// golden file; see `LoadCommand::dump` for the format
+    pub fn dump_load_commands(&self) -> String {
+        let mut dump = String::new();
+        for (i, cmd) in self.load_commands.iter().enumerate() {
+            // writing to a String can't fail
+            let _ = cmd.dump(i, self.data, self.ctx.le, &mut dump);
+        }
+        dump
+    }
+    /// The virtual address this binary starts executing at: for `LC_MAIN`, its `entryoff` from the start of the
+    /// `__TEXT` segment, and for `LC_UNIXTHREAD`, the instruction pointer of its initial thread state; dylibs, bundles and
+    /// other binaries with neither have none
+    pub fn entry_point(&self) -> Option<u64> {
+        for cmd in &self.load_commands {
+            match cmd.command {
+                load_command::CommandVariant::Main(command) => {
+                    let text = self.segments.find(constants::SEG_TEXT)?;
+                    return text.vmaddr.checked_add(command.entryoff);
+                },
+                load_command::CommandVariant::Unixthread(ref command) => {
+                    return command.instruction_pointer(self.header.cputype);
+                },
+                _ => (),
+            }
+        }
+        None
+    }
+    /// The 128-bit UUID of this binary from its `LC_UUID` command, if it has one, e.g. to match it with its dSYM
+    pub fn uuid(&self) -> Option<[u8; 16]> {
+        self.load_commands.iter().filter_map(|cmd| {
+            match cmd.command {
+                load_command::CommandVariant::Uuid(command) => Some(command.uuid),
+                _ => None,
+            }
+        }).next()
+    }
+    /// The raw code signature blob (a `CS_SuperBlob`) the `LC_CODE_SIGNATURE` command points to, if there is one,
+    /// erroring if it is not within the binary
+    pub fn code_signature(&self) -> error::Result<Option<&'a [u8]>> {
+        for cmd in &self.load_commands {
+            if let load_command::CommandVariant::CodeSignature(command) = cmd.command {
+                return command.data(self.data).map(Some);
+            }
+        }
+        Ok(None)
+    }
+    /// The platform and minimum OS version (X, Y, Z) this binary was built to run on, from its `LC_BUILD_VERSION`
+    /// command, or else its `LC_VERSION_MIN_*` command, if it has either
+    pub fn minimum_version(&self) -> Option<(load_command::Platform, (u16, u8, u8))> {
+        let mut version_min = None;
+        for cmd in &self.load_commands {
+            match cmd.command {
+                load_command::CommandVariant::BuildVersion(command) => return Some((command.platform(), command.minos_tuple())),
+                load_command::CommandVariant::VersionMinMacosx(command)
+                | load_command::CommandVariant::VersionMinIphoneos(command)
+                | load_command::CommandVariant::VersionMinTvos(command)
+                | load_command::CommandVariant::VersionMinWatchos(command) if version_min.is_none() => {
+                    version_min = Some((command.platform(), command.version_tuple()));
+                },
+                _ => (),
+            }
+        }
+        version_min
+    }
+    /// The virtual memory this binary's segments take up when loaded, i.e. the sum of their `vmsize`s, not counting the
+    /// `__PAGEZERO` segment, which only reserves the address space below the image
+    pub fn vm_size(&self) -> u64 {
+        self.segments.iter()
+            .filter(|segment| segment.name().ok() != Some(constants::SEG_PAGEZERO))
+            .fold(0u64, |size, segment| size.saturating_add(segment.vmsize))
+    }
+    /// The size of the `__PAGEZERO` segment, if there is one: typically 4GiB for 64-bit executables and 4KiB for 32-bit
+    /// ones, while libraries and object files have none
+    pub fn page_zero_size(&self) -> Option<u64> {
+        self.segments.page_zero().map(|segment| segment.vmsize)
+    }
+    /// The range of the data appended to this binary past the end of its segments, e.g. an archive or other resources,
+    /// if the file it was read from is longer than that; `total_len` is the length of that file
+    pub fn overlay(&self, total_len: usize) -> Option<Range<usize>> {
+        overlay(self.file_size_covered(), total_len)
+    }
+    /// The file range `fileoff..fileoff + filesize` of the `__LINKEDIT` segment, if there is one, within which the
+    /// symbol and string tables, dyld info and other linkedit data of a well-formed binary lie
+    pub fn linkedit_range(&self) -> Option<Range<u64>> {
+        self.segments.find(constants::SEG_LINKEDIT).map(|segment| segment.fileoff..segment.fileoff + segment.filesize)
+    }
+    /// The end of the file contents of this binary's segments, i.e. the greatest `fileoff + filesize`; any data in the
+    /// file past it is not loaded, e.g. appended overlay data
+    pub fn file_size_covered(&self) -> u64 {
+        self.segments.iter()
+            .filter(|segment| segment.filesize > 0)
+            .map(|segment| segment.fileoff.saturating_add(segment.filesize))
+            .max()
+            .unwrap_or(0)
+    }
+    /// The Objective-C image info of this binary, from its `__objc_imageinfo` or legacy `__OBJC,__image_info` section,
+    /// if it has either
+    pub fn objc_image_info(&self) -> error::Result<Option<objc::ImageInfo>> {
+        for section in self.segments.flat_sections() {
+            let (segment, section, data) = section?;
+            let name = section.name().ok();
+            if name == Some(objc::SECT_OBJC_IMAGEINFO) {
+                return objc::ImageInfo::parse(data, self.ctx.le).map(Some);
+            }
+            if name == Some(objc::SECT_IMAGE_INFO) && segment.name().ok() == Some(objc::SEG_OBJC) {
+                return objc::ImageInfo::parse_legacy(data, self.ctx.le).map(Some);
+            }
+        }
+        Ok(None)
+    }
+    /// The filetype of this binary, e.g., `FileType::Execute`
+    pub fn file_type(&self) -> header::FileType {
+        self.header.file_type()
+    }
+    /// Whether this binary is an executable
+    pub fn is_executable(&self) -> bool {
+        self.file_type().is_executable()
+    }
+    /// Whether this binary is a shared library
+    pub fn is_library(&self) -> bool {
+        self.file_type().is_library()
+    }
     pub fn symbols(&self) -> symbols::SymbolIterator<'a> {
         if let &Some(ref symbols) = &self.symbols {
             symbols.into_iter()
@@ -75,15 +263,107 @@ impl<'a> MachO<'a> {
         }
         Ok(relocs)
     }
-    /// Return the exported symbols in this binary (if any)
+    /// Return the sorted function starts recorded by the `LC_FUNCTION_STARTS` command of this binary (if any), with the
+    /// sections they are in
+    pub fn function_starts(&self) -> error::Result<Vec<FunctionStart>> {
+        let command = self.load_commands.iter().filter_map(|cmd| {
+            match cmd.command {
+                load_command::CommandVariant::FunctionStarts(command) => Some(command),
+                _ => None,
+            }
+        }).next();
+        let command = match command {
+            Some(command) => command,
+            None => return Ok(vec![]),
+        };
+        let text_vmaddr = self.segments.find(constants::SEG_TEXT).map(|segment| segment.vmaddr).unwrap_or(0);
+        let mut addresses = command.function_starts(self.data, text_vmaddr).collect::<error::Result<Vec<u64>>>()?;
+        addresses.sort();
+        let mut sections = Vec::new();
+        for segment in &self.segments {
+            for section in segment {
+                let (section, _data) = section?;
+                sections.push(section);
+            }
+        }
+        Ok(addresses.into_iter().map(|address| {
+            let containing_section = sections.iter()
+                .find(|section| section.addr <= address && address - section.addr < section.size)
+                .map(|section| format!("{},{}", section.segname().unwrap_or("BAD_SEGMENT_NAME"), section.name().unwrap_or("BAD_SECTION_NAME")));
+            FunctionStart {
+                address: address,
+                containing_section: containing_section,
+            }
+        }).collect())
+    }
+    /// Parses the header of the chained fixups of this binary, if it has an `LC_DYLD_CHAINED_FIXUPS` command
+    pub fn chained_fixups(&self) -> error::Result<Option<chained_fixups::ChainedFixups<'a>>> {
+        for cmd in &self.load_commands {
+            if let load_command::CommandVariant::DyldChainedFixups(command) = cmd.command {
+                return command.chained_fixups(self.data, self.ctx.le).map(Some);
+            }
+        }
+        Ok(None)
+    }
+    /// Return the name of the library with the (two-level namespace) library `ordinal` of a symbol, e.g., as returned by
+    /// `Nlist::library_ordinal`; `SELF_LIBRARY_ORDINAL` is this image, and `DYNAMIC_LOOKUP_ORDINAL` and `EXECUTABLE_ORDINAL`
+    /// are named `"flat-namespace"` and `"main-executable"`, as `dyldinfo` does
+    pub fn library_ordinal_name(&self, ordinal: u16) -> Option<&'a str> {
+        match ordinal {
+            symbols::DYNAMIC_LOOKUP_ORDINAL => Some("flat-namespace"),
+            symbols::EXECUTABLE_ORDINAL => Some("main-executable"),
+            ordinal if ordinal <= symbols::MAX_LIBRARY_ORDINAL => self.libs.get(ordinal as usize).cloned(),
+            _ => None,
+        }
+    }
+    /// Return the exported symbols in this binary (if any), with their virtual addresses, and their sizes computed from
+    /// the address of the next export in the same section (or the end of the section)
     pub fn exports(&self) -> error::Result<Vec<exports::Export>> {
-        if let Some(ref trie) = self.export_trie {
-            trie.exports(self.libs.as_slice())
+        let mut exports = if let Some(ref trie) = self.export_trie {
+            trie.exports(self.libs.as_slice())?
         } else {
-            Ok(vec![])
+            return Ok(vec![])
+        };
+        let base = self.segments.find(constants::SEG_TEXT).map(|segment| segment.vmaddr).unwrap_or(0);
+        let mut sections = Vec::new();
+        for segment in &self.segments {
+            for section in segment {
+                let (section, _data) = section?;
+                sections.push(section);
+            }
         }
+        let mut addresses = Vec::new();
+        for export in &mut exports {
+            if export.reexport().is_some() { continue }
+            if !export.is_absolute() {
+                export.address = base.wrapping_add(export.offset);
+            }
+            addresses.push(export.address);
+        }
+        addresses.sort();
+        addresses.dedup();
+        for export in &mut exports {
+            if export.reexport().is_some() || export.is_absolute() { continue }
+            let address = export.address;
+            let section = sections.iter().find(|section| section.addr <= address && address - section.addr < section.size);
+            // a section which runs past the end of the address space is malformed, and bounds nothing
+            if let Some(end) = section.and_then(|section| section.addr.checked_add(section.size)) {
+                let next = match addresses.binary_search(&address) {
+                    Ok(i) => addresses.get(i + 1).cloned(),
+                    Err(_) => None,
+                };
+                let next = match next {
+                    Some(next) if next < end => next,
+                    _ => end,
+                };
+                export.size = (next - address) as usize;
+            }
+        }
+        Ok(exports)
     }
-    /// Return the imported symbols in this binary that dyld knows about (if any)
+    /// Return the imported symbols in this binary that dyld knows about (if any), i.e., those bound by the opcodes of
+    /// its `LC_DYLD_INFO` command; binaries without one (which predate it, and bind through their indirect symbol
+    /// table and external relocations instead) have none
     pub fn imports(&self) -> error::Result<Vec<imports::Import>> {
         if let Some(ref interpreter) = self.bind_interpreter {
             interpreter.imports(self.libs.as_slice(), self.segments.as_slice(), &self.ctx)
@@ -91,6 +371,33 @@ impl<'a> MachO<'a> {
             Ok(vec![])
         }
     }
+    /// Returns the `(entry_id, vmaddr, fileoff)` of every Mach-O image embedded in this (`MH_FILESET`) binary;
+    /// an `entry_id` which is out of bounds of its load command is empty
+    pub fn fileset_entries(&self) -> Vec<(String, u64, u64)> {
+        let mut entries = Vec::new();
+        for cmd in &self.load_commands {
+            if let load_command::CommandVariant::FilesetEntry(entry) = cmd.command {
+                let id = entry.lc_str(&self.data[cmd.offset..]);
+                entries.push((id.to_string(), entry.vmaddr, entry.fileoff));
+            }
+        }
+        entries
+    }
+    /// Parses the embedded Mach-O image whose entry id is `id` from the same bytes as this binary, or `None` if there is no such entry
+    pub fn fileset_entry_macho(&self, id: &str) -> error::Result<Option<MachO<'a>>> {
+        for cmd in &self.load_commands {
+            if let load_command::CommandVariant::FilesetEntry(entry) = cmd.command {
+                if entry.lc_str(&self.data[cmd.offset..]).to_str().ok() == Some(id) {
+                    let fileoff = entry.fileoff as usize;
+                    if entry.fileoff > self.data.len() as u64 {
+                        return Err(error::Error::Malformed(format!("fileset entry {} at {:#x} is past the end of the binary ({:#x})", id, fileoff, self.data.len())));
+                    }
+                    return MachO::parse(self.data, fileoff).map(Some);
+                }
+            }
+        }
+        Ok(None)
+    }
     /// Parses the Mach-o binary from `bytes` at `offset`
     pub fn parse(bytes: &'a [u8], mut offset: usize) -> error::Result<MachO<'a>> {
         let offset = &mut offset;
@@ -100,31 +407,40 @@ impl<'a> MachO<'a> {
         let is_64 = ctx.container.is_big();
         *offset = *offset + header.size();
         let ncmds = header.ncmds;
-        let mut cmds: Vec<load_command::LoadCommand> = Vec::with_capacity(ncmds);
+        // the load commands are bounded by sizeofcmds, as they are for the kernel and dyld
+        let cmds_end = match offset.checked_add(header.sizeofcmds as usize) {
+            Some(end) if end <= bytes.len() => end,
+            _ => return Err(error::Error::Malformed(format!("sizeofcmds {:#x} at {:#x} is larger than the binary ({:#x})", header.sizeofcmds, *offset, bytes.len()))),
+        };
+        let cmds_bytes = &bytes[..cmds_end];
+        // every command is at least a header, so a bogus (huge) ncmds can't make us preallocate more than sizeofcmds allows
+        let mut cmds: Vec<load_command::LoadCommand> = Vec::with_capacity(ncmds.min(header.sizeofcmds as usize / load_command::SIZEOF_LOAD_COMMAND));
         let mut symbols = None;
         let mut libs = vec!["self"];
+        let mut rpaths = Vec::new();
         let mut export_trie = None;
         let mut bind_interpreter = None;
         let mut entry = 0x0;
         let mut name = None;
         let mut segments = segment::Segments::new(ctx);
-        for _ in 0..ncmds {
-            let cmd = load_command::LoadCommand::parse(bytes, offset, ctx.le)?;
+        for cmd in load_command::LoadCommandIterator::new(cmds_bytes, *offset, ncmds, ctx.le) {
+            let cmd = cmd?;
             match cmd.command {
                 load_command::CommandVariant::Segment32(command) => {
-                    segments.push(segment::Segment::from_32(bytes.as_ref(), &command, cmd.offset, ctx))
+                    segments.push(segment::Segment::from_32(bytes.as_ref(), &command, cmd.offset, ctx)?)
                 },
                 load_command::CommandVariant::Segment64(command) => {
-                    segments.push(segment::Segment::from_64(bytes.as_ref(), &command, cmd.offset, ctx))
+                    segments.push(segment::Segment::from_64(bytes.as_ref(), &command, cmd.offset, ctx)?)
                 },
                 load_command::CommandVariant::Symtab(command) => {
                     symbols = Some(symbols::Symbols::parse(bytes, &command, ctx)?);
                 },
                   load_command::CommandVariant::LoadDylib      (command)
+                | load_command::CommandVariant::LoadWeakDylib  (command)
                 | load_command::CommandVariant::LoadUpwardDylib(command)
                 | load_command::CommandVariant::ReexportDylib  (command)
                 | load_command::CommandVariant::LazyLoadDylib  (command) => {
-                    let lib = bytes.pread::<&str>(cmd.offset + command.dylib.name as usize)?;
+                    let lib = command.name(&bytes[cmd.offset..])?;
                     libs.push(lib);
                 },
                   load_command::CommandVariant::DyldInfo    (command)
@@ -132,14 +448,20 @@ impl<'a> MachO<'a> {
                     export_trie = Some(exports::ExportTrie::new(bytes, &command));
                     bind_interpreter = Some(imports::BindInterpreter::new(bytes, &command));
                 },
-                load_command::CommandVariant::Unixthread(command) => {
-                    entry = command.thread_state.eip as u64;
+                load_command::CommandVariant::DyldExportsTrie(command) => {
+                    export_trie = Some(exports::ExportTrie::new_from_linkedit_data_command(bytes, &command));
+                },
+                load_command::CommandVariant::Unixthread(ref command) => {
+                    entry = command.instruction_pointer(header.cputype).unwrap_or(0);
                 },
                 load_command::CommandVariant::Main(command) => {
                     entry = command.entryoff;
                 },
+                load_command::CommandVariant::Rpath(command) => {
+                    rpaths.push(command.name(&bytes[cmd.offset..])?);
+                },
                 load_command::CommandVariant::IdDylib(command) => {
-                    let id = bytes.pread::<&str>(cmd.offset + command.dylib.name as usize)?;
+                    let id = command.name(&bytes[cmd.offset..])?;
                     libs[0] = id;
                     name = Some(id);
                 },
@@ -147,12 +469,16 @@ impl<'a> MachO<'a> {
             }
             cmds.push(cmd)
         }
+        if cmds.len() < ncmds {
+            return Err(error::Error::Malformed(format!("only {} of the {} load commands fit in sizeofcmds {:#x}", cmds.len(), ncmds, header.sizeofcmds)));
+        }
         Ok(MachO {
             header: header,
             load_commands: cmds,
             segments: segments,
             symbols: symbols,
             libs: libs,
+            rpaths: rpaths,
             export_trie: export_trie,
             bind_interpreter: bind_interpreter,
             entry: entry,
@@ -165,12 +491,62 @@ impl<'a> MachO<'a> {
     }
 }
 
+/// The range `covered..total_len` between the end of the file contents of a binary and the end of its file, if any
+fn overlay(covered: u64, total_len: usize) -> Option<Range<usize>> {
+    if covered < total_len as u64 {
+        Some(covered as usize..total_len)
+    } else {
+        None
+    }
+}
+
+/// Checks that `count` pointers `stride` bytes apart, starting `offset` bytes into the `index`-th of the `segments`, are
+/// all within its file contents, which is where dyld finds the pointers it binds and rebases; `what` describes the
+/// pointers in the error
+fn check_pointers(segments: &[segment::Segment], index: u8, offset: u64, count: u64, stride: u64, what: &str) -> error::Result<()> {
+    let segment = match segments.get(index as usize) {
+        Some(segment) => segment,
+        None => return Err(error::Error::Malformed(format!("{} in segment {}, but there are only {} segments", what, index, segments.len()))),
+    };
+    if count == 0 {
+        return Ok(());
+    }
+    // the file contents were read from the binary, so unlike the vmsize they can't be bigger than it
+    let size = cmp::min(segment.filesize, segment.vmsize);
+    match (count - 1).checked_mul(stride).and_then(|delta| offset.checked_add(delta)) {
+        Some(last) if last < size => Ok(()),
+        _ => Err(error::Error::Malformed(format!("{} {} pointers {:#x} apart from {:#x} overrun segment {} of file size {:#x}", what, count, stride, offset, index, size))),
+    }
+}
+
+/// Checks that `count` more pointers on top of the `recorded` ones of one opcode stream don't outnumber the `size` byte
+/// pointers in the file contents of the `segments`: a stream fixes up each pointer at most once, so a stream repeating
+/// runs over the same pointers can't exhaust memory either
+fn check_pointer_count(segments: &[segment::Segment], size: u64, recorded: usize, count: u64, what: &str) -> error::Result<()> {
+    let slots = segments.iter().fold(0u64, |slots, segment| slots.saturating_add(cmp::min(segment.filesize, segment.vmsize) / size));
+    match (recorded as u64).checked_add(count) {
+        Some(total) if total <= slots => Ok(()),
+        _ => Err(error::Error::Malformed(format!("{} run of {} pointers after {} others is more than the {} pointers the segments hold", what, count, recorded, slots))),
+    }
+}
+
 #[cfg(feature = "std")]
 /// A Mach-o multi architecture (Fat) binary container
 pub struct MultiArch<'a> {
     data: &'a [u8],
     start: usize,
     pub narches: usize,
+    /// The width and byte order of the architecture headers
+    ctx: container::Ctx,
+}
+
+/// Reads the `index`-th architecture header of width and byte order `ctx` of the table at `start` in `data`
+fn fat_arch(data: &[u8], start: usize, index: usize, ctx: container::Ctx) -> error::Result<fat::FatArch64> {
+    let offset = index.checked_mul(fat::arch_size(ctx)).and_then(|delta| delta.checked_add(start));
+    match offset {
+        Some(offset) => fat::FatArch64::parse(data, offset, ctx),
+        None => Err(error::Error::Malformed(format!("fat arch {} at {:#x} overflows", index, start))),
+    }
 }
 
 /// Iterator over the fat architecture headers in a `MultiArch` container
@@ -179,6 +555,7 @@ pub struct FatArchIterator<'a> {
     data: &'a[u8],
     narches: usize,
     start: usize,
+    ctx: container::Ctx,
 }
 
 impl<'a> Iterator for FatArchIterator<'a> {
@@ -187,10 +564,35 @@ impl<'a> Iterator for FatArchIterator<'a> {
         if self.index >= self.narches {
             None
         } else {
-            let offset = (self.index * fat::SIZEOF_FAT_ARCH) + self.start;
-            let arch = self.data.pread_with::<fat::FatArch>(offset, scroll::BE).map_err(|e| e.into());
+            let index = self.index;
+            self.index += 1;
+            Some(fat_arch(self.data, self.start, index, self.ctx).and_then(|arch| {
+                match arch.to_fat_arch() {
+                    Some(arch) => Ok(arch),
+                    None => Err(error::Error::Malformed(format!("fat arch {} at {:#x} + {:#x} doesn't fit in a FatArch, use iter_arches_64", index, arch.offset, arch.size))),
+                }
+            }))
+        }
+    }
+}
+
+/// Iterator over the fat architecture headers in a `MultiArch` container, as `FatArch64`s whatever their width
+pub struct FatArch64Iterator<'a> {
+    index: usize,
+    data: &'a[u8],
+    narches: usize,
+    start: usize,
+    ctx: container::Ctx,
+}
+
+impl<'a> Iterator for FatArch64Iterator<'a> {
+    type Item = error::Result<fat::FatArch64>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.narches {
+            None
+        } else {
             self.index += 1;
-            Some(arch)
+            Some(fat_arch(self.data, self.start, self.index - 1, self.ctx))
         }
     }
 }
@@ -201,6 +603,7 @@ pub struct MachOIterator<'a> {
     data: &'a[u8],
     narches: usize,
     start: usize,
+    ctx: container::Ctx,
 }
 
 impl<'a> Iterator for MachOIterator<'a> {
@@ -210,15 +613,10 @@ impl<'a> Iterator for MachOIterator<'a> {
             None
         } else {
             let index = self.index;
-            let offset = (index * fat::SIZEOF_FAT_ARCH) + self.start;
             self.index += 1;
-            match self.data.pread_with::<fat::FatArch>(offset, scroll::BE) {
-                Ok(arch) => {
-                    let bytes = arch.slice(self.data);
-                    let binary = MachO::parse(bytes, 0);
-                    Some(binary)
-                },
-                Err(e) => Some(Err(e.into()))
+            match fat_arch(self.data, self.start, index, self.ctx) {
+                Ok(arch) => Some(arch.slice(self.data).and_then(|bytes| MachO::parse(bytes, 0))),
+                Err(e) => Some(Err(e))
             }
         }
     }
@@ -233,28 +631,49 @@ impl<'a, 'b> IntoIterator for &'b MultiArch<'a> {
             data: self.data,
             narches: self.narches,
             start: self.start,
+            ctx: self.ctx,
         }
     }
 }
 
 #[cfg(feature = "std")]
 impl<'a> MultiArch<'a> {
-    /// Lazily construct `Self`
+    /// Lazily construct `Self`; `FAT_MAGIC_64` containers have 64-bit architecture headers, which are read with
+    /// `iter_arches_64`
     pub fn new(bytes: &'a [u8]) -> error::Result<Self> {
         let header = fat::FatHeader::parse(bytes)?;
+        // a non-fat magic is treated as FAT_MAGIC, as it always has been
+        let ctx = fat::arch_ctx(header.magic).unwrap_or_else(|| container::Ctx::new(container::Container::Little, scroll::BE));
+        let narches = if ctx.le.is_little() { header.nfat_arch.swap_bytes() } else { header.nfat_arch };
         Ok(MultiArch {
             data: bytes,
             start: fat::SIZEOF_FAT_HEADER,
-            narches: header.nfat_arch as usize
+            narches: narches as usize,
+            ctx: ctx,
         })
     }
-    /// Iterate every fat arch header
+    /// Whether this container has 64-bit (`FAT_MAGIC_64`) architecture headers
+    pub fn is_64(&self) -> bool {
+        self.ctx.is_big()
+    }
+    /// Iterate every fat arch header; a 64-bit header whose offset or size doesn't fit in a `FatArch` is an error
     pub fn iter_arches(&self) -> FatArchIterator {
         FatArchIterator {
             index: 0,
             data: self.data,
             narches: self.narches,
             start: self.start,
+            ctx: self.ctx,
+        }
+    }
+    /// Iterate every fat arch header as a `FatArch64`, whether the container's headers are 32 or 64-bit
+    pub fn iter_arches_64(&self) -> FatArch64Iterator {
+        FatArch64Iterator {
+            index: 0,
+            data: self.data,
+            narches: self.narches,
+            start: self.start,
+            ctx: self.ctx,
         }
     }
     /// Return all the architectures in this binary
@@ -270,9 +689,8 @@ impl<'a> MultiArch<'a> {
         if index >= self.narches {
             return Err(error::Error::Malformed(format!("Requested the {}-th binary, but there are only {} architectures in this container", index, self.narches).into()))
         }
-        let offset = (index * fat::SIZEOF_FAT_ARCH) + self.start;
-        let arch = self.data.pread_with::<fat::FatArch>(offset, scroll::BE)?;
-        let bytes = arch.slice(self.data);
+        let arch = fat_arch(self.data, self.start, index, self.ctx)?;
+        let bytes = arch.slice(self.data)?;
         Ok(MachO::parse(bytes, 0)?)
     }
 
@@ -284,13 +702,29 @@ impl<'a> MultiArch<'a> {
         }
         None
     }
-    /// Try and find the `cputype` in `Self`, if there is one
-    pub fn find_cputype(&self, cputype: u32) -> error::Result<Option<fat::FatArch>> {
+    /// Try and find the `cputype` in `Self`, if there is one.
+    ///
+    /// When a `cpusubtype` is given, an arch with exactly that subtype (ignoring the capability bits) is preferred,
+    /// falling back to an arch whose subtype is `CPU_SUBTYPE_ANY`; `None` (or `CPU_SUBTYPE_ANY`) accepts any subtype.
+    pub fn find_cputype(&self, cputype: u32, cpusubtype: Option<u32>) -> error::Result<Option<fat::FatArch>> {
+        use self::cputype::{CPU_SUBTYPE_ANY, CPU_SUBTYPE_MASK};
+        let mut fallback = None;
         for arch in self.iter_arches() {
             let arch = arch?;
-            if arch.cputype == cputype { return Ok(Some(arch)) }
+            if arch.cputype != cputype { continue }
+            match cpusubtype {
+                None | Some(CPU_SUBTYPE_ANY) => return Ok(Some(arch)),
+                Some(cpusubtype) => {
+                    if arch.cpusubtype & !CPU_SUBTYPE_MASK == cpusubtype & !CPU_SUBTYPE_MASK {
+                        return Ok(Some(arch))
+                    }
+                    if arch.cpusubtype == CPU_SUBTYPE_ANY && fallback.is_none() {
+                        fallback = Some(arch);
+                    }
+                }
+            }
         }
-        Ok(None)
+        Ok(fallback)
     }
 }
 
@@ -324,7 +758,7 @@ impl<'a> Mach<'a> {
         }
         let magic = peek(&bytes, 0)?;
         match magic {
-            fat::FAT_MAGIC => {
+            fat::FAT_MAGIC | fat::FAT_CIGAM | fat::FAT_MAGIC_64 | fat::FAT_CIGAM_64 => {
                 let multi = MultiArch::new(bytes)?;
                 Ok(Mach::Fat(multi))
             },
@@ -335,4 +769,168 @@ impl<'a> Mach<'a> {
             }
         }
     }
+    /// The paths of the dylibs this binary loads, in order; empty for a fat binary, whose architectures may each load
+    /// different dylibs, see `MultiArch::get`
+    pub fn libraries(&self) -> Vec<&'a str> {
+        match *self {
+            Mach::Binary(ref binary) => binary.libraries(),
+            Mach::Fat(_) => Vec::new(),
+        }
+    }
+    /// The `LC_RPATH` search paths of this binary, in order; empty for a fat binary, since each architecture carries its
+    /// own, see `MultiArch::get`
+    pub fn rpaths(&self) -> Vec<&'a str> {
+        match *self {
+            Mach::Binary(ref binary) => binary.rpaths(),
+            Mach::Fat(_) => Vec::new(),
+        }
+    }
+    /// The exported symbols of this binary, with their virtual addresses and sizes, as `MachO::exports`; empty for a fat
+    /// binary, whose architectures may each export different symbols, see `MultiArch::get`
+    pub fn exports(&self) -> error::Result<Vec<exports::Export>> {
+        match *self {
+            Mach::Binary(ref binary) => binary.exports(),
+            Mach::Fat(_) => Ok(Vec::new()),
+        }
+    }
+    /// The name of the library with the (two-level namespace) library `ordinal` of a symbol, as `MachO::library_ordinal_name`;
+    /// `None` for a fat binary, whose architectures may each load different libraries, see `MultiArch::get`
+    pub fn library_ordinal_name(&self, ordinal: u16) -> Option<&'a str> {
+        match *self {
+            Mach::Binary(ref binary) => binary.library_ordinal_name(ordinal),
+            Mach::Fat(_) => None,
+        }
+    }
+    /// Whether this binary is encrypted; `false` for a fat binary, as `LC_ENCRYPTION_INFO` is per architecture and App
+    /// Store binaries often encrypt only some of them, see `MultiArch::get`
+    pub fn is_encrypted(&self) -> bool {
+        match *self {
+            Mach::Binary(ref binary) => binary.is_encrypted(),
+            Mach::Fat(_) => false,
+        }
+    }
+    /// Renders every load command in order in the style of `otool -l`; empty for a fat binary, which has no load
+    /// commands of its own, so dump each architecture from `MultiArch::get` instead
+    pub fn dump_load_commands(&self) -> String {
+        match *self {
+            Mach::Binary(ref binary) => binary.dump_load_commands(),
+            Mach::Fat(_) => String::new(),
+        }
+    }
+    /// The sorted function starts recorded by the `LC_FUNCTION_STARTS` command of this binary, with the sections they
+    /// are in; empty for a fat binary, whose architectures each have their own, see `MultiArch::get`
+    pub fn function_starts(&self) -> error::Result<Vec<FunctionStart>> {
+        match *self {
+            Mach::Binary(ref binary) => binary.function_starts(),
+            Mach::Fat(_) => Ok(Vec::new()),
+        }
+    }
+    /// The virtual address this binary starts executing at, if it has an entry point; `None` for a fat binary, as each
+    /// architecture has its own, see `MultiArch::get`
+    pub fn entry_point(&self) -> Option<u64> {
+        match *self {
+            Mach::Binary(ref binary) => binary.entry_point(),
+            Mach::Fat(_) => None,
+        }
+    }
+    /// The UUID of this binary, if it has one; `None` for a fat binary, as each architecture has its own, see
+    /// `MultiArch::get`
+    pub fn uuid(&self) -> Option<[u8; 16]> {
+        match *self {
+            Mach::Binary(ref binary) => binary.uuid(),
+            Mach::Fat(_) => None,
+        }
+    }
+    /// The raw code signature blob of this binary, if it has one; `None` for a fat binary, as each architecture is
+    /// signed separately, see `MultiArch::get`
+    pub fn code_signature(&self) -> error::Result<Option<&'a [u8]>> {
+        match *self {
+            Mach::Binary(ref binary) => binary.code_signature(),
+            Mach::Fat(_) => Ok(None),
+        }
+    }
+    /// The platform and minimum OS version this binary was built to run on; `None` for a fat binary, whose
+    /// architectures commonly target different OS versions, see `MultiArch::get`
+    pub fn minimum_version(&self) -> Option<(load_command::Platform, (u16, u8, u8))> {
+        match *self {
+            Mach::Binary(ref binary) => binary.minimum_version(),
+            Mach::Fat(_) => None,
+        }
+    }
+    /// The virtual memory this binary's segments take up when loaded, without `__PAGEZERO`; 0 for a fat binary, since
+    /// only one of its architectures is ever loaded, see `MultiArch::get`
+    pub fn vm_size(&self) -> u64 {
+        match *self {
+            Mach::Binary(ref binary) => binary.vm_size(),
+            Mach::Fat(_) => 0,
+        }
+    }
+    /// The size of the `__PAGEZERO` segment, if there is one; `None` for a fat binary, which has no segments of its
+    /// own, see `MultiArch::get`
+    pub fn page_zero_size(&self) -> Option<u64> {
+        match *self {
+            Mach::Binary(ref binary) => binary.page_zero_size(),
+            Mach::Fat(_) => None,
+        }
+    }
+    /// The range of the data appended to this binary past the end of its segments, if the file of length `total_len`
+    /// it was read from has any; `None` for a fat binary, whose file offsets are relative to the container rather than
+    /// any one architecture, see `MultiArch::get`
+    pub fn overlay(&self, total_len: usize) -> Option<Range<usize>> {
+        match *self {
+            Mach::Binary(ref binary) => binary.overlay(total_len),
+            Mach::Fat(_) => None,
+        }
+    }
+    /// The file range of the `__LINKEDIT` segment, if there is one; `None` for a fat binary, which has no segments of
+    /// its own, see `MultiArch::get`
+    pub fn linkedit_range(&self) -> Option<Range<u64>> {
+        match *self {
+            Mach::Binary(ref binary) => binary.linkedit_range(),
+            Mach::Fat(_) => None,
+        }
+    }
+    /// The end of the file contents of this binary's segments; 0 for a fat binary, which has no segments of its own,
+    /// see `MultiArch::get`
+    pub fn file_size_covered(&self) -> u64 {
+        match *self {
+            Mach::Binary(ref binary) => binary.file_size_covered(),
+            Mach::Fat(_) => 0,
+        }
+    }
+    /// The filetype of this binary; `None` for a fat binary, which has no header of its own, see `MultiArch::get`
+    pub fn file_type(&self) -> Option<header::FileType> {
+        match *self {
+            Mach::Binary(ref binary) => Some(binary.file_type()),
+            Mach::Fat(_) => None,
+        }
+    }
+    /// Whether this binary is an executable
+    pub fn is_executable(&self) -> bool {
+        self.file_type().map(|filetype| filetype.is_executable()).unwrap_or(false)
+    }
+    /// Whether this binary is a shared library
+    pub fn is_library(&self) -> bool {
+        self.file_type().map(|filetype| filetype.is_library()).unwrap_or(false)
+    }
+    /// Whether this binary is a relocatable object file, as `MachO::is_object_file`
+    pub fn is_object(&self) -> bool {
+        self.file_type().map(|filetype| filetype.is_object()).unwrap_or(false)
+    }
+    /// Returns the `(entry_id, vmaddr, fileoff)` of every Mach-O image embedded in a kernelcache or other fileset binary;
+    /// empty for a fat binary, see `MultiArch::get`
+    pub fn fileset_entries(&self) -> Vec<(String, u64, u64)> {
+        match *self {
+            Mach::Binary(ref binary) => binary.fileset_entries(),
+            Mach::Fat(_) => Vec::new(),
+        }
+    }
+    /// Parses the embedded Mach-O image whose entry id is `id`, without copying the bytes, or `None` if there is no such
+    /// entry; always `None` for a fat binary, see `MultiArch::get`
+    pub fn fileset_entry_macho(&self, id: &str) -> error::Result<Option<Mach<'a>>> {
+        match *self {
+            Mach::Binary(ref binary) => Ok(binary.fileset_entry_macho(id)?.map(Mach::Binary)),
+            Mach::Fat(_) => Ok(None),
+        }
+    }
 }
@@ -0,0 +1,87 @@
+//! The Objective-C image info of a binary, which records the Objective-C ABI and Swift version it was built with
+//!
+//! It is in the `__objc_imageinfo` section of the `__DATA` (or `__DATA_CONST`) segment in binaries using the modern
+//! (Objective-C 2) runtime, and in the `__image_info` section of the `__OBJC` segment in binaries using the legacy one.
+
+use scroll::{Pread, Endian};
+use error;
+
+/// The size of an `objc_image_info`
+pub const SIZEOF_IMAGE_INFO: usize = 8;
+
+/// The image's classes replace those of another image (fix and continue)
+pub const OBJC_IMAGE_IS_REPLACEMENT: u32 = 1 << 0;
+/// The image supports garbage collection
+pub const OBJC_IMAGE_SUPPORTS_GC: u32 = 1 << 1;
+/// The image requires garbage collection
+pub const OBJC_IMAGE_REQUIRES_GC: u32 = 1 << 2;
+/// The image's selectors and classes have been optimized by dyld's shared cache builder
+pub const OBJC_IMAGE_OPTIMIZED_BY_DYLD: u32 = 1 << 3;
+/// The image was built for a simulator
+pub const OBJC_IMAGE_IS_SIMULATED: u32 = 1 << 5;
+/// The image's categories have class properties
+pub const OBJC_IMAGE_HAS_CATEGORY_CLASS_PROPERTIES: u32 = 1 << 6;
+/// Mask for the version of the Swift ABI from before it was stable, which is 0 for images without Swift
+pub const OBJC_IMAGE_SWIFT_UNSTABLE_VERSION_MASK: u32 = 0xff << 8;
+/// Mask for the version of the stable Swift ABI
+pub const OBJC_IMAGE_SWIFT_STABLE_VERSION_MASK: u32 = 0xffff << 16;
+
+/// The section of the modern runtime's image info, in the `__DATA` or `__DATA_CONST` segment
+pub const SECT_OBJC_IMAGEINFO: &str = "__objc_imageinfo";
+/// The segment of the legacy runtime's image info
+pub const SEG_OBJC: &str = "__OBJC";
+/// The section of the legacy runtime's image info
+pub const SECT_IMAGE_INFO: &str = "__image_info";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An `objc_image_info`
+pub struct ImageInfo {
+    /// The version of the image info, which is always 0
+    pub version: u32,
+    /// The `OBJC_IMAGE_*` flags and the Swift version
+    pub flags: u32,
+    objc2: bool,
+}
+
+impl ImageInfo {
+    /// Parse the image info at the start of the `__objc_imageinfo` section data `bytes`
+    pub fn parse(bytes: &[u8], le: Endian) -> error::Result<Self> {
+        if bytes.len() < SIZEOF_IMAGE_INFO {
+            return Err(error::Error::Malformed(format!("Objective-C image info of size {:#x} is smaller than {:#x}", bytes.len(), SIZEOF_IMAGE_INFO)));
+        }
+        Ok(ImageInfo {
+            version: bytes.pread_with(0, le)?,
+            flags: bytes.pread_with(4, le)?,
+            objc2: true,
+        })
+    }
+    /// Parse the image info at the start of the legacy runtime's `__OBJC,__image_info` section data `bytes`
+    pub fn parse_legacy(bytes: &[u8], le: Endian) -> error::Result<Self> {
+        let info = ImageInfo::parse(bytes, le)?;
+        Ok(ImageInfo { objc2: false, ..info })
+    }
+    /// Whether this image uses the modern (Objective-C 2) runtime, i.e. it was read from an `__objc_imageinfo` section
+    pub fn is_objc2(&self) -> bool {
+        self.objc2
+    }
+    /// Whether `flag`, e.g. `OBJC_IMAGE_SUPPORTS_GC`, is set
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags & flag == flag
+    }
+    /// Whether the image supports garbage collection
+    pub fn supports_gc(&self) -> bool {
+        self.has_flag(OBJC_IMAGE_SUPPORTS_GC)
+    }
+    /// The version of the unstable Swift ABI the image was built with, or `None` if it has no Swift code; images built
+    /// since the ABI became stable still set this (to 7), alongside `swift_stable_version`
+    pub fn swift_version(&self) -> Option<u8> {
+        match ((self.flags & OBJC_IMAGE_SWIFT_UNSTABLE_VERSION_MASK) >> 8) as u8 {
+            0 => None,
+            version => Some(version),
+        }
+    }
+    /// The version of the stable Swift ABI the image was built with, or 0 if none
+    pub fn swift_stable_version(&self) -> u16 {
+        ((self.flags & OBJC_IMAGE_SWIFT_STABLE_VERSION_MASK) >> 16) as u16
+    }
+}
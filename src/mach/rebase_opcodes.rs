@@ -0,0 +1,41 @@
+//! Rebase opcodes are interpreted by the dynamic linker to slide every absolute pointer in this binary when it is loaded at an address other than its preferred one
+//!
+//! The rebase information is a stream of byte sized opcodes whose
+//! symbolic names start with REBASE_OPCODE_.  Conceptually the rebase
+//! information is a table of tuples: <seg-index, seg-offset, type>.
+//! The opcodes are a compressed way to encode the table by only
+//! encoding when a column changes.  In addition simple patterns like
+//! "every n'th offset for m times" can be encoded in a few bytes.
+
+pub type Opcode = u8;
+
+// The following are used to encode rebasing information
+pub const REBASE_TYPE_POINTER                              : u8     = 1;
+pub const REBASE_TYPE_TEXT_ABSOLUTE32                      : u8     = 2;
+pub const REBASE_TYPE_TEXT_PCREL32                         : u8     = 3;
+pub const REBASE_OPCODE_MASK                               : u8     = 0xF0;
+pub const REBASE_IMMEDIATE_MASK                            : u8     = 0x0F;
+pub const REBASE_OPCODE_DONE                               : Opcode = 0x00;
+pub const REBASE_OPCODE_SET_TYPE_IMM                       : Opcode = 0x10;
+pub const REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB        : Opcode = 0x20;
+pub const REBASE_OPCODE_ADD_ADDR_ULEB                      : Opcode = 0x30;
+pub const REBASE_OPCODE_ADD_ADDR_IMM_SCALED                : Opcode = 0x40;
+pub const REBASE_OPCODE_DO_REBASE_IMM_TIMES                : Opcode = 0x50;
+pub const REBASE_OPCODE_DO_REBASE_ULEB_TIMES               : Opcode = 0x60;
+pub const REBASE_OPCODE_DO_REBASE_ADD_ADDR_ULEB            : Opcode = 0x70;
+pub const REBASE_OPCODE_DO_REBASE_ULEB_TIMES_SKIPPING_ULEB : Opcode = 0x80;
+
+pub fn opcode_to_str(opcode: Opcode) -> &'static str {
+    match opcode {
+        REBASE_OPCODE_DONE => "REBASE_OPCODE_DONE",
+        REBASE_OPCODE_SET_TYPE_IMM => "REBASE_OPCODE_SET_TYPE_IMM",
+        REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB => "REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB",
+        REBASE_OPCODE_ADD_ADDR_ULEB => "REBASE_OPCODE_ADD_ADDR_ULEB",
+        REBASE_OPCODE_ADD_ADDR_IMM_SCALED => "REBASE_OPCODE_ADD_ADDR_IMM_SCALED",
+        REBASE_OPCODE_DO_REBASE_IMM_TIMES => "REBASE_OPCODE_DO_REBASE_IMM_TIMES",
+        REBASE_OPCODE_DO_REBASE_ULEB_TIMES => "REBASE_OPCODE_DO_REBASE_ULEB_TIMES",
+        REBASE_OPCODE_DO_REBASE_ADD_ADDR_ULEB => "REBASE_OPCODE_DO_REBASE_ADD_ADDR_ULEB",
+        REBASE_OPCODE_DO_REBASE_ULEB_TIMES_SKIPPING_ULEB => "REBASE_OPCODE_DO_REBASE_ULEB_TIMES_SKIPPING_ULEB",
+        _ => "UNKNOWN OPCODE"
+    }
+}
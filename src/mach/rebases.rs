@@ -0,0 +1,130 @@
+//! Pointers which the dynamic linker slides when this binary is not loaded at its preferred address
+
+use core::ops::Range;
+use core::fmt::{self, Debug};
+use scroll::Pread;
+
+use container;
+use error;
+use mach::{check_pointers, check_pointer_count};
+use mach::leb;
+use mach::load_command;
+use mach::rebase_opcodes;
+use mach::segment;
+
+#[derive(Debug, Clone, Copy)]
+/// A pointer which the dynamic linker rebases, as described by the rebase opcodes
+pub struct Rebase {
+    /// The index of the segment the pointer is in
+    pub segment_index: u8,
+    /// The offset of the pointer from the start of its segment
+    pub segment_offset: u64,
+    /// The `REBASE_TYPE_*` of this rebase
+    pub rebase_type: u8,
+}
+
+/// An interpreter for mach REBASE opcodes
+pub struct RebaseInterpreter<'a> {
+    data: &'a [u8],
+    location: Range<usize>,
+}
+
+impl<'a> Debug for RebaseInterpreter<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(fmt, "RebaseInterpreter {{")?;
+        writeln!(fmt, "  Location: {:#x}..{:#x}", self.location.start, self.location.end)?;
+        writeln!(fmt, "}}")
+    }
+}
+
+impl<'a> RebaseInterpreter<'a> {
+    /// Construct a new rebase interpreter from `bytes` and the load `command`
+    pub fn new(bytes: &'a [u8], command: &load_command::DyldInfoCommand) -> Self {
+        let start = command.rebase_off as usize;
+        RebaseInterpreter {
+            data: bytes,
+            location: start..start + command.rebase_size as usize,
+        }
+    }
+    /// Return every rebase in this binary, erroring if one is in a segment which isn't one of the binary's `segments`, or
+    /// past the end of its file contents
+    pub fn rebases(&self, segments: &[segment::Segment], ctx: &container::Ctx) -> error::Result<Vec<Rebase>> {
+        use mach::rebase_opcodes::*;
+        let mut rebases = Vec::new();
+        let mut rebase = Rebase { segment_index: 0, segment_offset: 0, rebase_type: 0 };
+        let size = ctx.size() as u64;
+        let offset = &mut self.location.start.clone();
+        // advances the segment offset by `count` * `stride`, erroring instead of overflowing
+        let advance = |segment_offset: u64, count: u64, stride: u64| -> error::Result<u64> {
+            count.checked_mul(stride).and_then(|delta| segment_offset.checked_add(delta)).ok_or_else(|| {
+                error::Error::Malformed(format!("rebase segment offset {:#x} overflows when advanced {} times by {:#x}", segment_offset, count, stride))
+            })
+        };
+        // checks that `count` pointers `stride` apart from the current one are within its segment, and that there are
+        // pointers enough for them on top of the `recorded` ones
+        let check = |rebase: &Rebase, recorded: usize, count: u64, stride: u64| -> error::Result<()> {
+            check_pointers(segments, rebase.segment_index, rebase.segment_offset, count, stride, "rebase")?;
+            check_pointer_count(segments, size, recorded, count, "rebase")
+        };
+        while *offset < self.location.end {
+            let opcode = self.data.gread::<u8>(offset)? as rebase_opcodes::Opcode;
+            let immediate = opcode & REBASE_IMMEDIATE_MASK;
+            match opcode & REBASE_OPCODE_MASK {
+                REBASE_OPCODE_DONE => break,
+                REBASE_OPCODE_SET_TYPE_IMM => {
+                    rebase.rebase_type = immediate;
+                },
+                REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB => {
+                    if immediate as usize >= segments.len() {
+                        return Err(error::Error::Malformed(format!("rebase opcode at {:#x} sets segment {}, but there are only {} segments", *offset - 1, immediate, segments.len())));
+                    }
+                    rebase.segment_index = immediate;
+                    rebase.segment_offset = leb::read_uleb128(self.data, offset)?;
+                },
+                REBASE_OPCODE_ADD_ADDR_ULEB => {
+                    let addr = leb::read_uleb128(self.data, offset)?;
+                    rebase.segment_offset = advance(rebase.segment_offset, 1, addr)?;
+                },
+                REBASE_OPCODE_ADD_ADDR_IMM_SCALED => {
+                    rebase.segment_offset = advance(rebase.segment_offset, immediate as u64, size)?;
+                },
+                REBASE_OPCODE_DO_REBASE_IMM_TIMES | REBASE_OPCODE_DO_REBASE_ULEB_TIMES => {
+                    let count = if opcode & REBASE_OPCODE_MASK == REBASE_OPCODE_DO_REBASE_IMM_TIMES {
+                        immediate as u64
+                    } else {
+                        leb::read_uleb128(self.data, offset)?
+                    };
+                    // validate the whole run before recording any of it, so a bogus count can't exhaust memory
+                    check(&rebase, rebases.len(), count, size)?;
+                    advance(rebase.segment_offset, count, size)?;
+                    for _i in 0..count {
+                        rebases.push(rebase);
+                        rebase.segment_offset += size;
+                    }
+                },
+                REBASE_OPCODE_DO_REBASE_ADD_ADDR_ULEB => {
+                    check(&rebase, rebases.len(), 1, size)?;
+                    rebases.push(rebase);
+                    let addr = leb::read_uleb128(self.data, offset)?;
+                    rebase.segment_offset = advance(rebase.segment_offset, 1, addr)
+                        .and_then(|segment_offset| advance(segment_offset, 1, size))?;
+                },
+                REBASE_OPCODE_DO_REBASE_ULEB_TIMES_SKIPPING_ULEB => {
+                    let count = leb::read_uleb128(self.data, offset)?;
+                    let skip = leb::read_uleb128(self.data, offset)?;
+                    let stride = skip.checked_add(size).ok_or_else(|| error::Error::Malformed(format!("rebase skip {:#x} overflows", skip)))?;
+                    check(&rebase, rebases.len(), count, stride)?;
+                    advance(rebase.segment_offset, count, stride)?;
+                    for _i in 0..count {
+                        rebases.push(rebase);
+                        rebase.segment_offset += stride;
+                    }
+                },
+                _ => {
+                    return Err(error::Error::Malformed(format!("unknown rebase opcode {:#x} at {:#x}", opcode, *offset - 1)));
+                }
+            }
+        }
+        Ok(rebases)
+    }
+}
@@ -23,9 +23,9 @@
 // by the link-editor.  The value R_ABS is used for relocation entries for
 // absolute symbols which need no further relocation.
 use core::fmt;
+use scroll;
 use mach;
 
-// TODO: armv7 relocations are scattered, must and r_address with 0x8000_0000 to check if its scattered or not
 #[derive(Copy, Clone, Pread, Pwrite, IOwrite, IOread)]
 #[repr(C)]
 pub struct RelocationInfo {
@@ -36,6 +36,8 @@ pub struct RelocationInfo {
     pub r_info: u32,
 }
 
+pub const SIZEOF_RELOCATION_INFO: usize = 8;
+
 impl RelocationInfo {
     /// Symbol index if `r_extern` == 1 or section ordinal if `r_extern` == 0. In bits :24
     #[inline]
@@ -72,12 +74,152 @@ impl RelocationInfo {
     pub fn is_pic(&self) -> bool {
         self.r_pcrel() > 0
     }
+    /// Symbol index if `is_extern()`, or section ordinal otherwise
+    #[inline]
+    pub fn symbolnum(&self) -> usize {
+        self.r_symbolnum()
+    }
+    /// Whether the relocated value is pc relative
+    #[inline]
+    pub fn is_pcrel(&self) -> bool {
+        self.r_pcrel() == 1
+    }
+    /// The width in bytes of the relocated value: 1, 2, 4 or 8
+    #[inline]
+    pub fn length(&self) -> usize {
+        1 << self.r_length()
+    }
+    /// Converts this relocation from a big-endian binary, where the `r_info` bitfields are allocated starting from
+    /// the most significant bit, to the little-endian layout the accessors of this struct decode
+    pub fn to_little_endian_bitfields(self) -> Self {
+        let r_info = self.r_info;
+        let symbolnum = r_info >> 8;
+        let pcrel = (r_info >> 7) & 0x1;
+        let length = (r_info >> 5) & 0x3;
+        let ext = (r_info >> 4) & 0x1;
+        let typ = r_info & 0xf;
+        RelocationInfo {
+            r_address: self.r_address,
+            r_info: symbolnum | pcrel << 24 | length << 25 | ext << 27 | typ << 28,
+        }
+    }
+    /// If true, this entry is actually a `ScatteredRelocationInfo`, and its fields must not be interpreted as laid out here
+    #[inline]
+    pub fn is_scattered(&self) -> bool {
+        self.r_address as u32 & R_SCATTERED != 0
+    }
     /// Returns a string representation of this relocation, given the machine `cputype`
     pub fn to_str(&self, cputype: mach::cputype::CpuType) -> &'static str {
         reloc_to_str(self.r_type(), cputype)
     }
 }
 
+/// Set in the first word of a relocation entry when it is a scattered relocation
+pub const R_SCATTERED: u32 = 0x8000_0000;
+
+/// A scattered relocation entry, which records the address of the relocated value rather than a symbol or section
+/// ordinal, and is distinguished from a `RelocationInfo` by `R_SCATTERED` being set in its first word
+#[derive(Copy, Clone, Pread, Pwrite, IOwrite, IOread)]
+#[repr(C)]
+pub struct ScatteredRelocationInfo {
+    /// Contains all of the relocation info as a bitfield.
+    /// r_address, 24 bits, r_type 4 bits, r_length 2 bits, r_pcrel 1 bit, r_scattered 1 bit
+    pub r_info: u32,
+    /// The address of the relocatable expression for the item in the file that needs to be updated
+    pub r_value: i32,
+}
+
+impl ScatteredRelocationInfo {
+    /// Offset in the section to what is being relocated, in bits :24
+    #[inline]
+    pub fn r_address(&self) -> u32 {
+        self.r_info & 0x00ff_ffff
+    }
+    /// If not 0, machine specific relocation type, in bits :4
+    #[inline]
+    pub fn r_type(&self) -> u8 {
+        ((self.r_info & 0x0f00_0000) >> 24) as u8
+    }
+    /// The length of the relocation, 0=byte, 1=word, 2=long, 3=quad, 2 bits
+    #[inline]
+    pub fn r_length(&self) -> u8 {
+        ((self.r_info & 0x3000_0000) >> 28) as u8
+    }
+    /// Was relocated pc relative already, 1 bit
+    #[inline]
+    pub fn r_pcrel(&self) -> u8 {
+        ((self.r_info & 0x4000_0000) >> 30) as u8
+    }
+    /// If true, this is a PIC relocation
+    #[inline]
+    pub fn is_pic(&self) -> bool {
+        self.r_pcrel() > 0
+    }
+    /// Returns a string representation of this relocation, given the machine `cputype`
+    pub fn to_str(&self, cputype: mach::cputype::CpuType) -> &'static str {
+        reloc_to_str(self.r_type(), cputype)
+    }
+}
+
+impl fmt::Debug for ScatteredRelocationInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScatteredRelocationInfo")
+            .field("r_address", &format_args!("{:#x}", &self.r_address()))
+            .field("r_type", &self.r_type())
+            .field("r_length", &self.r_length())
+            .field("r_pcrel", &self.r_pcrel())
+            .field("r_value", &format_args!("{:#x}", &self.r_value))
+            .finish()
+    }
+}
+
+/// A relocation entry, which is either a regular or a scattered relocation
+#[derive(Debug, Copy, Clone)]
+pub enum Relocation {
+    Normal(RelocationInfo),
+    Scattered(ScatteredRelocationInfo),
+}
+
+impl Relocation {
+    /// Whether this is a scattered relocation
+    pub fn is_scattered(&self) -> bool {
+        match *self {
+            Relocation::Scattered(_) => true,
+            Relocation::Normal(_) => false,
+        }
+    }
+    /// Returns a string representation of this relocation's type, given the machine `cputype`
+    pub fn to_str(&self, cputype: mach::cputype::CpuType) -> &'static str {
+        match *self {
+            Relocation::Normal(ref reloc) => reloc.to_str(cputype),
+            Relocation::Scattered(ref reloc) => reloc.to_str(cputype),
+        }
+    }
+    /// Reinterprets `reloc`, read with the endianness `le`, as a scattered relocation if it is one.
+    ///
+    /// The bitfields of a non-scattered relocation from a big-endian binary are converted to the little-endian layout;
+    /// scattered relocations have the same layout either way.
+    pub fn new(reloc: RelocationInfo, le: scroll::Endian) -> Self {
+        if reloc.is_scattered() {
+            Relocation::Scattered(ScatteredRelocationInfo {
+                r_info: reloc.r_address as u32,
+                r_value: reloc.r_info as i32,
+            })
+        } else if le.is_little() {
+            Relocation::Normal(reloc)
+        } else {
+            Relocation::Normal(reloc.to_little_endian_bitfields())
+        }
+    }
+}
+
+impl From<RelocationInfo> for Relocation {
+    /// Reinterprets `reloc`, read from a little-endian binary, as a scattered relocation if it is one
+    fn from(reloc: RelocationInfo) -> Self {
+        Relocation::new(reloc, scroll::LE)
+    }
+}
+
 /// Absolute relocation type for Mach-O files
 pub const R_ABS: u8 = 0;
 
@@ -122,6 +264,8 @@ pub const GENERIC_RELOC_PAIR: u8 = 1;
 pub const GENERIC_RELOC_SECTDIFF: u8 = 2;
 pub const GENERIC_RELOC_LOCAL_SECTDIFF: u8 = 3;
 pub const GENERIC_RELOC_PB_LA_P: u8 = 4;
+/// Thread local variable
+pub const GENERIC_RELOC_TLV: u8 = 5;
 
 // arm relocations
 pub const ARM_RELOC_VANILLA: u8 = GENERIC_RELOC_VANILLA;
@@ -138,7 +282,8 @@ pub const ARM_RELOC_HALF_SECTDIFF: u8 = 9;
 
 /// For pointers.
 pub const ARM64_RELOC_UNSIGNED: u8 = 0;
-/// Must be followed by an ARM64_RELOC_UNSIGNED
+/// Must be followed by an ARM64_RELOC_UNSIGNED. The pair relocates `minuend - subtrahend`, where this relocation's
+/// symbol is the subtrahend and the ARM64_RELOC_UNSIGNED's the minuend, plus the content at the relocated address
 pub const ARM64_RELOC_SUBTRACTOR: u8 = 1;
 /// A B/BL instruction with 26-bit displacement.
 pub const ARM64_RELOC_BRANCH26: u8 = 2;
@@ -156,14 +301,52 @@ pub const ARM64_RELOC_POINTER_TO_GOT: u8 = 7;
 pub const ARM64_RELOC_TLVP_LOAD_PAGE21: u8 = 8;
 /// Offset within page of TLVP slot, scaled by r_length.
 pub const ARM64_RELOC_TLVP_LOAD_PAGEOFF12: u8 = 9;
-/// Must be followed by ARM64_RELOC_PAGE21 or ARM64_RELOC_PAGEOFF12.
+/// Must be followed by ARM64_RELOC_PAGE21 or ARM64_RELOC_PAGEOFF12. It has no symbol: its `r_symbolnum` is instead the
+/// 24-bit signed addend of the relocation that follows it, whose instruction has no room for one.
 pub const ARM64_RELOC_ADDEND: u8 = 10;
 
+// powerpc relocations
+pub const PPC_RELOC_VANILLA: u8 = GENERIC_RELOC_VANILLA;
+/// The second relocation entry of a pair
+pub const PPC_RELOC_PAIR: u8 = GENERIC_RELOC_PAIR;
+/// 14 bit branch displacement (to a word address)
+pub const PPC_RELOC_BR14: u8 = 2;
+/// 24 bit branch displacement (to a word address)
+pub const PPC_RELOC_BR24: u8 = 3;
+/// A PAIR follows with the low half
+pub const PPC_RELOC_HI16: u8 = 4;
+/// A PAIR follows with the high half
+pub const PPC_RELOC_LO16: u8 = 5;
+/// Same as the HI16 except the low 16 bits and the high 16 bits are added together with the low 16 bits sign extended
+/// first. This means if bit 15 of the low 16 bits is set the high 16 bits stored in the instruction will be adjusted.
+pub const PPC_RELOC_HA16: u8 = 6;
+/// Same as the LO16 except that the low 2 bits are not stored in the instruction and are always zero
+pub const PPC_RELOC_LO14: u8 = 7;
+/// A PAIR follows
+pub const PPC_RELOC_SECTDIFF: u8 = 8;
+/// Prebound lazy pointer
+pub const PPC_RELOC_PB_LA_PTR: u8 = 9;
+/// Section difference forms of the HI16, LO16, HA16 and LO14 relocations
+pub const PPC_RELOC_HI16_SECTDIFF: u8 = 10;
+pub const PPC_RELOC_LO16_SECTDIFF: u8 = 11;
+pub const PPC_RELOC_HA16_SECTDIFF: u8 = 12;
+pub const PPC_RELOC_JBSR: u8 = 13;
+pub const PPC_RELOC_LO14_SECTDIFF: u8 = 14;
+/// Like PPC_RELOC_SECTDIFF, but the symbol referenced was local
+pub const PPC_RELOC_LOCAL_SECTDIFF: u8 = 15;
+
+/// The name of the relocation type `reloc` under `cputype`; see `reloc_type_to_str`
 pub fn reloc_to_str(reloc: u8, cputype: mach::cputype::CpuType) -> &'static str {
+    reloc_type_to_str(cputype, reloc)
+}
+
+/// The name of relocation type `r_type`, e.g. `"ARM64_RELOC_BRANCH26"`, whose meaning depends entirely on the `cputype`
+/// of the binary it is in
+pub fn reloc_type_to_str(cputype: mach::cputype::CpuType, r_type: u8) -> &'static str {
     use mach::constants::cputype::*;
     match cputype {
         CPU_TYPE_ARM64 => {
-            match reloc {
+            match r_type {
                 ARM64_RELOC_UNSIGNED => "ARM64_RELOC_UNSIGNED",
                 ARM64_RELOC_SUBTRACTOR => "ARM64_RELOC_SUBTRACTOR",
                 ARM64_RELOC_BRANCH26 => "ARM64_RELOC_BRANCH26",
@@ -179,7 +362,7 @@ pub fn reloc_to_str(reloc: u8, cputype: mach::cputype::CpuType) -> &'static str
             }
         },
         CPU_TYPE_X86_64 => {
-            match reloc {
+            match r_type {
                 X86_64_RELOC_UNSIGNED => "X86_64_RELOC_UNSIGNED",
                 X86_64_RELOC_SIGNED => "X86_64_RELOC_SIGNED",
                 X86_64_RELOC_BRANCH => "X86_64_RELOC_BRANCH",
@@ -194,7 +377,7 @@ pub fn reloc_to_str(reloc: u8, cputype: mach::cputype::CpuType) -> &'static str
             }
         },
         CPU_TYPE_ARM => {
-            match reloc {
+            match r_type {
                 ARM_RELOC_VANILLA => "ARM_RELOC_VANILLA",
                 ARM_RELOC_PAIR => "ARM_RELOC_PAIR",
                 ARM_RELOC_SECTDIFF => "ARM_RELOC_SECTDIFF",
@@ -209,12 +392,34 @@ pub fn reloc_to_str(reloc: u8, cputype: mach::cputype::CpuType) -> &'static str
             }
         },
         CPU_TYPE_X86 => {
-            match reloc {
+            match r_type {
                 GENERIC_RELOC_VANILLA => "GENERIC_RELOC_VANILLA",
                 GENERIC_RELOC_PAIR => "GENERIC_RELOC_PAIR",
                 GENERIC_RELOC_SECTDIFF => "GENERIC_RELOC_SECTDIFF",
                 GENERIC_RELOC_LOCAL_SECTDIFF => "GENERIC_RELOC_LOCAL_SECTDIFF",
                 GENERIC_RELOC_PB_LA_P => "GENERIC_RELOC_PB_LA_P",
+                GENERIC_RELOC_TLV => "GENERIC_RELOC_TLV",
+                _ => "UNKNOWN",
+            }
+        },
+        CPU_TYPE_POWERPC | CPU_TYPE_POWERPC64 => {
+            match r_type {
+                PPC_RELOC_VANILLA => "PPC_RELOC_VANILLA",
+                PPC_RELOC_PAIR => "PPC_RELOC_PAIR",
+                PPC_RELOC_BR14 => "PPC_RELOC_BR14",
+                PPC_RELOC_BR24 => "PPC_RELOC_BR24",
+                PPC_RELOC_HI16 => "PPC_RELOC_HI16",
+                PPC_RELOC_LO16 => "PPC_RELOC_LO16",
+                PPC_RELOC_HA16 => "PPC_RELOC_HA16",
+                PPC_RELOC_LO14 => "PPC_RELOC_LO14",
+                PPC_RELOC_SECTDIFF => "PPC_RELOC_SECTDIFF",
+                PPC_RELOC_PB_LA_PTR => "PPC_RELOC_PB_LA_PTR",
+                PPC_RELOC_HI16_SECTDIFF => "PPC_RELOC_HI16_SECTDIFF",
+                PPC_RELOC_LO16_SECTDIFF => "PPC_RELOC_LO16_SECTDIFF",
+                PPC_RELOC_HA16_SECTDIFF => "PPC_RELOC_HA16_SECTDIFF",
+                PPC_RELOC_JBSR => "PPC_RELOC_JBSR",
+                PPC_RELOC_LO14_SECTDIFF => "PPC_RELOC_LO14_SECTDIFF",
+                PPC_RELOC_LOCAL_SECTDIFF => "PPC_RELOC_LOCAL_SECTDIFF",
                 _ => "UNKNOWN",
             }
         },
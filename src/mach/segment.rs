@@ -7,28 +7,70 @@ use std::ops::{Deref, DerefMut};
 use container;
 use error;
 
-use mach::relocation::RelocationInfo;
-use mach::load_command::{Section32, Section64, SegmentCommand32, SegmentCommand64, SIZEOF_SECTION_32, SIZEOF_SECTION_64, SIZEOF_SEGMENT_COMMAND_32, SIZEOF_SEGMENT_COMMAND_64, LC_SEGMENT, LC_SEGMENT_64};
+use mach::relocation::{Relocation, RelocationInfo, SIZEOF_RELOCATION_INFO};
+use mach::constants::{SEG_PAGEZERO, SG_PROTECTED_VERSION_1, SG_READ_ONLY, SECTION_TYPE, SECTION_ATTRIBUTES, S_MOD_INIT_FUNC_POINTERS, S_MOD_TERM_FUNC_POINTERS, S_ZEROFILL, S_GB_ZEROFILL, S_THREAD_LOCAL_ZEROFILL, S_CSTRING_LITERALS, VM_PROT_READ, VM_PROT_WRITE, VM_PROT_EXECUTE};
+use mach::load_command::{fixed_str, Section32, Section64, SegmentCommand32, SegmentCommand64, SIZEOF_SECTION_32, SIZEOF_SECTION_64, SIZEOF_SEGMENT_COMMAND_32, SIZEOF_SEGMENT_COMMAND_64, LC_SEGMENT, LC_SEGMENT_64};
 
+/// Iterator over a fixed-stride array of relocations, from either end
 pub struct RelocationIterator<'a> {
     data: &'a [u8],
     nrelocs: usize,
     offset: usize,
+    /// the index of the next relocation from the front
     count: usize,
+    /// one past the index of the next relocation from the back
+    end: usize,
     ctx: scroll::Endian,
 }
 
+impl<'a> RelocationIterator<'a> {
+    /// Iterate the `nrelocs` relocations at `offset` in `data`; `data` must be the original binary
+    pub fn new(data: &'a [u8], offset: usize, nrelocs: usize, ctx: container::Ctx) -> Self {
+        RelocationIterator {
+            offset: offset,
+            nrelocs: nrelocs,
+            count: 0,
+            end: nrelocs,
+            data: data,
+            ctx: ctx.le,
+        }
+    }
+    fn relocation(&self, index: usize) -> error::Result<Relocation> {
+        let offset = index.checked_mul(SIZEOF_RELOCATION_INFO).and_then(|delta| delta.checked_add(self.offset));
+        let offset = match offset {
+            Some(offset) => offset,
+            None => return Err(error::Error::Malformed(format!("relocation {} of {} at {:#x} overflows", index, self.nrelocs, self.offset))),
+        };
+        let reloc = self.data.pread_with::<RelocationInfo>(offset, self.ctx)?;
+        Ok(Relocation::new(reloc, self.ctx))
+    }
+}
+
 impl<'a> Iterator for RelocationIterator<'a> {
-    type Item = error::Result<RelocationInfo>;
+    type Item = error::Result<Relocation>;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.count >= self.nrelocs {
+        if self.count >= self.end {
             None
         } else {
             self.count += 1;
-            match self.data.gread_with(&mut self.offset, self.ctx) {
-                Ok(res) => Some(Ok(res)),
-                Err(e) => Some(Err(e.into()))
-            }
+            Some(self.relocation(self.count - 1))
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.count;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ::std::iter::ExactSizeIterator for RelocationIterator<'a> {}
+
+impl<'a> ::std::iter::DoubleEndedIterator for RelocationIterator<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.count >= self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(self.relocation(self.end))
         }
     }
 }
@@ -59,23 +101,77 @@ pub struct Section {
 impl Section {
     /// The name of this section
     pub fn name(&self) -> error::Result<&str> {
-        Ok(self.sectname.pread::<&str>(0)?)
+        fixed_str(&self.sectname)
     }
     /// The containing segment's name
     pub fn segname(&self) -> error::Result<&str> {
-        Ok(self.segname.pread::<&str>(0)?)
+        fixed_str(&self.segname)
+    }
+    /// The section type (`S_REGULAR`, `S_ZEROFILL`, `S_CSTRING_LITERALS`, etc.), i.e., the `SECTION_TYPE` bits of its flags
+    pub fn section_type(&self) -> u32 {
+        self.flags & SECTION_TYPE
+    }
+    /// Whether this is a zerofill section (`S_ZEROFILL`, `S_GB_ZEROFILL` or `S_THREAD_LOCAL_ZEROFILL`), which occupies no
+    /// space in the file regardless of its offset and size
+    pub fn is_zerofill(&self) -> bool {
+        let section_type = self.section_type();
+        section_type == S_ZEROFILL || section_type == S_GB_ZEROFILL || section_type == S_THREAD_LOCAL_ZEROFILL
+    }
+    /// The section attributes (`S_ATTR_PURE_INSTRUCTIONS`, etc.), i.e., the `SECTION_ATTRIBUTES` bits of its flags
+    pub fn attributes(&self) -> u32 {
+        self.flags & SECTION_ATTRIBUTES
+    }
+    /// The alignment this section requires, i.e. `1 << align`, as `align` is its log2; an `align` of 64 or more can't be
+    /// represented, and is 0
+    pub fn alignment(&self) -> u64 {
+        1u64.checked_shl(self.align).unwrap_or(0)
+    }
+    /// Whether the address of this section has the alignment it requires
+    pub fn is_addr_aligned(&self) -> bool {
+        self.addr.checked_rem(self.alignment()) == Some(0)
+    }
+    /// Reads the function pointers of this `S_MOD_INIT_FUNC_POINTERS` (C++ static initializers, `__mod_init_func`) or
+    /// `S_MOD_TERM_FUNC_POINTERS` (terminators, `__mod_term_func`) section from its `data`, at the pointer width of
+    /// `ctx`; erroring if it is any other type of section, or its size isn't a whole number of pointers.
+    ///
+    /// The pointers are as stored in the file, so are still to be rebased, or decoded, in a binary using chained fixups
+    pub fn init_pointers(&self, data: SectionData, ctx: container::Ctx) -> error::Result<Vec<u64>> {
+        let section_type = self.section_type();
+        if section_type != S_MOD_INIT_FUNC_POINTERS && section_type != S_MOD_TERM_FUNC_POINTERS {
+            return Err(error::Error::Malformed(format!("Section {:?} of type {:#x} is not a function pointer section", self.name().unwrap_or("BAD_SECTION_NAME"), section_type)));
+        }
+        let size = ctx.size();
+        if data.len().checked_rem(size) != Some(0) {
+            return Err(error::Error::Malformed(format!("Section {:?} of size {:#x} is not an array of {} byte pointers", self.name().unwrap_or("BAD_SECTION_NAME"), data.len(), size)));
+        }
+        let mut pointers = Vec::with_capacity(data.len() / size);
+        let mut offset = 0;
+        while offset < data.len() {
+            let pointer = if ctx.is_big() {
+                data.gread_with::<u64>(&mut offset, ctx.le)?
+            } else {
+                data.gread_with::<u32>(&mut offset, ctx.le)? as u64
+            };
+            pointers.push(pointer);
+        }
+        Ok(pointers)
+    }
+    /// Iterate the NUL-terminated strings of this `S_CSTRING_LITERALS` section (e.g. `__TEXT,__cstring`) in its `data`,
+    /// with the address of each; empty strings (e.g. alignment padding) and strings which aren't valid UTF-8 are skipped.
+    /// Any other type of section yields nothing, as its bytes needn't be strings at all
+    pub fn cstrings<'a>(&self, data: SectionData<'a>) -> CStringIterator<'a> {
+        let data = if self.section_type() == S_CSTRING_LITERALS { data } else { &[] };
+        CStringIterator {
+            data: data,
+            offset: 0,
+            addr: self.addr,
+        }
     }
     /// Iterate this sections relocations given `data`; `data` must be the original binary
     pub fn iter_relocations<'b>(&self, data: &'b [u8], ctx: container::Ctx) -> RelocationIterator<'b> {
         let offset = self.reloff as usize;
         debug!("Relocations for {} starting at offset: {:#x}", self.name().unwrap_or("BAD_SECTION_NAME"), offset);
-        RelocationIterator {
-            offset: offset,
-            nrelocs: self.nreloc as usize,
-            count: 0,
-            data: data,
-            ctx: ctx.le,
-        }
+        RelocationIterator::new(data, offset, self.nreloc as usize, ctx)
     }
 }
 
@@ -116,11 +212,26 @@ impl From<Section> for Section32 {
     }
 }
 
+/// Debug formats a fixed-size name, lossily when it is not valid UTF-8, so that printing a malformed binary can't panic
+struct DebugName<'a>(&'a [u8]);
+
+impl<'a> fmt::Debug for DebugName<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match fixed_str(self.0) {
+            Ok(name) => fmt::Debug::fmt(name, fmt),
+            Err(_) => {
+                let len = self.0.iter().position(|&b| b == 0).unwrap_or(self.0.len());
+                fmt::Debug::fmt(&String::from_utf8_lossy(&self.0[..len]), fmt)
+            },
+        }
+    }
+}
+
 impl fmt::Debug for Section {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("Section")
-            .field("sectname", &self.name().unwrap())
-            .field("segname",  &self.segname().unwrap())
+            .field("sectname", &DebugName(&self.sectname))
+            .field("segname",  &DebugName(&self.segname))
             .field("addr",     &self.addr)
             .field("size",     &self.size)
             .field("offset",   &self.offset)
@@ -210,6 +321,32 @@ impl ctx::IntoCtx<container::Ctx> for Section {
     }
 }
 
+/// Iterator over the `(address, string)`s of a C string literals section
+pub struct CStringIterator<'a> {
+    data: &'a [u8],
+    offset: usize,
+    addr: u64,
+}
+
+impl<'a> Iterator for CStringIterator<'a> {
+    type Item = (u64, &'a str);
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.offset < self.data.len() {
+            let start = self.offset;
+            let bytes = &self.data[start..];
+            let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+            self.offset += len + 1;
+            if len == 0 {
+                continue;
+            }
+            if let Ok(string) = ::core::str::from_utf8(&bytes[..len]) {
+                return Some((self.addr.wrapping_add(start as u64), string));
+            }
+        }
+        None
+    }
+}
+
 pub struct SectionIterator<'a> {
     data: &'a [u8],
     count: usize,
@@ -235,8 +372,17 @@ impl<'a> Iterator for SectionIterator<'a> {
             self.idx += 1;
             match self.data.gread_with::<Section>(&mut self.offset, self.ctx) {
                 Ok(section) => {
-                    let data = &self.data[section.offset as usize..][..section.size as usize];
-                    Some(Ok((section, data)))
+                    if section.is_zerofill() {
+                        return Some(Ok((section, &[])));
+                    }
+                    let end = (section.offset as u64).checked_add(section.size);
+                    match end {
+                        Some(end) if end <= self.data.len() as u64 => {
+                            let data = &self.data[section.offset as usize..end as usize];
+                            Some(Ok((section, data)))
+                        },
+                        _ => Some(Err(error::Error::Malformed(format!("Section {:?} data range {:#x} + {:#x} is outside of the binary of size {:#x}", section.name().unwrap_or("BAD_SECTION_NAME"), section.offset, section.size, self.data.len())))),
+                    }
                 },
                 Err(e) => Some(Err(e.into()))
             }
@@ -318,7 +464,7 @@ impl<'a> fmt::Debug for Segment<'a> {
         fmt.debug_struct("Segment")
             .field("cmd", &self.cmd)
             .field("cmdsize", &self.cmdsize)
-            .field("segname", &self.segname.pread::<&str>(0).unwrap())
+            .field("segname", &DebugName(&self.segname))
             .field("vmaddr",  &self.vmaddr)
             .field("vmsize",  &self.vmsize)
             .field("fileoff", &self.fileoff)
@@ -328,7 +474,8 @@ impl<'a> fmt::Debug for Segment<'a> {
             .field("nsects", &self.nsects)
             .field("flags", &self.flags)
             .field("data", &self.data.len())
-            .field("sections", &self.sections().unwrap())
+            // a bad section table is printed as the error, rather than panicking
+            .field("sections", &self.sections())
             .finish()
     }
 }
@@ -363,6 +510,41 @@ impl<'a> ctx::IntoCtx<container::Ctx> for Segment<'a> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// The decoded `VM_PROT_*` bits of a segment's protection
+pub struct Protection {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl Protection {
+    /// Whether this protection is both writable and executable, a W^X violation
+    pub fn is_writable_and_executable(&self) -> bool {
+        self.write && self.execute
+    }
+}
+
+impl From<u32> for Protection {
+    fn from(prot: u32) -> Self {
+        Protection {
+            read: prot & VM_PROT_READ != 0,
+            write: prot & VM_PROT_WRITE != 0,
+            execute: prot & VM_PROT_EXECUTE != 0,
+        }
+    }
+}
+
+impl fmt::Display for Protection {
+    /// Prints the protection as `vmmap` does, e.g., `r-x`
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}{}{}",
+               if self.read { "r" } else { "-" },
+               if self.write { "w" } else { "-" },
+               if self.execute { "x" } else { "-" })
+    }
+}
+
 impl<'a> Segment<'a> {
     /// Create a new, blank segment, with cmd either `LC_SEGMENT_64`, or `LC_SEGMENT`, depending on `ctx`. **NB** You are responsible for providing a correctly marshalled byte array as the sections. You should not use this for anything other than writing.
     pub fn new(ctx: container::Ctx, sections: &'a [u8]) -> Self {
@@ -386,7 +568,31 @@ impl<'a> Segment<'a> {
     }
     /// Get the name of this segment
     pub fn name(&self) -> error::Result<&str> {
-        Ok(self.segname.pread::<&str>(0)?)
+        fixed_str(&self.segname)
+    }
+    /// Whether this segment's address and file offset are both aligned to `page_size`, as they must be to be mapped
+    pub fn is_page_aligned(&self, page_size: u64) -> bool {
+        self.vmaddr.checked_rem(page_size) == Some(0) && self.fileoff.checked_rem(page_size) == Some(0)
+    }
+    /// Whether every bit of the `SG_*` `flag` is set
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags & flag == flag
+    }
+    /// Whether this segment is protected (encrypted), `SG_PROTECTED_VERSION_1`
+    pub fn is_protected(&self) -> bool {
+        self.has_flag(SG_PROTECTED_VERSION_1)
+    }
+    /// Whether this segment is made read-only after fixups, `SG_READ_ONLY`
+    pub fn is_read_only(&self) -> bool {
+        self.has_flag(SG_READ_ONLY)
+    }
+    /// The protection this segment is initially mapped with
+    pub fn initial_protection(&self) -> Protection {
+        Protection::from(self.initprot)
+    }
+    /// The maximum protection this segment may be given
+    pub fn max_protection(&self) -> Protection {
+        Protection::from(self.maxprot)
     }
     /// Get the sections from this segment, erroring if any section couldn't be retrieved
     pub fn sections(&self) -> error::Result<Vec<(Section, SectionData<'a>)>> {
@@ -396,10 +602,21 @@ impl<'a> Segment<'a> {
         }
         Ok(sections)
     }
-    /// Convert the raw C 32-bit segment command to a generalized version
-    pub fn from_32(bytes: &'a[u8], segment: &SegmentCommand32, offset: usize, ctx: container::Ctx) -> Self {
-        let data = &bytes[segment.fileoff as usize..(segment.fileoff + segment.filesize) as usize];
-        Segment {
+    /// Find the section of this segment named `name`, without reading the sections past it, erroring if a section
+    /// before it couldn't be retrieved
+    pub fn section_by_name(&self, name: &str) -> error::Result<Option<(Section, SectionData<'a>)>> {
+        for section in self.into_iter() {
+            let (section, data) = section?;
+            if section.name().ok() == Some(name) {
+                return Ok(Some((section, data)));
+            }
+        }
+        Ok(None)
+    }
+    /// Convert the raw C 32-bit segment command to a generalized version, erroring if its file range is not within `bytes`
+    pub fn from_32(bytes: &'a[u8], segment: &SegmentCommand32, offset: usize, ctx: container::Ctx) -> error::Result<Self> {
+        let data = segment_data(bytes, segment.fileoff as u64, segment.filesize as u64)?;
+        Ok(Segment {
             cmd:      segment.cmd,
             cmdsize:  segment.cmdsize,
             segname:  segment.segname,
@@ -415,12 +632,12 @@ impl<'a> Segment<'a> {
             offset:   offset,
             raw_data: bytes,
             ctx:      ctx,
-        }
+        })
     }
-    /// Convert the raw C 64-bit segment command to a generalized version
-    pub fn from_64(bytes: &'a [u8], segment: &SegmentCommand64, offset: usize, ctx: container::Ctx) -> Self {
-        let data = &bytes[segment.fileoff as usize..(segment.fileoff + segment.filesize) as usize];
-        Segment {
+    /// Convert the raw C 64-bit segment command to a generalized version, erroring if its file range is not within `bytes`
+    pub fn from_64(bytes: &'a [u8], segment: &SegmentCommand64, offset: usize, ctx: container::Ctx) -> error::Result<Self> {
+        let data = segment_data(bytes, segment.fileoff, segment.filesize)?;
+        Ok(Segment {
             cmd:      segment.cmd,
             cmdsize:  segment.cmdsize,
             segname:  segment.segname,
@@ -436,12 +653,24 @@ impl<'a> Segment<'a> {
             data:     data,
             raw_data: bytes,
             ctx:      ctx,
-        }
+        })
     }
 }
 
-#[derive(Debug, Default)]
-/// An opaque 32/64-bit container for Mach-o segments
+/// Get the `filesize` bytes at `fileoff` in `bytes`, erroring if they are out of bounds
+fn segment_data(bytes: &[u8], fileoff: u64, filesize: u64) -> error::Result<&[u8]> {
+    let end = fileoff.checked_add(filesize);
+    match end {
+        Some(end) if end <= bytes.len() as u64 => Ok(&bytes[fileoff as usize..end as usize]),
+        _ => Err(error::Error::Malformed(format!("Segment file range {:#x} + {:#x} is outside of the binary of size {:#x}", fileoff, filesize, bytes.len()))),
+    }
+}
+
+#[derive(Debug)]
+/// An opaque 32/64-bit container for Mach-o segments.
+///
+/// There is deliberately no `Default`: the container size and endianness of the segments must be those of the binary
+/// they are for, not the host's, so construct it with `Segments::with_ctx`
 pub struct Segments<'a> {
     segments: Vec<Segment<'a>>,
     ctx: container::Ctx,
@@ -469,16 +698,114 @@ impl<'a, 'b> IntoIterator for &'b Segments<'a> {
 }
 
 impl<'a> Segments<'a> {
-    /// Construct a new generalized segment container from this `ctx`
+    /// Construct a new generalized segment container from this `ctx`; the same as `Segments::with_ctx`
     pub fn new(ctx: container::Ctx) -> Self {
+        Self::with_ctx(ctx)
+    }
+    /// Construct an empty segment container for a binary with the container size and endianness of `ctx`
+    pub fn with_ctx(ctx: container::Ctx) -> Self {
         Segments {
             segments: Vec::new(),
             ctx: ctx,
         }
     }
+    /// The container size and endianness of these segments
+    pub fn ctx(&self) -> container::Ctx {
+        self.ctx
+    }
+    /// Find the segment named `name`, if there is one
+    pub fn find(&self, name: &str) -> Option<&Segment<'a>> {
+        self.segments.iter().find(|segment| segment.name().ok() == Some(name))
+    }
+    /// The `__PAGEZERO` segment, which maps the unreadable page(s) at address zero of an executable, if there is one
+    pub fn page_zero(&self) -> Option<&Segment<'a>> {
+        self.find(SEG_PAGEZERO)
+    }
+    /// Find the section named `sect` in the segment named `seg`, erroring if a section of that segment couldn't be retrieved
+    pub fn find_section(&self, seg: &str, sect: &str) -> error::Result<Option<(Section, SectionData<'a>)>> {
+        match self.find(seg) {
+            Some(segment) => segment.section_by_name(sect),
+            None => Ok(None),
+        }
+    }
+    /// Translate the virtual address `vaddr` to the file offset it is loaded from, if it is within the file-backed
+    /// part of a segment; addresses in the zero-filled tail of a segment (or in a segment without any file contents,
+    /// like `__PAGEZERO`) have no file offset
+    pub fn vaddr_to_offset(&self, vaddr: u64) -> Option<u64> {
+        self.file_backed_segment(vaddr).map(|(segment, delta)| segment.fileoff + delta)
+    }
+    /// Translate the file offset `off` to the virtual address it is loaded at, if it is within a segment's file range
+    pub fn offset_to_vaddr(&self, off: u64) -> Option<u64> {
+        self.segments.iter().filter(|segment| segment.filesize > 0).filter_map(|segment| {
+            match off.checked_sub(segment.fileoff) {
+                Some(delta) if delta < segment.filesize && delta < segment.vmsize => segment.vmaddr.checked_add(delta),
+                _ => None,
+            }
+        }).next()
+    }
+    /// Find the section whose file range contains the file offset `off`, and the segment it belongs to; zerofill sections
+    /// have no file contents, so never contain an offset, and sections that fail to parse are skipped
+    pub fn section_at_offset(&self, off: u64) -> Option<(&Segment<'a>, Section)> {
+        self.flat_sections().filter_map(|section| section.ok()).filter(|(_, section, _)| !section.is_zerofill()).filter_map(|(segment, section, _)| {
+            match off.checked_sub(section.offset as u64) {
+                Some(delta) if delta < section.size => Some((segment, section)),
+                _ => None,
+            }
+        }).next()
+    }
+    /// Get the `len` bytes of the file loaded at the virtual address `vaddr`, if they are all within the file-backed
+    /// range of a single segment
+    pub fn data_at_vaddr(&self, vaddr: u64, len: usize) -> Option<&'a [u8]> {
+        let (segment, delta) = self.file_backed_segment(vaddr)?;
+        let start = delta as usize;
+        let end = start.checked_add(len)?;
+        if end as u64 > segment.vmsize {
+            return None;
+        }
+        segment.data.get(start..end)
+    }
+    /// Find the segment whose file-backed range contains the virtual address `vaddr`, and the offset of `vaddr` in it
+    fn file_backed_segment(&self, vaddr: u64) -> Option<(&Segment<'a>, u64)> {
+        self.segments.iter().filter_map(|segment| {
+            match vaddr.checked_sub(segment.vmaddr) {
+                Some(delta) if delta < segment.vmsize && delta < segment.filesize => Some((segment, delta)),
+                _ => None,
+            }
+        }).next()
+    }
     /// Get every section from every segment
     // thanks to SpaceManic for figuring out the 'b lifetimes here :)
     pub fn sections<'b>(&'b self) -> Box<Iterator<Item=SectionIterator<'a>> + 'b> {
         Box::new(self.segments.iter().map(|segment| segment.into_iter()))
     }
+    /// Get every section of every segment, in order, alongside the segment it belongs to
+    pub fn flat_sections<'b>(&'b self) -> FlatSectionIterator<'a, 'b> {
+        FlatSectionIterator {
+            segments: self.segments.iter(),
+            current: None,
+        }
+    }
+}
+
+/// Iterator over every section of every segment of a `Segments`, with the segment each section belongs to
+pub struct FlatSectionIterator<'a: 'b, 'b> {
+    segments: ::std::slice::Iter<'b, Segment<'a>>,
+    current: Option<(&'b Segment<'a>, SectionIterator<'a>)>,
+}
+
+impl<'a, 'b> Iterator for FlatSectionIterator<'a, 'b> {
+    type Item = error::Result<(&'b Segment<'a>, Section, SectionData<'a>)>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((segment, ref mut sections)) = self.current {
+                if let Some(section) = sections.next() {
+                    return Some(section.map(|(section, data)| (segment, section, data)));
+                }
+            }
+            match self.segments.next() {
+                Some(segment) => self.current = Some((segment, segment.into_iter())),
+                None => return None,
+            }
+        }
+    }
 }
@@ -0,0 +1,214 @@
+//! Symbolic debugging ("stab") entries of the symbol table, as described in `<mach-o/stab.h>`
+//!
+//! A symbol is a stab if any of the `N_STAB` bits of its `n_type` are set, in which case the whole `n_type` is one of
+//! the values below, and its other fields are interpreted as documented for each value (`name,,n_sect,n_desc,n_value`).
+//! For example, each object file the link editor consumed is described by an `N_OSO` entry, preceded by the `N_SO`
+//! entries naming its source directory and file.
+
+
+/// global symbol: name,,NO_SECT,type,0
+pub const N_GSYM: u8 = 0x20;
+/// procedure name (f77 kludge): name,,NO_SECT,0,0
+pub const N_FNAME: u8 = 0x22;
+/// procedure: name,,n_sect,linenumber,address
+pub const N_FUN: u8 = 0x24;
+/// static symbol: name,,n_sect,type,address
+pub const N_STSYM: u8 = 0x26;
+/// .lcomm symbol: name,,n_sect,type,address
+pub const N_LCSYM: u8 = 0x28;
+/// begin nsect sym: 0,,n_sect,0,address
+pub const N_BNSYM: u8 = 0x2e;
+/// global pascal symbol: name,,NO_SECT,subtype,line
+pub const N_PC: u8 = 0x30;
+/// AST file path: name,,NO_SECT,0,0
+pub const N_AST: u8 = 0x32;
+/// emitted with gcc2_compiled and in gcc source
+pub const N_OPT: u8 = 0x3c;
+/// register sym: name,,NO_SECT,type,register
+pub const N_RSYM: u8 = 0x40;
+/// src line: 0,,n_sect,linenumber,address
+pub const N_SLINE: u8 = 0x44;
+/// end nsect sym: 0,,n_sect,0,address
+pub const N_ENSYM: u8 = 0x4e;
+/// structure elt: name,,NO_SECT,type,struct_offset
+pub const N_SSYM: u8 = 0x60;
+/// source file name: name,,n_sect,0,address
+pub const N_SO: u8 = 0x64;
+/// object file name: name,,0,0,st_mtime
+pub const N_OSO: u8 = 0x66;
+/// local sym: name,,NO_SECT,type,offset
+pub const N_LSYM: u8 = 0x80;
+/// include file beginning: name,,NO_SECT,0,sum
+pub const N_BINCL: u8 = 0x82;
+/// #included file name: name,,n_sect,0,address
+pub const N_SOL: u8 = 0x84;
+/// compiler parameters: name,,NO_SECT,0,0
+pub const N_PARAMS: u8 = 0x86;
+/// compiler version: name,,NO_SECT,0,0
+pub const N_VERSION: u8 = 0x88;
+/// compiler -O level: name,,NO_SECT,0,0
+pub const N_OLEVEL: u8 = 0x8a;
+/// parameter: name,,NO_SECT,type,offset
+pub const N_PSYM: u8 = 0xa0;
+/// include file end: name,,NO_SECT,0,0
+pub const N_EINCL: u8 = 0xa2;
+/// alternate entry: name,,n_sect,linenumber,address
+pub const N_ENTRY: u8 = 0xa4;
+/// left bracket: 0,,NO_SECT,nesting level,address
+pub const N_LBRAC: u8 = 0xc0;
+/// deleted include file: name,,NO_SECT,0,sum
+pub const N_EXCL: u8 = 0xc2;
+/// right bracket: 0,,NO_SECT,nesting level,address
+pub const N_RBRAC: u8 = 0xe0;
+/// begin common: name,,NO_SECT,0,0
+pub const N_BCOMM: u8 = 0xe2;
+/// end common: name,,n_sect,0,0
+pub const N_ECOMM: u8 = 0xe4;
+/// end common (local name): 0,,n_sect,0,address
+pub const N_ECOML: u8 = 0xe8;
+/// second stab entry with length information
+pub const N_LENG: u8 = 0xfe;
+
+/// The kind of a stab, decoded from its `n_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// `N_GSYM`
+    Gsym,
+    /// `N_FNAME`
+    Fname,
+    /// `N_FUN`
+    Fun,
+    /// `N_STSYM`
+    Stsym,
+    /// `N_LCSYM`
+    Lcsym,
+    /// `N_BNSYM`
+    Bnsym,
+    /// `N_PC`
+    Pc,
+    /// `N_AST`
+    Ast,
+    /// `N_OPT`
+    Opt,
+    /// `N_RSYM`
+    Rsym,
+    /// `N_SLINE`
+    Sline,
+    /// `N_ENSYM`
+    Ensym,
+    /// `N_SSYM`
+    Ssym,
+    /// `N_SO`
+    So,
+    /// `N_OSO`
+    Oso,
+    /// `N_LSYM`
+    Lsym,
+    /// `N_BINCL`
+    Bincl,
+    /// `N_SOL`
+    Sol,
+    /// `N_PARAMS`
+    Params,
+    /// `N_VERSION`
+    Version,
+    /// `N_OLEVEL`
+    Olevel,
+    /// `N_PSYM`
+    Psym,
+    /// `N_EINCL`
+    Eincl,
+    /// `N_ENTRY`
+    Entry,
+    /// `N_LBRAC`
+    Lbrac,
+    /// `N_EXCL`
+    Excl,
+    /// `N_RBRAC`
+    Rbrac,
+    /// `N_BCOMM`
+    Bcomm,
+    /// `N_ECOMM`
+    Ecomm,
+    /// `N_ECOML`
+    Ecoml,
+    /// `N_LENG`
+    Leng,
+    Unknown(u8),
+}
+
+impl From<u8> for Kind {
+    fn from(n_type: u8) -> Self {
+        match n_type {
+            N_GSYM => Kind::Gsym,
+            N_FNAME => Kind::Fname,
+            N_FUN => Kind::Fun,
+            N_STSYM => Kind::Stsym,
+            N_LCSYM => Kind::Lcsym,
+            N_BNSYM => Kind::Bnsym,
+            N_PC => Kind::Pc,
+            N_AST => Kind::Ast,
+            N_OPT => Kind::Opt,
+            N_RSYM => Kind::Rsym,
+            N_SLINE => Kind::Sline,
+            N_ENSYM => Kind::Ensym,
+            N_SSYM => Kind::Ssym,
+            N_SO => Kind::So,
+            N_OSO => Kind::Oso,
+            N_LSYM => Kind::Lsym,
+            N_BINCL => Kind::Bincl,
+            N_SOL => Kind::Sol,
+            N_PARAMS => Kind::Params,
+            N_VERSION => Kind::Version,
+            N_OLEVEL => Kind::Olevel,
+            N_PSYM => Kind::Psym,
+            N_EINCL => Kind::Eincl,
+            N_ENTRY => Kind::Entry,
+            N_LBRAC => Kind::Lbrac,
+            N_EXCL => Kind::Excl,
+            N_RBRAC => Kind::Rbrac,
+            N_BCOMM => Kind::Bcomm,
+            N_ECOMM => Kind::Ecomm,
+            N_ECOML => Kind::Ecoml,
+            N_LENG => Kind::Leng,
+            _ => Kind::Unknown(n_type),
+        }
+    }
+}
+
+pub fn stab_to_str(n_type: u8) -> &'static str {
+    match n_type {
+        N_GSYM => "N_GSYM",
+        N_FNAME => "N_FNAME",
+        N_FUN => "N_FUN",
+        N_STSYM => "N_STSYM",
+        N_LCSYM => "N_LCSYM",
+        N_BNSYM => "N_BNSYM",
+        N_PC => "N_PC",
+        N_AST => "N_AST",
+        N_OPT => "N_OPT",
+        N_RSYM => "N_RSYM",
+        N_SLINE => "N_SLINE",
+        N_ENSYM => "N_ENSYM",
+        N_SSYM => "N_SSYM",
+        N_SO => "N_SO",
+        N_OSO => "N_OSO",
+        N_LSYM => "N_LSYM",
+        N_BINCL => "N_BINCL",
+        N_SOL => "N_SOL",
+        N_PARAMS => "N_PARAMS",
+        N_VERSION => "N_VERSION",
+        N_OLEVEL => "N_OLEVEL",
+        N_PSYM => "N_PSYM",
+        N_EINCL => "N_EINCL",
+        N_ENTRY => "N_ENTRY",
+        N_LBRAC => "N_LBRAC",
+        N_EXCL => "N_EXCL",
+        N_RBRAC => "N_RBRAC",
+        N_BCOMM => "N_BCOMM",
+        N_ECOMM => "N_ECOMM",
+        N_ECOML => "N_ECOML",
+        N_LENG => "N_LENG",
+        _ => "UNKNOWN_STAB",
+    }
+}
@@ -2,11 +2,13 @@
 //!
 //! Symbols are essentially a type, offset, and the symbol name
 
-use scroll::{ctx, Pread};
+use scroll::{ctx, Pread, Pwrite};
 use scroll::ctx::SizeWith;
+use std::collections::HashMap;
 use error;
 use container::{self, Container};
 use mach::load_command;
+use mach::stab;
 use core::fmt::{self, Debug};
 
 // If the type is N_SECT then the n_sect field contains an ordinal of the
@@ -35,6 +37,62 @@ pub const N_PBUD: u8 = 0xc;
 /// indirect
 pub const N_INDR: u8 = 0xa;
 
+// The n_type field really contains four fields:
+//     unsigned char N_STAB:3,
+//                   N_PEXT:1,
+//                   N_TYPE:3,
+//                   N_EXT:1;
+/// if any of these bits set, a symbolic debugging entry
+pub const N_STAB: u8 = 0xe0;
+/// private external symbol bit
+pub const N_PEXT: u8 = 0x10;
+/// mask for the type bits
+pub const N_TYPE: u8 = 0x0e;
+/// external symbol bit, set for external symbols
+pub const N_EXT:  u8 = 0x01;
+
+// For two-level namespace images, the high 8 bits of the n_desc field of an undefined symbol (or a prebound
+// undefined symbol) contain the ordinal of the library the symbol is expected to be found in
+/// the symbol is defined in this image
+pub const SELF_LIBRARY_ORDINAL: u16 = 0x0;
+/// the largest ordinal of a library in the `LC_LOAD_DYLIB` (etc.) commands
+pub const MAX_LIBRARY_ORDINAL: u16 = 0xfd;
+/// the symbol is looked up in every image, as in a flat namespace
+pub const DYNAMIC_LOOKUP_ORDINAL: u16 = 0xfe;
+/// the symbol is defined in the main executable
+pub const EXECUTABLE_ORDINAL: u16 = 0xff;
+
+// The low 3 bits of the n_desc field of an undefined symbol (in a binary that isn't an MH_OBJECT) say how it is
+// referenced
+/// mask for the reference type bits of `n_desc`
+pub const REFERENCE_TYPE: u16 = 0x7;
+pub const REFERENCE_FLAG_UNDEFINED_NON_LAZY: u16 = 0;
+pub const REFERENCE_FLAG_UNDEFINED_LAZY: u16 = 1;
+pub const REFERENCE_FLAG_DEFINED: u16 = 2;
+pub const REFERENCE_FLAG_PRIVATE_DEFINED: u16 = 3;
+pub const REFERENCE_FLAG_PRIVATE_UNDEFINED_NON_LAZY: u16 = 4;
+pub const REFERENCE_FLAG_PRIVATE_UNDEFINED_LAZY: u16 = 5;
+/// the symbol must not be stripped, as it is looked up dynamically, e.g. with `NSLookupSymbolInImage`
+pub const REFERENCED_DYNAMICALLY: u16 = 0x10;
+/// the defined symbol must not be dead stripped, in an MH_OBJECT
+pub const N_NO_DEAD_STRIP: u16 = 0x20;
+/// the symbol was discarded by the link editor, in a fully linked image
+pub const N_DESC_DISCARDED: u16 = 0x20;
+/// the undefined symbol is a weak reference: it is allowed to be missing, and is then 0
+pub const N_WEAK_REF: u16 = 0x40;
+/// the defined symbol is a weak definition, which is overridden by any non-weak definition of the same name
+pub const N_WEAK_DEF: u16 = 0x80;
+/// the undefined symbol references a weak definition (the same bit as `N_WEAK_DEF`)
+pub const N_REF_TO_WEAK: u16 = 0x80;
+/// the defined symbol is a Thumb function, so its address must have the low bit set when it is called
+pub const N_ARM_THUMB_DEF: u16 = 0x8;
+/// the defined symbol is a resolver function, which returns the address of the actual definition
+pub const N_SYMBOL_RESOLVER: u16 = 0x100;
+/// the defined symbol is an alternate entry into the function before it, and must stay with it
+pub const N_ALT_ENTRY: u16 = 0x200;
+/// the defined symbol is a cold function, used rarely
+pub const N_COLD_FUNC: u16 = 0x400;
+
 pub const NLIST_TYPE_MASK: u8 = 0xe;
 pub const NLIST_TYPE_GLOBAL: u8 = 0x1;
 pub const NLIST_TYPE_LOCAL: u8 = 0x0;
@@ -50,6 +108,42 @@ pub fn n_type_to_str(n_type: u8) -> &'static str {
     }
 }
 
+/// The decoded `N_TYPE` of a symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolType {
+    /// `N_UNDF`
+    Undefined,
+    /// `N_ABS`
+    Absolute,
+    /// `N_SECT`, defined in the section `n_sect`
+    Section,
+    /// `N_PBUD`
+    PreboundUndefined,
+    /// `N_INDR`
+    Indirect,
+    /// A symbolic debugging entry, whose `n_type` is the `N_STAB` value rather than a type
+    Stab(u8),
+    /// An unknown type
+    Unknown(u8),
+}
+
+impl SymbolType {
+    /// Decodes the raw `n_type` of a symbol
+    pub fn from_n_type(n_type: u8) -> Self {
+        if n_type & N_STAB != 0 {
+            return SymbolType::Stab(n_type);
+        }
+        match n_type & N_TYPE {
+            N_UNDF => SymbolType::Undefined,
+            N_ABS => SymbolType::Absolute,
+            N_SECT => SymbolType::Section,
+            N_PBUD => SymbolType::PreboundUndefined,
+            N_INDR => SymbolType::Indirect,
+            typ => SymbolType::Unknown(typ),
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Pread, Pwrite, SizeWith)]
 pub struct Nlist32 {
@@ -135,6 +229,60 @@ impl Nlist {
     pub fn is_global(&self) -> bool {
         self.n_type & !NLIST_TYPE_MASK == NLIST_TYPE_GLOBAL
     }
+    /// Decodes the type of this symbol
+    pub fn type_(&self) -> SymbolType {
+        SymbolType::from_n_type(self.n_type)
+    }
+    /// Whether this is a symbolic debugging entry, i.e., any of the `N_STAB` bits are set
+    pub fn is_stab(&self) -> bool {
+        self.n_type & N_STAB != 0
+    }
+    /// The kind of this symbolic debugging entry, if it is one
+    pub fn stab(&self) -> Option<stab::Kind> {
+        if self.is_stab() {
+            Some(stab::Kind::from(self.n_type))
+        } else {
+            None
+        }
+    }
+    /// Whether this symbol is external, i.e., its `N_EXT` bit is set
+    pub fn is_external(&self) -> bool {
+        self.n_type & N_EXT != 0
+    }
+    /// Whether this symbol is a private external, i.e., its `N_PEXT` bit is set
+    pub fn is_private_external(&self) -> bool {
+        self.n_type & N_PEXT != 0
+    }
+    /// The 1-based ordinal of the section this symbol is defined in, if it is `N_SECT`
+    pub fn section_index(&self) -> Option<usize> {
+        if self.type_() == SymbolType::Section && self.n_sect != NO_SECT as usize {
+            Some(self.n_sect)
+        } else {
+            None
+        }
+    }
+    /// How this undefined symbol is referenced, i.e. the `REFERENCE_TYPE` bits of `n_desc`, e.g.
+    /// `REFERENCE_FLAG_UNDEFINED_LAZY`
+    pub fn reference_type(&self) -> u16 {
+        self.n_desc & REFERENCE_TYPE
+    }
+    /// Whether this undefined symbol is a weak reference (`N_WEAK_REF`), which may be missing at runtime
+    pub fn is_weak_ref(&self) -> bool {
+        self.n_desc & N_WEAK_REF != 0
+    }
+    /// Whether this defined symbol is a weak definition (`N_WEAK_DEF`); for an undefined symbol the same bit means it
+    /// references a weak definition (`N_REF_TO_WEAK`) instead
+    pub fn is_weak_def(&self) -> bool {
+        !self.is_undefined() && self.n_desc & N_WEAK_DEF != 0
+    }
+    /// Whether this defined symbol is a Thumb function (`N_ARM_THUMB_DEF`), which is called at its address plus one
+    pub fn is_thumb(&self) -> bool {
+        !self.is_undefined() && self.n_desc & N_ARM_THUMB_DEF != 0
+    }
+    /// The library ordinal of this (undefined) symbol, i.e., `GET_LIBRARY_ORDINAL(n_desc)`
+    pub fn library_ordinal(&self) -> u16 {
+        (self.n_desc >> 8) & 0xff
+    }
     /// Whether this symbol is undefined or not
     pub fn is_undefined(&self) -> bool {
         self.n_sect == 0 && self.n_type & NLIST_TYPE_MASK == 0
@@ -156,6 +304,30 @@ impl ctx::SizeWith<container::Ctx> for Nlist {
     }
 }
 
+impl From<Nlist> for Nlist32 {
+    fn from(nlist: Nlist) -> Self {
+        Nlist32 {
+            n_strx: nlist.n_strx as u32,
+            n_type: nlist.n_type,
+            n_sect: nlist.n_sect as u8,
+            n_desc: nlist.n_desc,
+            n_value: nlist.n_value as u32,
+        }
+    }
+}
+
+impl From<Nlist> for Nlist64 {
+    fn from(nlist: Nlist) -> Self {
+        Nlist64 {
+            n_strx: nlist.n_strx as u32,
+            n_type: nlist.n_type,
+            n_sect: nlist.n_sect as u8,
+            n_desc: nlist.n_desc,
+            n_value: nlist.n_value,
+        }
+    }
+}
+
 impl From<Nlist32> for Nlist {
     fn from(nlist: Nlist32) -> Self {
         Nlist {
@@ -196,6 +368,95 @@ impl<'a> ctx::TryFromCtx<'a, container::Ctx> for Nlist {
     }
 }
 
+/// The symbol table a `SymtabBuilder` built: the `LC_SYMTAB` command for it, and its nlists and string table
+#[derive(Debug)]
+pub struct BuiltSymtab {
+    /// The `LC_SYMTAB` command, with the `symoff`, `nsyms`, `stroff` and `strsize` of `data`
+    pub command: load_command::SymtabCommand,
+    /// The packed nlist array followed by the string table, to be written at `command.symoff`
+    pub data: Vec<u8>,
+}
+
+/// Builds an nlist symbol table and its string table, for writing into a binary.
+///
+/// The symbols are written in the order they are added; `LC_DYSYMTAB` expects the local, then the external defined,
+/// then the undefined symbols, each group contiguous. Names are deduplicated in the string table, which starts with an
+/// empty name (a NUL byte) at index 0, and the `n_strx` of the symbols are set to match
+#[derive(Debug, Default)]
+pub struct SymtabBuilder {
+    symbols: Vec<(String, Nlist)>,
+}
+
+impl SymtabBuilder {
+    pub fn new() -> Self {
+        SymtabBuilder::default()
+    }
+    /// Add the symbol `name` described by `nlist`, whose `n_strx` is ignored
+    pub fn add(&mut self, name: &str, nlist: Nlist) -> &mut Self {
+        self.symbols.push((name.to_string(), nlist));
+        self
+    }
+    /// The number of symbols added
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+    /// Whether no symbols have been added
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+    /// Build the symbol table for a binary with the container size and endianness of `ctx`, to be written at the file
+    /// offset `symoff`, with its string table right after the nlists, padded to the pointer size
+    pub fn build(&self, symoff: usize, ctx: container::Ctx) -> error::Result<BuiltSymtab> {
+        let mut strtab = vec![0u8];
+        let mut indices = HashMap::new();
+        let mut nlists = Vec::with_capacity(self.symbols.len());
+        for (name, nlist) in &self.symbols {
+            let n_strx = if name.is_empty() {
+                0
+            } else {
+                *indices.entry(name.as_str()).or_insert_with(|| {
+                    let n_strx = strtab.len();
+                    strtab.extend_from_slice(name.as_bytes());
+                    strtab.push(0);
+                    n_strx
+                })
+            };
+            nlists.push(Nlist { n_strx: n_strx, ..nlist.clone() });
+        }
+        let pointer_size = if ctx.is_big() { 8 } else { 4 };
+        while strtab.len() % pointer_size != 0 {
+            strtab.push(0);
+        }
+        let size = Nlist::size_with(&ctx);
+        let stroff = symoff + nlists.len() * size;
+        if stroff as u64 + strtab.len() as u64 > u32::MAX as u64 {
+            return Err(error::Error::Malformed(format!("symbol table at {:#x} with {} symbols and a string table of {:#x} bytes doesn't fit in 32-bit offsets", symoff, nlists.len(), strtab.len())));
+        }
+        let mut data = vec![0u8; stroff - symoff + strtab.len()];
+        for (i, (nlist, (name, _))) in nlists.into_iter().zip(self.symbols.iter()).enumerate() {
+            if nlist.n_sect > MAX_SECT as usize {
+                return Err(error::Error::Malformed(format!("symbol {} ({}) has section ordinal {}, which is more than {}", i, name, nlist.n_sect, MAX_SECT)));
+            }
+            if ctx.is_big() {
+                data.pwrite_with(Nlist64::from(nlist), i * size, ctx.le)?;
+            } else if nlist.n_value > u32::MAX as u64 {
+                return Err(error::Error::Malformed(format!("symbol {} ({}) has value {:#x}, which doesn't fit in a 32-bit nlist", i, name, nlist.n_value)));
+            } else {
+                data.pwrite_with(Nlist32::from(nlist), i * size, ctx.le)?;
+            }
+        }
+        data[stroff - symoff..].copy_from_slice(&strtab);
+        let command = load_command::SymtabCommand {
+            symoff: symoff as u32,
+            nsyms: self.symbols.len() as u32,
+            stroff: stroff as u32,
+            strsize: strtab.len() as u32,
+            ..load_command::SymtabCommand::new()
+        };
+        Ok(BuiltSymtab { command: command, data: data })
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SymbolsCtx {
     pub nsyms: usize,
@@ -215,11 +476,29 @@ impl<'a, T: ?Sized> ctx::TryFromCtx<'a, SymbolsCtx, T> for Symbols<'a> where T:
             start: 0,
             nsyms: nsyms,
             strtab: strtab,
+            strsize: data.len().saturating_sub(strtab),
             ctx: ctx,
         }, data.len()))
     }
 }
 
+/// Looks up the name at `n_strx` in the string table of size `strsize` at `strtab` in `data`; an `n_strx` of 0 is the
+/// empty name
+fn symbol_name(data: &[u8], strtab: usize, strsize: usize, n_strx: usize) -> error::Result<&str> {
+    if n_strx == 0 {
+        return Ok("");
+    }
+    if n_strx >= strsize {
+        return Err(error::Error::Malformed(format!("Symbol name offset {:#x} is outside of the string table of size {:#x}", n_strx, strsize)));
+    }
+    let end = strtab.saturating_add(strsize).min(data.len());
+    match data.get(strtab..end) {
+        Some(strings) => Ok(strings.pread(n_strx)?),
+        None => Err(error::Error::Malformed(format!("String table at {:#x} is outside of the binary of size {:#x}", strtab, data.len()))),
+    }
+}
+
+/// Iterator over the symbols of a `Symbols`, with each name looked up in the string table as it is reached
 #[derive(Default)]
 pub struct SymbolIterator<'a> {
     data: &'a [u8],
@@ -228,6 +507,7 @@ pub struct SymbolIterator<'a> {
     count: usize,
     ctx: container::Ctx,
     strtab: usize,
+    strsize: usize,
 }
 
 impl<'a> Iterator for SymbolIterator<'a> {
@@ -239,11 +519,11 @@ impl<'a> Iterator for SymbolIterator<'a> {
             self.count += 1;
             match self.data.gread_with::<Nlist>(&mut self.offset, self.ctx) {
                 Ok(symbol) => {
-                    match self.data.pread(self.strtab + symbol.n_strx) {
+                    match symbol_name(self.data, self.strtab, self.strsize, symbol.n_strx) {
                         Ok(name) => {
                             Some(Ok((name, symbol)))
                         },
-                        Err(e) => return Some(Err(e.into()))
+                        Err(e) => return Some(Err(e))
                     }
                 },
                 Err(e) => return Some(Err(e.into()))
@@ -259,6 +539,7 @@ pub struct Symbols<'a> {
     nsyms: usize,
     // TODO: we can use an actual strtab here and tie it to symbols lifetime
     strtab: usize,
+    strsize: usize,
     ctx: container::Ctx,
 }
 
@@ -281,15 +562,20 @@ impl<'a> Symbols<'a> {
             start: start,
             nsyms: nsyms,
             strtab: strtab,
+            strsize: bytes.len().saturating_sub(strtab),
             ctx: container::Ctx::default(),
         })
     }
     pub fn parse(bytes: &'a [u8], symtab: &load_command::SymtabCommand, ctx: container::Ctx) -> error::Result<Symbols<'a>> {
         // we need to normalize the strtab offset before we receive the truncated bytes in pread_with
         let strtab = symtab.stroff - symtab.symoff;
-        Ok(bytes.pread_with(symtab.symoff as usize, SymbolsCtx { nsyms: symtab.nsyms as usize, strtab: strtab as usize, ctx: ctx })?)
+        let mut symbols: Symbols = bytes.pread_with(symtab.symoff as usize, SymbolsCtx { nsyms: symtab.nsyms as usize, strtab: strtab as usize, ctx: ctx })?;
+        symbols.strsize = symbols.strsize.min(symtab.strsize as usize);
+        Ok(symbols)
     }
 
+    /// Iterate the symbols with their names, erroring for a symbol whose `n_strx` is outside of the string table; an
+    /// `n_strx` of 0 is the empty name
     pub fn iter(&self) -> SymbolIterator<'a> {
         SymbolIterator {
             offset: self.start as usize,
@@ -298,13 +584,14 @@ impl<'a> Symbols<'a> {
             data: self.data,
             ctx: self.ctx,
             strtab: self.strtab,
+            strsize: self.strsize,
         }
     }
 
     /// Parses a single Nlist symbol from the binary, with its accompanying name
     pub fn get(&self, index: usize) -> ::error::Result<(&'a str, Nlist)> {
         let sym: Nlist = self.data.pread_with(self.start + (index * Nlist::size_with(&self.ctx)), self.ctx)?;
-        let name = self.data.pread(self.strtab + sym.n_strx)?;
+        let name = symbol_name(self.data, self.strtab, self.strsize, sym.n_strx)?;
         Ok((name, sym))
     }
 }
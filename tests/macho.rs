@@ -26,6 +26,60 @@ fn parse_fat_header() {
 
 const DEADBEEF_MACH_64: [u8; 8496] = [0xCF,0xFA,0xED,0xFE,0x7,0x0,0x0,0x1,0x3,0x0,0x0,0x80,0x2,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x10,0x5,0x0,0x0,0x85,0x0,0x20,0x0,0x0,0x0,0x0,0x0,0x19,0x0,0x0,0x0,0x48,0x0,0x0,0x0,0x5F,0x5F,0x50,0x41,0x47,0x45,0x5A,0x45,0x52,0x4F,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x19,0x0,0x0,0x0,0x28,0x2,0x0,0x0,0x5F,0x5F,0x54,0x45,0x58,0x54,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x0,0x0,0x0,0x7,0x0,0x0,0x0,0x5,0x0,0x0,0x0,0x6,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x74,0x65,0x78,0x74,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x54,0x45,0x58,0x54,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x40,0xF,0x0,0x0,0x1,0x0,0x0,0x0,0x34,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x40,0xF,0x0,0x0,0x4,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x4,0x0,0x80,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x73,0x74,0x75,0x62,0x73,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x54,0x45,0x58,0x54,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x74,0xF,0x0,0x0,0x1,0x0,0x0,0x0,0x6,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x74,0xF,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x8,0x4,0x0,0x80,0x0,0x0,0x0,0x0,0x6,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x73,0x74,0x75,0x62,0x5F,0x68,0x65,0x6C,0x70,0x65,0x72,0x0,0x0,0x0,0x5F,0x5F,0x54,0x45,0x58,0x54,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x7C,0xF,0x0,0x0,0x1,0x0,0x0,0x0,0x1A,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x7C,0xF,0x0,0x0,0x2,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x4,0x0,0x80,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x63,0x73,0x74,0x72,0x69,0x6E,0x67,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x54,0x45,0x58,0x54,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x96,0xF,0x0,0x0,0x1,0x0,0x0,0x0,0x6,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x96,0xF,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x75,0x6E,0x77,0x69,0x6E,0x64,0x5F,0x69,0x6E,0x66,0x6F,0x0,0x0,0x0,0x5F,0x5F,0x54,0x45,0x58,0x54,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x9C,0xF,0x0,0x0,0x1,0x0,0x0,0x0,0x48,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x9C,0xF,0x0,0x0,0x2,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x65,0x68,0x5F,0x66,0x72,0x61,0x6D,0x65,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x54,0x45,0x58,0x54,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0xE8,0xF,0x0,0x0,0x1,0x0,0x0,0x0,0x18,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0xE8,0xF,0x0,0x0,0x3,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x19,0x0,0x0,0x0,0xE8,0x0,0x0,0x0,0x5F,0x5F,0x44,0x41,0x54,0x41,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x10,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x0,0x0,0x0,0x7,0x0,0x0,0x0,0x3,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x6E,0x6C,0x5F,0x73,0x79,0x6D,0x62,0x6F,0x6C,0x5F,0x70,0x74,0x72,0x0,0x5F,0x5F,0x44,0x41,0x54,0x41,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x10,0x0,0x0,0x1,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x10,0x0,0x0,0x3,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x6,0x0,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x5F,0x5F,0x6C,0x61,0x5F,0x73,0x79,0x6D,0x62,0x6F,0x6C,0x5F,0x70,0x74,0x72,0x0,0x5F,0x5F,0x44,0x41,0x54,0x41,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x10,0x10,0x0,0x0,0x1,0x0,0x0,0x0,0x8,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x10,0x10,0x0,0x0,0x3,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x7,0x0,0x0,0x0,0x3,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x19,0x0,0x0,0x0,0x48,0x0,0x0,0x0,0x5F,0x5F,0x4C,0x49,0x4E,0x4B,0x45,0x44,0x49,0x54,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x20,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x20,0x0,0x0,0x0,0x0,0x0,0x0,0x30,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x7,0x0,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x22,0x0,0x0,0x80,0x30,0x0,0x0,0x0,0x0,0x20,0x0,0x0,0x8,0x0,0x0,0x0,0x8,0x20,0x0,0x0,0x18,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x20,0x20,0x0,0x0,0x10,0x0,0x0,0x0,0x30,0x20,0x0,0x0,0x30,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0x18,0x0,0x0,0x0,0xA8,0x20,0x0,0x0,0x4,0x0,0x0,0x0,0xF8,0x20,0x0,0x0,0x38,0x0,0x0,0x0,0xB,0x0,0x0,0x0,0x50,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0xE8,0x20,0x0,0x0,0x4,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0xE,0x0,0x0,0x0,0x20,0x0,0x0,0x0,0xC,0x0,0x0,0x0,0x2F,0x75,0x73,0x72,0x2F,0x6C,0x69,0x62,0x2F,0x64,0x79,0x6C,0x64,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x1B,0x0,0x0,0x0,0x18,0x0,0x0,0x0,0x7E,0xD2,0x4,0x95,0x33,0x23,0x3B,0xF4,0xB6,0x8F,0xBF,0x5D,0x8C,0x23,0x7B,0xA8,0x24,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x0,0xA,0xA,0x0,0x0,0xA,0xA,0x0,0x2A,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x28,0x0,0x0,0x80,0x18,0x0,0x0,0x0,0x40,0xF,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0xC,0x0,0x0,0x0,0x38,0x0,0x0,0x0,0x18,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0x0,0x0,0xBD,0x4,0x0,0x0,0x1,0x0,0x2F,0x75,0x73,0x72,0x2F,0x6C,0x69,0x62,0x2F,0x6C,0x69,0x62,0x53,0x79,0x73,0x74,0x65,0x6D,0x2E,0x42,0x2E,0x64,0x79,0x6C,0x69,0x62,0x0,0x0,0x0,0x0,0x0,0x0,0x26,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x60,0x20,0x0,0x0,0x8,0x0,0x0,0x0,0x29,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x68,0x20,0x0,0x0,0x0,0x0,0x0,0x0,0x2B,0x0,0x0,0x0,0x10,0x0,0x0,0x0,0x68,0x20,0x0,0x0,0x40,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x55,0x48,0x89,0xE5,0x48,0x83,0xEC,0x10,0x48,0x8D,0x3D,0x47,0x0,0x0,0x0,0xC7,0x45,0xFC,0x0,0x0,0x0,0x0,0xC7,0x45,0xF8,0xEF,0xBE,0xAD,0xDE,0x8B,0x75,0xF8,0xB0,0x0,0xE8,0xD,0x0,0x0,0x0,0x31,0xF6,0x89,0x45,0xF4,0x89,0xF0,0x48,0x83,0xC4,0x10,0x5D,0xC3,0xFF,0x25,0x96,0x0,0x0,0x0,0x0,0x0,0x4C,0x8D,0x1D,0x85,0x0,0x0,0x0,0x41,0x53,0xFF,0x25,0x75,0x0,0x0,0x0,0x90,0x68,0x0,0x0,0x0,0x0,0xE9,0xE6,0xFF,0xFF,0xFF,0x30,0x78,0x25,0x78,0xA,0x0,0x1,0x0,0x0,0x0,0x1C,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x1C,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x1C,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0x40,0xF,0x0,0x0,0x34,0x0,0x0,0x0,0x34,0x0,0x0,0x0,0x75,0xF,0x0,0x0,0x0,0x0,0x0,0x0,0x34,0x0,0x0,0x0,0x3,0x0,0x0,0x0,0xC,0x0,0x1,0x0,0x10,0x0,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x14,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x3,0x7A,0x52,0x0,0x1,0x78,0x10,0x1,0x10,0xC,0x7,0x8,0x90,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x8C,0xF,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x11,0x22,0x10,0x51,0x0,0x0,0x0,0x0,0x11,0x40,0x64,0x79,0x6C,0x64,0x5F,0x73,0x74,0x75,0x62,0x5F,0x62,0x69,0x6E,0x64,0x65,0x72,0x0,0x51,0x72,0x0,0x90,0x0,0x72,0x10,0x11,0x40,0x5F,0x70,0x72,0x69,0x6E,0x74,0x66,0x0,0x90,0x0,0x0,0x0,0x0,0x1,0x5F,0x0,0x5,0x0,0x2,0x5F,0x6D,0x68,0x5F,0x65,0x78,0x65,0x63,0x75,0x74,0x65,0x5F,0x68,0x65,0x61,0x64,0x65,0x72,0x0,0x21,0x6D,0x61,0x69,0x6E,0x0,0x25,0x2,0x0,0x0,0x0,0x3,0x0,0xC0,0x1E,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0xC0,0x1E,0x0,0x0,0x0,0x0,0x0,0x0,0xFA,0xDE,0xC,0x5,0x0,0x0,0x0,0x3C,0x0,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x14,0xFA,0xDE,0xC,0x0,0x0,0x0,0x0,0x28,0x0,0x0,0x0,0x1,0x0,0x0,0x0,0x6,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0xB,0x6C,0x69,0x62,0x53,0x79,0x73,0x74,0x65,0x6D,0x2E,0x42,0x0,0x0,0x0,0x0,0x3,0x0,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0xF,0x1,0x10,0x0,0x0,0x0,0x0,0x0,0x1,0x0,0x0,0x0,0x16,0x0,0x0,0x0,0xF,0x1,0x0,0x0,0x40,0xF,0x0,0x0,0x1,0x0,0x0,0x0,0x1C,0x0,0x0,0x0,0x1,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x24,0x0,0x0,0x0,0x1,0x0,0x0,0x1,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x0,0x2,0x0,0x0,0x0,0x3,0x0,0x0,0x0,0x0,0x0,0x0,0x40,0x2,0x0,0x0,0x0,0x20,0x0,0x5F,0x5F,0x6D,0x68,0x5F,0x65,0x78,0x65,0x63,0x75,0x74,0x65,0x5F,0x68,0x65,0x61,0x64,0x65,0x72,0x0,0x5F,0x6D,0x61,0x69,0x6E,0x0,0x5F,0x70,0x72,0x69,0x6E,0x74,0x66,0x0,0x64,0x79,0x6C,0x64,0x5F,0x73,0x74,0x75,0x62,0x5F,0x62,0x69,0x6E,0x64,0x65,0x72,0x0,0x0,0x0,0x0,];
 
+/// The context of the synthetic binaries of these tests, which are 64-bit and little endian
+fn le_64() -> goblin::container::Ctx {
+    goblin::container::Ctx::new(goblin::container::Container::Big, scroll::LE)
+}
+
+/// The bytes of the load `command`, padded with zeros to `cmdsize`, e.g. to make room for the strings after it
+fn command<T>(command: T, cmdsize: usize) -> Vec<u8> where T: scroll::ctx::TryIntoCtx<scroll::Endian, Error = scroll::Error, Size = usize> {
+    use scroll::Pwrite;
+    let mut bytes = vec![0u8; cmdsize];
+    bytes.pwrite_with(command, 0, scroll::LE).unwrap();
+    bytes
+}
+
+/// A synthetic binary of `filetype` for `cputype`, whose header is followed by the load `commands`, which its `ncmds`
+/// and `sizeofcmds` describe
+fn macho_64(cputype: u32, filetype: u32, commands: &[Vec<u8>]) -> Vec<u8> {
+    use scroll::Pwrite;
+    use goblin::mach::header::*;
+    let sizeofcmds = commands.iter().map(|command| command.len()).sum::<usize>();
+    let cpusubtype = if cputype == cputype::CPU_TYPE_X86_64 { cputype::CPU_SUBTYPE_X86_64_ALL as u8 } else { 0 };
+    let header = Header { magic: MH_MAGIC_64, cputype: cputype, cpusubtype: cpusubtype, filetype: filetype, ncmds: commands.len(), sizeofcmds: sizeofcmds as u32, ..Default::default() };
+    let mut bytes = vec![0u8; SIZEOF_HEADER_64];
+    bytes.pwrite_with(header, 0, le_64()).unwrap();
+    for command in commands {
+        bytes.extend_from_slice(command);
+    }
+    bytes
+}
+
+/// The bytes of an `LC_SEGMENT_64` named `segname`, followed by its `sections`, each of which is named
+/// `(sectname, addr, size, offset, flags)`
+fn segment_64(segname: &str, vmaddr: u64, vmsize: u64, fileoff: u64, filesize: u64, sections: &[(&str, u64, u64, u32, u32)]) -> Vec<u8> {
+    use scroll::Pwrite;
+    use load_command::*;
+    let name = |name: &str| {
+        let mut bytes = [0u8; 16];
+        bytes[..name.len()].copy_from_slice(name.as_bytes());
+        bytes
+    };
+    let cmdsize = SIZEOF_SEGMENT_COMMAND_64 + sections.len() * SIZEOF_SECTION_64;
+    let segment = SegmentCommand64 { cmd: LC_SEGMENT_64, cmdsize: cmdsize as u32, segname: name(segname), vmaddr: vmaddr, vmsize: vmsize, fileoff: fileoff, filesize: filesize, maxprot: 7, initprot: 3, nsects: sections.len() as u32, flags: 0 };
+    let mut bytes = command(segment, cmdsize);
+    for (i, &(sectname, addr, size, offset, flags)) in sections.iter().enumerate() {
+        let section = Section64 { sectname: name(sectname), segname: name(segname), addr: addr, size: size, offset: offset, align: 0, reloff: 0, nreloc: 0, flags: flags, reserved1: 0, reserved2: 0, reserved3: 0 };
+        bytes.pwrite_with(section, SIZEOF_SEGMENT_COMMAND_64 + i * SIZEOF_SECTION_64, scroll::LE).unwrap();
+    }
+    bytes
+}
+
+/// A symbol table entry with no name
+fn nlist(n_type: u8, n_sect: usize, n_desc: u16, n_value: u64) -> symbols::Nlist {
+    symbols::Nlist { n_strx: 0, n_type: n_type, n_sect: n_sect, n_desc: n_desc, n_value: n_value }
+}
+
 fn macho_get_section<'a>(macho: &MachO<'a>, section_name: &str) -> Option<&'a [u8]> {
     let segment_name = "__TEXT";
 
@@ -123,3 +177,2261 @@ fn relocations() {
     assert_eq!(reloc.is_pic(), true);
     assert_eq!(reloc.is_extern(), true);
 }
+
+#[test]
+fn thread_command_states() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    // an x86_64 state followed by an (unknown) two long state
+    let size = 8 + 8 + 168 + 8 + 8;
+    let mut bytes = vec![0u8; size];
+    bytes.pwrite_with(LC_UNIXTHREAD, 0, LE).unwrap();
+    bytes.pwrite_with(size as u32, 4, LE).unwrap();
+    bytes.pwrite_with(X86_THREAD_STATE64, 8, LE).unwrap();
+    bytes.pwrite_with(X86_THREAD_STATE64_COUNT, 12, LE).unwrap();
+    // rip
+    bytes.pwrite_with(0x100000f40u64, 16 + 16 * 8, LE).unwrap();
+    bytes.pwrite_with(0xdeadu32, 184, LE).unwrap();
+    bytes.pwrite_with(2u32, 188, LE).unwrap();
+    bytes.pwrite_with(0xbeefu32, 196, LE).unwrap();
+    let command = LoadCommand::parse(&bytes, &mut 0, LE).unwrap();
+    match command.command {
+        CommandVariant::Unixthread(thread) => {
+            assert_eq!(thread.flavor, X86_THREAD_STATE64);
+            assert_eq!(thread.instruction_pointer(cputype::CPU_TYPE_X86_64), Some(0x100000f40));
+            assert_eq!(thread.instruction_pointer(cputype::CPU_TYPE_ARM64), None);
+            match thread.thread_state {
+                ThreadState::X86_64(state) => assert_eq!(state.rip, 0x100000f40),
+                state => panic!("wrong thread state: {:?}", state),
+            }
+            assert_eq!(thread.additional_states.len(), 1);
+            assert_eq!(thread.additional_states[0].flavor, 0xdead);
+            match thread.additional_states[0].state {
+                ThreadState::Unknown(ref words) => assert_eq!(words, &[0, 0xbeef]),
+                ref state => panic!("wrong thread state: {:?}", state),
+            }
+        },
+        command => panic!("wrong command: {:?}", command),
+    }
+    // a state which claims to be larger than the command
+    bytes.pwrite_with(0x1000u32, 188, LE).unwrap();
+    assert!(LoadCommand::parse(&bytes, &mut 0, LE).is_err());
+}
+
+#[test]
+fn section_iterator_terminates() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let text = binary.segments.iter().find(|segment| segment.name().unwrap() == "__TEXT").unwrap();
+    assert_eq!(text.nsects, 6);
+    let mut sections = text.into_iter();
+    assert_eq!(sections.len(), 6);
+    for _ in 0..text.nsects {
+        assert!(sections.next().unwrap().is_ok());
+    }
+    assert!(sections.next().is_none());
+    assert!(sections.next().is_none());
+}
+
+#[test]
+fn lc_str_names() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; 24];
+    bytes.pwrite_with(LC_RPATH, 0, LE).unwrap();
+    bytes.pwrite_with(24u32, 4, LE).unwrap();
+    bytes.pwrite_with(12u32, 8, LE).unwrap();
+    bytes.pwrite("@loader_path", 12).unwrap();
+    let command = LoadCommand::parse(&bytes, &mut 0, LE).unwrap();
+    match command.command {
+        CommandVariant::Rpath(mut rpath) => {
+            assert_eq!(rpath.name(&bytes).unwrap(), "@loader_path");
+            rpath.path = 24;
+            assert!(rpath.name(&bytes).is_err());
+        },
+        command => panic!("wrong command: {:?}", command),
+    }
+}
+
+#[test]
+fn lc_str_lazy() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; 24];
+    bytes.pwrite_with(LC_RPATH, 0, LE).unwrap();
+    bytes.pwrite_with(24u32, 4, LE).unwrap();
+    bytes.pwrite_with(12u32, 8, LE).unwrap();
+    bytes.pwrite("@loader_path", 12).unwrap();
+    let command = LoadCommand::parse(&bytes, &mut 0, LE).unwrap();
+    match command.command {
+        CommandVariant::Rpath(rpath) => {
+            let path = rpath.lc_str(&bytes);
+            assert_eq!(path.offset, 12);
+            assert_eq!(&*path, "@loader_path");
+            assert!(path.starts_with("@loader"));
+            assert_eq!(format!("{}", path), "@loader_path");
+            assert_eq!(path.to_str().unwrap(), "@loader_path");
+            // out of range offsets resolve to the empty string, or an error
+            let bad = LcStr::new(&bytes, rpath.cmdsize, 24);
+            assert_eq!(&*bad, "");
+            assert!(bad.to_str().is_err());
+            // as do commands whose bytes are truncated
+            let truncated = rpath.lc_str(&bytes[..16]);
+            assert!(truncated.is_empty());
+            assert!(truncated.to_str().is_err());
+        },
+        command => panic!("wrong command: {:?}", command),
+    }
+}
+
+#[test]
+fn data_in_code_entries() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; 32];
+    let command = LinkeditDataCommand { cmd: LC_DATA_IN_CODE, cmdsize: 16, dataoff: 16, datasize: 16 };
+    bytes.pwrite_with(command, 0, LE).unwrap();
+    bytes.pwrite_with(DataInCodeEntry { offset: 0x1000, length: 8, kind: 1 }, 16, LE).unwrap();
+    bytes.pwrite_with(DataInCodeEntry { offset: 0x2000, length: 4, kind: 3 }, 24, LE).unwrap();
+    let ctx = le_64();
+    let entries = command.data_in_code(&bytes, ctx).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].offset, 0x2000);
+    assert_eq!(entries[1].length, 4);
+    assert_eq!(entries[1].kind, 3);
+    // entries which run past the end of the data are errors
+    let command = LinkeditDataCommand { dataoff: 24, ..command };
+    let mut entries = command.data_in_code(&bytes, ctx);
+    assert!(entries.next().unwrap().is_ok());
+    assert!(entries.next().unwrap().is_err());
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn function_starts() {
+    use load_command::*;
+    // deltas of 0x1000, 0x20 and 0x90 (encoded in two bytes), then the terminator and padding
+    let bytes = [0x80, 0x20, 0x20, 0x90, 0x01, 0x00, 0x00, 0x00];
+    let command = LinkeditDataCommand { cmd: LC_FUNCTION_STARTS, cmdsize: 16, dataoff: 0, datasize: 8 };
+    let starts = command.function_starts(&bytes, 0x100000000).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(starts, [0x100001000, 0x100001020, 0x1000010b0]);
+    // a ULEB128 which runs past the end of the blob is an error, not an out of bounds read
+    let command = LinkeditDataCommand { datasize: 4, ..command };
+    let mut starts = command.function_starts(&bytes, 0x100000000);
+    assert_eq!(starts.next().unwrap().unwrap(), 0x100001000);
+    assert_eq!(starts.next().unwrap().unwrap(), 0x100001020);
+    assert!(starts.next().unwrap().is_err());
+    assert!(starts.next().is_none());
+}
+
+#[test]
+fn linker_option_strings() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; 32];
+    bytes.pwrite_with(LinkerOptionCommand { cmd: LC_LINKER_OPTION, cmdsize: 32, count: 2 }, 0, LE).unwrap();
+    bytes.pwrite("-framework", 12).unwrap();
+    bytes.pwrite("Foo", 23).unwrap();
+    let command = LoadCommand::parse(&bytes, &mut 0, LE).unwrap();
+    match command.command {
+        CommandVariant::LinkerOption(mut command) => {
+            assert_eq!(command.strings(&bytes).unwrap(), ["-framework", "Foo"]);
+            // the zero fill at the end can only account for six more (empty) strings
+            command.count = 9;
+            assert!(command.strings(&bytes).is_err());
+        },
+        command => panic!("wrong command: {:?}", command),
+    }
+}
+
+#[test]
+fn version_min_display() {
+    use load_command::*;
+    let command = VersionMinCommand { version: 0x000a0f00, sdk: 0x000a0f04, ..VersionMinCommand::new(false) };
+    assert_eq!(command.version_tuple(), (10, 15, 0));
+    assert_eq!(command.sdk_tuple(), (10, 15, 4));
+    assert_eq!(command.to_string(), "10.15.0 (sdk 10.15.4)");
+}
+
+#[test]
+fn build_version() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; 40];
+    let command = BuildVersionCommand { cmd: LC_BUILD_VERSION, cmdsize: 40, platform: PLATFORM_MACOS, minos: 0x000a0e00, sdk: 0x000a0f00, ntools: 2 };
+    bytes.pwrite_with(command, 0, LE).unwrap();
+    bytes.pwrite_with(BuildToolVersion { tool: 3, version: 0x02620000 }, 24, LE).unwrap();
+    bytes.pwrite_with(BuildToolVersion { tool: 1, version: 0x03ff0000 }, 32, LE).unwrap();
+    let command = match LoadCommand::parse(&bytes, &mut 0, LE).unwrap().command {
+        CommandVariant::BuildVersion(command) => command,
+        command => panic!("wrong command: {:?}", command),
+    };
+    assert_eq!(command.platform(), Platform::MacOS);
+    assert_eq!(command.minos_tuple(), (10, 14, 0));
+    assert_eq!(command.sdk_tuple(), (10, 15, 0));
+    let tools = command.tools(&bytes, LE).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(tools.len(), 2);
+    assert_eq!(tools[1].tool, 1);
+    assert_eq!(tools[0].tool_kind(), Tool::Ld);
+    assert_eq!(tools[1].tool_kind().to_string(), "clang");
+    assert_eq!(Tool::from(0x1000), Tool::Unknown(0x1000));
+    assert_eq!(command.platform().to_string(), "macOS");
+    assert!(!command.platform().is_simulator());
+    let simulator = Platform::from(PLATFORM_IOSSIMULATOR);
+    assert!(simulator.is_simulator());
+    assert_eq!(simulator.to_string(), "iOS Simulator");
+    assert_eq!(Platform::from(PLATFORM_MACCATALYST), Platform::MacCatalyst);
+    assert_eq!(Platform::from(0xff), Platform::Unknown(0xff));
+    // tools which do not fit in cmdsize are errors
+    let command = BuildVersionCommand { ntools: 3, ..command };
+    assert!(command.tools(&bytes, LE).nth(2).unwrap().is_err());
+}
+
+#[test]
+fn source_version() {
+    use load_command::*;
+    let version = |version| SourceVersionCommand { cmd: LC_SOURCE_VERSION, cmdsize: 16, version: version };
+    let command = version(0x4ee0000000000);
+    assert_eq!(command.version_tuple(), (1262, 0, 0, 0, 0));
+    assert_eq!(command.to_string(), "1262");
+    let command = version(0x2bf0001f00402);
+    assert_eq!(command.version_tuple(), (703, 0, 31, 1, 2));
+    assert_eq!(command.to_string(), "703.0.31.1.2");
+    let command = version(0xffffffffffffffff);
+    assert_eq!(command.version_tuple(), (0xffffff, 0x3ff, 0x3ff, 0x3ff, 0x3ff));
+    assert_eq!(version(0).to_string(), "0");
+}
+
+#[test]
+fn indirect_symbols() {
+    use load_command::*;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let dysymtab = binary.load_commands.iter().filter_map(|cmd| match cmd.command {
+        CommandVariant::Dysymtab(command) => Some(command),
+        _ => None,
+    }).next().unwrap();
+    let ctx = le_64();
+    let indirect = dysymtab.indirect_symbols(&DEADBEEF_MACH_64[..], ctx).collect::<Result<Vec<_>, _>>().unwrap();
+    println!("indirect symbols: {:?}", indirect);
+    assert_eq!(indirect.len(), dysymtab.nindirectsyms as usize);
+    assert!(indirect.len() > 0);
+    let nsyms = binary.symbols().count() as u32;
+    assert!(indirect.iter().all(|&index| index & (INDIRECT_SYMBOL_LOCAL | INDIRECT_SYMBOL_ABS) != 0 || index < nsyms));
+}
+
+#[test]
+fn dyld_info_exports() {
+    use load_command::*;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let dyld_info = binary.load_commands.iter().filter_map(|cmd| match cmd.command {
+        CommandVariant::DyldInfoOnly(command) => Some(command),
+        _ => None,
+    }).next().unwrap();
+    let exports = dyld_info.exports(&DEADBEEF_MACH_64[..], &binary.libs).unwrap();
+    let names = exports.iter().map(|export| export.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, binary.exports().unwrap().iter().map(|export| export.name.as_str()).collect::<Vec<_>>());
+    let main = exports.iter().find(|export| export.name == "_main").unwrap();
+    assert_eq!(main.offset, 0xf40);
+    assert_eq!(main.flags, goblin::mach::exports::EXPORT_SYMBOL_FLAGS_KIND_REGULAR);
+    assert_eq!(main.kind, goblin::mach::exports::SymbolKind::Regular);
+}
+
+#[test]
+fn dyld_info_binds() {
+    use load_command::*;
+    use imports::BindKind;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let dyld_info = binary.load_commands.iter().filter_map(|cmd| match cmd.command {
+        CommandVariant::DyldInfoOnly(command) => Some(command),
+        _ => None,
+    }).next().unwrap();
+    let ctx = le_64();
+    let binds = dyld_info.binds(&DEADBEEF_MACH_64[..], &binary.segments, ctx).unwrap();
+    println!("binds: {:#?}", binds);
+    let imports = binary.imports().unwrap();
+    assert_eq!(imports.len(), binds.len());
+    for (import, bind) in imports.iter().zip(binds.iter()) {
+        assert_eq!(import.name, bind.symbol_name);
+        assert_eq!(import.is_lazy, bind.is_lazy());
+        assert_eq!(import.is_weak, bind.is_weak() || bind.kind == BindKind::Weak);
+        if bind.kind != BindKind::Weak && bind.library_ordinal > 0 {
+            assert_eq!(import.dylib, binary.libs[bind.library_ordinal as usize]);
+        }
+        assert_eq!(import.address, binary.segments[bind.segment_index as usize].vmaddr + bind.segment_offset);
+    }
+    assert!(binds.iter().any(|bind| bind.is_lazy()));
+}
+
+#[test]
+fn bind_segment_out_of_range() {
+    use goblin::mach::bind_opcodes::*;
+    use load_command::*;
+    // bind "_a" in segment 5, which doesn't exist
+    let bytes = [BIND_OPCODE_SET_DYLIB_ORDINAL_IMM | 1, BIND_OPCODE_SET_SYMBOL_TRAILING_FLAGS_IMM, b'_', b'a', 0,
+                 BIND_OPCODE_SET_TYPE_IMM | BIND_TYPE_POINTER, BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 5, 0x10,
+                 BIND_OPCODE_DO_BIND, BIND_OPCODE_DONE, BIND_OPCODE_DO_BIND, 0];
+    let mut command = DyldInfoCommand::default();
+    command.bind_size = bytes.len() as u32;
+    let interpreter = imports::BindInterpreter::new(&bytes, &command);
+    let ctx = le_64();
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert!(interpreter.binds(&binary.segments, &ctx).is_err());
+    assert!(interpreter.imports(&["self", "libfoo.dylib"], &binary.segments, &ctx).is_err());
+    // binding in segment 2 is fine, and nothing after BIND_OPCODE_DONE is bound
+    let mut bytes = bytes;
+    bytes[6] = BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 2;
+    let interpreter = imports::BindInterpreter::new(&bytes, &command);
+    let binds = interpreter.binds(&binary.segments, &ctx).unwrap();
+    assert_eq!(binds.len(), 1);
+    assert_eq!(binds[0].segment_index, 2);
+    assert_eq!(binds[0].segment_offset, 0x10);
+    assert_eq!(binds[0].library_ordinal, 1);
+    // but a huge run of binds overruns the segment, and is rejected before any of it is recorded
+    let bytes = [BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 2, 0, BIND_OPCODE_DO_BIND_ULEB_TIMES_SKIPPING_ULEB, 0x80, 0x80, 0x80, 0x80, 0x80, 0x20, 0,
+                 BIND_OPCODE_DONE];
+    command.bind_size = bytes.len() as u32;
+    assert!(imports::BindInterpreter::new(&bytes, &command).binds(&binary.segments, &ctx).is_err());
+    // as is a skip which overflows
+    let bytes = [BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 2, 0, BIND_OPCODE_DO_BIND_ULEB_TIMES_SKIPPING_ULEB, 2,
+                 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, BIND_OPCODE_DONE];
+    command.bind_size = bytes.len() as u32;
+    assert!(imports::BindInterpreter::new(&bytes, &command).binds(&binary.segments, &ctx).is_err());
+}
+
+#[test]
+fn bind_run_past_file_contents() {
+    use goblin::mach::bind_opcodes::*;
+    use load_command::*;
+    // a segment claiming 1 << 40 bytes of memory, only 0x10 of which are in the file
+    let segment = segment_64("__DATA", 0x1000, 1 << 40, 0, 0x10, &[]);
+    let bytes = macho_64(cputype::CPU_TYPE_X86_64, header::MH_EXECUTE, &[segment]);
+    let binary = MachO::parse(&bytes, 0).unwrap();
+    let ctx = le_64();
+    let binds = |opcodes: &[u8]| {
+        let command = DyldInfoCommand { bind_size: opcodes.len() as u32, ..Default::default() };
+        imports::BindInterpreter::new(opcodes, &command).binds(&binary.segments, &ctx).map(|binds| binds.len())
+    };
+    // 1 << 36 binds fit in its memory, but not its file contents
+    let opcodes = [BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB, 0, BIND_OPCODE_DO_BIND_ULEB_TIMES_SKIPPING_ULEB, 0x80, 0x80, 0x80, 0x80, 0x80, 0x02, 0,
+                   BIND_OPCODE_DONE];
+    assert!(binds(&opcodes).is_err());
+    // its two pointers can be bound
+    let opcodes = [BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB, 0, BIND_OPCODE_DO_BIND_ULEB_TIMES_SKIPPING_ULEB, 2, 0, BIND_OPCODE_DONE];
+    assert_eq!(binds(&opcodes).unwrap(), 2);
+    // but not over and over
+    let opcodes = [BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB, 0, BIND_OPCODE_DO_BIND_ULEB_TIMES_SKIPPING_ULEB, 2, 0,
+                   BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB, 0, BIND_OPCODE_DO_BIND_ULEB_TIMES_SKIPPING_ULEB, 2, 0, BIND_OPCODE_DONE];
+    assert!(binds(&opcodes).is_err());
+}
+
+#[test]
+fn dyld_info_rebases() {
+    use goblin::mach::rebase_opcodes::*;
+    use load_command::*;
+    let ctx = le_64();
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let dyld_info = binary.load_commands.iter().filter_map(|cmd| match cmd.command {
+        CommandVariant::DyldInfoOnly(command) => Some(command),
+        _ => None,
+    }).next().unwrap();
+    let rebases = dyld_info.rebases(&DEADBEEF_MACH_64[..], &binary.segments, ctx).unwrap();
+    println!("rebases: {:?}", rebases);
+    assert!(rebases.iter().all(|rebase| (rebase.segment_index as usize) < binary.segments.len()));
+    // two pointers in segment 2 at 0x10, skipping 0x8 between them, then one at 0x40
+    let bytes = [REBASE_OPCODE_SET_TYPE_IMM | REBASE_TYPE_POINTER, REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 2, 0x10,
+                 REBASE_OPCODE_DO_REBASE_ULEB_TIMES_SKIPPING_ULEB, 2, 8, REBASE_OPCODE_ADD_ADDR_ULEB, 0x10,
+                 REBASE_OPCODE_DO_REBASE_IMM_TIMES | 1, REBASE_OPCODE_DONE];
+    let mut command = DyldInfoCommand::default();
+    command.rebase_size = bytes.len() as u32;
+    let rebases = command.rebases(&bytes, &binary.segments, ctx).unwrap();
+    let offsets = rebases.iter().map(|rebase| rebase.segment_offset).collect::<Vec<_>>();
+    assert_eq!(offsets, [0x10, 0x20, 0x40]);
+    assert!(rebases.iter().all(|rebase| rebase.segment_index == 2 && rebase.rebase_type == REBASE_TYPE_POINTER));
+    // a run which overflows the segment offset
+    let bytes = [REBASE_OPCODE_DO_REBASE_ULEB_TIMES, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f];
+    command.rebase_size = bytes.len() as u32;
+    assert!(command.rebases(&bytes, &binary.segments, ctx).is_err());
+    // a huge run which doesn't overflow, but overruns the segment, is rejected before any of it is recorded
+    let bytes = [REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 2, 0, REBASE_OPCODE_DO_REBASE_ULEB_TIMES, 0x80, 0x80, 0x80, 0x80, 0x80, 0x20];
+    command.rebase_size = bytes.len() as u32;
+    assert!(command.rebases(&bytes, &binary.segments, ctx).is_err());
+    let bytes = [REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 2, 0, REBASE_OPCODE_DO_REBASE_ULEB_TIMES_SKIPPING_ULEB, 0x80, 0x80, 0x80, 0x80, 0x80, 0x20, 0];
+    command.rebase_size = bytes.len() as u32;
+    assert!(command.rebases(&bytes, &binary.segments, ctx).is_err());
+    // as is a segment which doesn't exist
+    let bytes = [REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 9, 0, REBASE_OPCODE_DO_REBASE_IMM_TIMES | 1];
+    command.rebase_size = bytes.len() as u32;
+    assert!(command.rebases(&bytes, &binary.segments, ctx).is_err());
+}
+
+#[test]
+fn rebase_run_past_file_contents() {
+    use goblin::mach::rebase_opcodes::*;
+    use load_command::*;
+    // a segment claiming 1 << 40 bytes of memory, only 0x10 of which are in the file
+    let segment = segment_64("__DATA", 0x1000, 1 << 40, 0, 0x10, &[]);
+    let bytes = macho_64(cputype::CPU_TYPE_X86_64, header::MH_EXECUTE, &[segment]);
+    let binary = MachO::parse(&bytes, 0).unwrap();
+    let rebases = |opcodes: &[u8]| {
+        let command = DyldInfoCommand { rebase_size: opcodes.len() as u32, ..Default::default() };
+        command.rebases(opcodes, &binary.segments, le_64()).map(|rebases| rebases.len())
+    };
+    // 1 << 36 rebases fit in its memory, but not its file contents
+    let opcodes = [REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB, 0, REBASE_OPCODE_DO_REBASE_ULEB_TIMES, 0x80, 0x80, 0x80, 0x80, 0x80, 0x02, REBASE_OPCODE_DONE];
+    assert!(rebases(&opcodes).is_err());
+    // its two pointers can be rebased
+    let opcodes = [REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB, 0, REBASE_OPCODE_DO_REBASE_ULEB_TIMES, 2, REBASE_OPCODE_DONE];
+    assert_eq!(rebases(&opcodes).unwrap(), 2);
+    // but not over and over
+    let opcodes = [REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB, 0, REBASE_OPCODE_DO_REBASE_ULEB_TIMES, 2,
+                   REBASE_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB, 0, REBASE_OPCODE_DO_REBASE_ULEB_TIMES, 2, REBASE_OPCODE_DONE];
+    assert!(rebases(&opcodes).is_err());
+}
+
+#[test]
+fn routines() {
+    use scroll::{Pread, LE};
+    use load_command::*;
+    let bytes = command(RoutinesCommand32 { cmd: LC_ROUTINES, cmdsize: SIZEOF_ROUTINES_COMMAND_32 as u32, init_address: 0x1f40, init_module: 3, reserved1: 0, reserved2: 0, reserved3: 0, reserved4: 0, reserved5: 0, reserved6: 0 }, SIZEOF_ROUTINES_COMMAND_32);
+    let routines = match bytes.pread_with::<CommandVariant>(0, LE).unwrap() {
+        CommandVariant::Routines32(command) => Routines::from(command),
+        command => panic!("wrong command: {:?}", command),
+    };
+    assert_eq!((routines.cmd, routines.cmdsize), (LC_ROUTINES, 40));
+    assert_eq!((routines.init_address, routines.init_module), (0x1f40, 3));
+    let bytes = command(RoutinesCommand64 { cmd: LC_ROUTINES_64, cmdsize: SIZEOF_ROUTINES_COMMAND_64 as u32, init_address: 0x1_0000_1f40, init_module: 5, reserved1: 0, reserved2: 0, reserved3: 0, reserved4: 0, reserved5: 0, reserved6: 0 }, SIZEOF_ROUTINES_COMMAND_64);
+    let routines = match bytes.pread_with::<CommandVariant>(0, LE).unwrap() {
+        CommandVariant::Routines64(command) => Routines::from(command),
+        command => panic!("wrong command: {:?}", command),
+    };
+    assert_eq!((routines.cmd, routines.cmdsize), (LC_ROUTINES_64, 72));
+    assert_eq!((routines.init_address, routines.init_module), (0x1_0000_1f40, 5));
+}
+
+#[test]
+fn cpu_subtype_names() {
+    use cputype::*;
+    assert_eq!(cpu_subtype_to_str(CPU_TYPE_X86_64, CPU_SUBTYPE_X86_64_ALL | CPU_SUBTYPE_LIB64), "x86_64");
+    assert_eq!(cpu_subtype_to_str(CPU_TYPE_X86_64, CPU_SUBTYPE_X86_64_H), "x86_64h");
+    assert_eq!(cpu_subtype_to_str(CPU_TYPE_ARM64, CPU_SUBTYPE_ARM64E | 0x80000000), "arm64e");
+    assert_eq!(cpu_subtype_to_str(CPU_TYPE_ARM, CPU_SUBTYPE_ARM_V7S), "armv7s");
+    assert_eq!(cpu_subtype_to_str(CPU_TYPE_X86, CPU_SUBTYPE_X86_ALL), "i386");
+}
+
+#[test]
+fn arm64e_ptrauth_abi() {
+    use cputype::*;
+    assert!(is_arm64e(CPU_TYPE_ARM64, CPU_SUBTYPE_ARM64E));
+    assert!(is_arm64e(CPU_TYPE_ARM64, CPU_SUBTYPE_ARM64E | CPU_SUBTYPE_PTRAUTH_ABI | 0x0200_0000));
+    assert!(!is_arm64e(CPU_TYPE_ARM64, CPU_SUBTYPE_ARM64_ALL));
+    assert!(!is_arm64e(CPU_TYPE_ARM, CPU_SUBTYPE_ARM64E));
+    // an unversioned ABI has no version, even with bits in the version field
+    assert_eq!(ptrauth_abi_version(CPU_SUBTYPE_ARM64E), None);
+    assert_eq!(ptrauth_abi_version(CPU_SUBTYPE_ARM64E | 0x0100_0000), None);
+    assert_eq!(ptrauth_abi_version(CPU_SUBTYPE_ARM64E | CPU_SUBTYPE_PTRAUTH_ABI), Some(0));
+    // the kernel bit is not part of the version
+    assert_eq!(ptrauth_abi_version(CPU_SUBTYPE_ARM64E | CPU_SUBTYPE_PTRAUTH_ABI | CPU_SUBTYPE_ARM64E_KERNEL_PTRAUTH_ABI | 0x0300_0000), Some(3));
+    assert_eq!(ptrauth_abi_version(CPU_SUBTYPE_ARM64_ALL | CPU_SUBTYPE_PTRAUTH_ABI), None);
+}
+
+#[test]
+fn segment_file_range_out_of_bounds() {
+    use scroll::{Pread, LE};
+    use load_command::*;
+    let ctx = le_64();
+    let bytes = [0u8; SIZEOF_SEGMENT_COMMAND_64];
+    let mut command = bytes.pread_with::<SegmentCommand64>(0, LE).unwrap();
+    command.fileoff = 0x10;
+    command.filesize = 0x20;
+    assert!(segment::Segment::from_64(&bytes, &command, 0, ctx).is_ok());
+    command.filesize = 0x80;
+    assert!(segment::Segment::from_64(&bytes, &command, 0, ctx).is_err());
+    command.fileoff = u64::max_value();
+    command.filesize = 2;
+    assert!(segment::Segment::from_64(&bytes, &command, 0, ctx).is_err());
+}
+
+#[test]
+fn section_data_out_of_bounds() {
+    use scroll::{Pread, Pwrite, LE};
+    use load_command::*;
+    let ctx = le_64();
+    let data_offset = SIZEOF_SEGMENT_COMMAND_64 + 4 * SIZEOF_SECTION_64;
+    let mut bytes = vec![0u8; data_offset + 0x10];
+    let mut command = bytes.pread_with::<SegmentCommand64>(0, LE).unwrap();
+    command.cmd = LC_SEGMENT_64;
+    command.nsects = 4;
+    command.filesize = bytes.len() as u64;
+    let section = |offset: u32, size: u64, flags: u32| {
+        let mut section = [0u8; SIZEOF_SECTION_64].pread_with::<Section64>(0, LE).unwrap();
+        section.offset = offset;
+        section.size = size;
+        section.flags = flags;
+        section
+    };
+    let sections = [
+        section(data_offset as u32, 0x10, 0),
+        // past the end of the binary
+        section(data_offset as u32, 0x1000, 0),
+        // overflowing
+        section(u32::max_value(), u64::max_value(), 0),
+        // zerofill sections have no file data
+        section(0, 0x1000, constants::S_ZEROFILL),
+    ];
+    for (i, section) in sections.iter().enumerate() {
+        bytes.pwrite_with(*section, SIZEOF_SEGMENT_COMMAND_64 + i * SIZEOF_SECTION_64, LE).unwrap();
+    }
+    let segment = segment::Segment::from_64(&bytes, &command, 0, ctx).unwrap();
+    let mut sections = segment.into_iter();
+    assert_eq!(sections.next().unwrap().unwrap().1.len(), 0x10);
+    assert!(sections.next().unwrap().is_err());
+    assert!(sections.next().unwrap().is_err());
+    let (section, data) = sections.next().unwrap().unwrap();
+    assert_eq!(section.size, 0x1000);
+    assert!(data.is_empty());
+    assert!(sections.next().is_none());
+}
+
+#[test]
+fn section_type_and_attributes() {
+    use constants::*;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    for segment in &*binary.segments {
+        for section in segment {
+            let (section, _) = section.unwrap();
+            assert_eq!(section.section_type() | section.attributes(), section.flags);
+            match section.name().unwrap() {
+                "__text" => {
+                    assert_eq!(section.section_type(), S_REGULAR);
+                    assert!(section.attributes() & S_ATTR_PURE_INSTRUCTIONS != 0);
+                },
+                "__cstring" => assert_eq!(section.section_type(), S_CSTRING_LITERALS),
+                _ => (),
+            }
+        }
+    }
+}
+
+#[test]
+fn write_load_commands() {
+    use scroll::LE;
+    use load_command::*;
+    let bytes = &DEADBEEF_MACH_64[..];
+    let binary = MachO::parse(bytes, 0).unwrap();
+    let mut written = 0;
+    for cmd in &binary.load_commands {
+        let mut out = vec![0u8; cmd.command.cmdsize()];
+        match cmd.command {
+            CommandVariant::LoadDylib(_) | CommandVariant::LoadDylinker(_) | CommandVariant::Unixthread(_) => {
+                assert!(cmd.write(&mut out, 0, LE).is_err());
+            },
+            _ => {
+                let size = cmd.write(&mut out, 0, LE).unwrap();
+                assert_eq!(&out[..size], &bytes[cmd.offset..cmd.offset + size]);
+                written += 1;
+            }
+        }
+    }
+    assert!(written > 0);
+}
+
+#[test]
+fn dylib_modules_and_toc() {
+    use scroll::{Pwrite, LE};
+    use scroll::ctx::SizeWith;
+    use load_command::*;
+    use goblin::container::{Ctx, Container};
+    assert_eq!(DylibModule::size_with(&LE), SIZEOF_DYLIB_MODULE);
+    assert_eq!(DylibModule64::size_with(&LE), SIZEOF_DYLIB_MODULE_64);
+    let mut bytes = vec![0u8; 2 * SIZEOF_DYLIB_MODULE_64 + 2 * SIZEOF_DYLIB_TABLE_OF_CONTENTS];
+    // the module names, at the start of each 32 and 64-bit module
+    bytes.pwrite_with(7u32, 0, LE).unwrap();
+    bytes.pwrite_with(9u32, SIZEOF_DYLIB_MODULE, LE).unwrap();
+    bytes.pwrite_with(11u32, SIZEOF_DYLIB_MODULE_64, LE).unwrap();
+    let tocoff = 2 * SIZEOF_DYLIB_MODULE_64;
+    bytes.pwrite_with(DylibTableOfContents { symbol_index: 3, module_index: 1 }, tocoff + SIZEOF_DYLIB_TABLE_OF_CONTENTS, LE).unwrap();
+    let mut dysymtab = DysymtabCommand::new();
+    dysymtab.nmodtab = 2;
+    dysymtab.tocoff = tocoff as u32;
+    dysymtab.ntoc = 2;
+    let names = |ctx| dysymtab.modules(&bytes, ctx).map(|module| module.unwrap().module_name).collect::<Vec<_>>();
+    assert_eq!(names(Ctx::new(Container::Little, LE)), [7, 9]);
+    assert_eq!(names(le_64()), [7, 11]);
+    let toc = dysymtab.table_of_contents(&bytes, le_64()).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(toc.len(), 2);
+    assert_eq!((toc[1].symbol_index, toc[1].module_index), (3, 1));
+}
+
+#[test]
+fn dylib_reference() {
+    use scroll::{Pread, BE, LE};
+    use scroll::ctx::SizeWith;
+    use load_command::*;
+    assert_eq!(DylibReference::size_with(&LE), SIZEOF_DYLIB_REFERENCE);
+    let reference = [0x56, 0x34, 0x12, 0x02].pread_with::<DylibReference>(0, LE).unwrap();
+    assert_eq!(reference.isym(), 0x123456);
+    assert_eq!(reference.flags(), 2);
+    // big-endian bitfields start from the most significant bit, so the isym comes first
+    let reference = [0x12, 0x34, 0x56, 0x02].pread_with::<DylibReference>(0, BE).unwrap().to_little_endian_bitfields();
+    assert_eq!(reference.isym(), 0x123456);
+    assert_eq!(reference.flags(), 2);
+}
+
+#[test]
+fn twolevel_hints() {
+    use scroll::LE;
+    use scroll::ctx::SizeWith;
+    use load_command::*;
+    assert_eq!(TwolevelHint::size_with(&LE), SIZEOF_TWOLEVEL_HINT);
+    let bytes = [0xff, 0xff, 0x02, 0x00, 0x00, 0x01, 0x03, 0x00, 0x00, 0x00, 0x00];
+    let command = TwolevelHintsCommand { cmd: LC_TWOLEVEL_HINTS, cmdsize: 16, offset: 2, nhints: 2 };
+    let ctx = le_64();
+    let hints = command.hints(&bytes, ctx).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!((hints[0].isub_image(), hints[0].itoc()), (2, 0x10000));
+    assert_eq!((hints[1].isub_image(), hints[1].itoc()), (3, 0));
+    // only 3 bytes are left for a third hint
+    let command = TwolevelHintsCommand { nhints: 3, ..command };
+    assert!(command.hints(&bytes, ctx).nth(2).unwrap().is_err());
+    // the isub_image comes first in a big-endian binary
+    let bytes = [0x02, 0x01, 0x00, 0x00, 0x03, 0x00, 0x00, 0x05];
+    let command = TwolevelHintsCommand { offset: 0, nhints: 2, ..command };
+    let ctx = goblin::container::Ctx::new(goblin::container::Container::Big, scroll::BE);
+    let hints = command.hints(&bytes, ctx).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!((hints[0].isub_image(), hints[0].itoc()), (2, 0x10000));
+    assert_eq!((hints[1].isub_image(), hints[1].itoc()), (3, 5));
+}
+
+#[test]
+fn code_signature() {
+    use scroll::{BE, LE, Pwrite};
+    use goblin::mach::codesign::*;
+    let mut bytes = vec![0u8; 4 + 86];
+    {
+        let sig = &mut bytes[4..];
+        sig.pwrite_with(SuperBlobHeader { magic: CSMAGIC_EMBEDDED_SIGNATURE, length: 86, count: 1 }, 0, BE).unwrap();
+        sig.pwrite_with(BlobIndex { typ: CSSLOT_CODEDIRECTORY, offset: 20 }, 12, BE).unwrap();
+        let header = CodeDirectoryHeader {
+            magic: CSMAGIC_CODEDIRECTORY, length: 66, version: 0x20100, flags: 0, hash_offset: 58, ident_offset: 44,
+            n_special_slots: 2, n_code_slots: 2, code_limit: 0x2000, hash_size: 4, hash_type: CS_HASHTYPE_SHA256_TRUNCATED,
+            platform: 0, page_size: 12, spare2: 0,
+        };
+        let cd = &mut sig[20..];
+        cd.pwrite_with(header, 0, BE).unwrap();
+        cd[44..50].copy_from_slice(b"a.out\0");
+        cd[50..66].copy_from_slice(&[2, 2, 2, 2, 1, 1, 1, 1, 0xa, 0xa, 0xa, 0xa, 0xb, 0xb, 0xb, 0xb]);
+    }
+    let command = load_command::LinkeditDataCommand { cmd: load_command::LC_CODE_SIGNATURE, cmdsize: 16, dataoff: 4, datasize: 86 };
+    let signature = command.code_signature(&bytes).unwrap();
+    assert_eq!(signature.blobs.len(), 1);
+    let cd = signature.code_directory().unwrap();
+    assert_eq!(cd.identifier, "a.out");
+    assert_eq!(cd.hash_type(), CS_HASHTYPE_SHA256_TRUNCATED);
+    assert_eq!(cd.page_size(), Some(4096));
+    assert_eq!(cd.code_hashes, vec![&[0xa; 4][..], &[0xb; 4][..]]);
+    assert_eq!(cd.special_hashes, vec![&[1; 4][..], &[2; 4][..]]);
+    // fields are big-endian no matter the binary's endianness
+    bytes.pwrite_with(CSMAGIC_EMBEDDED_SIGNATURE, 4, LE).unwrap();
+    assert!(command.code_signature(&bytes).is_err());
+    // the code directory claims more hashes than fit in its blob
+    bytes.pwrite_with(CSMAGIC_EMBEDDED_SIGNATURE, 4, BE).unwrap();
+    bytes.pwrite_with(3u32, 4 + 20 + 28, BE).unwrap();
+    assert!(command.code_signature(&bytes).is_err());
+    // as does one with billions of empty hashes, or any empty special hashes
+    let mut cd = bytes[4 + 20..].to_vec();
+    cd[36] = 0;
+    cd.pwrite_with(0xffff_ffffu32, 28, BE).unwrap();
+    assert!(CodeDirectory::parse(&cd).is_err());
+    cd.pwrite_with(0u32, 28, BE).unwrap();
+    assert!(CodeDirectory::parse(&cd).is_err());
+    // but no hashes at all needn't have a size
+    cd.pwrite_with(0u32, 24, BE).unwrap();
+    assert!(CodeDirectory::parse(&cd).unwrap().code_hashes.is_empty());
+}
+
+#[test]
+fn find_cputype() {
+    use scroll::{BE, Pwrite};
+    use goblin::mach::fat::*;
+    use cputype::*;
+    let arches = [
+        (CPU_TYPE_X86_64, CPU_SUBTYPE_X86_64_H),
+        (CPU_TYPE_ARM64, CPU_SUBTYPE_ANY),
+        (CPU_TYPE_ARM64, CPU_SUBTYPE_ARM64E | 0x80000000),
+    ];
+    let mut bytes = vec![0u8; SIZEOF_FAT_HEADER + arches.len() * SIZEOF_FAT_ARCH];
+    bytes.pwrite_with(FatHeader { magic: FAT_MAGIC, nfat_arch: arches.len() as u32 }, 0, BE).unwrap();
+    for (i, &(cputype, cpusubtype)) in arches.iter().enumerate() {
+        let arch = FatArch { cputype: cputype, cpusubtype: cpusubtype, offset: i as u32, size: 0, align: 0 };
+        bytes.pwrite_with(arch, SIZEOF_FAT_HEADER + i * SIZEOF_FAT_ARCH, BE).unwrap();
+    }
+    let multi = goblin::mach::MultiArch::new(&bytes).unwrap();
+    let offset = |cputype, cpusubtype| multi.find_cputype(cputype, cpusubtype).unwrap().map(|arch| arch.offset);
+    assert_eq!(offset(CPU_TYPE_ARM64, Some(CPU_SUBTYPE_ARM64E)), Some(2));
+    assert_eq!(offset(CPU_TYPE_ARM64, Some(CPU_SUBTYPE_ARM64_ALL)), Some(1));
+    assert_eq!(offset(CPU_TYPE_ARM64, None), Some(1));
+    assert_eq!(offset(CPU_TYPE_X86_64, Some(CPU_SUBTYPE_X86_64_H)), Some(0));
+    assert_eq!(offset(CPU_TYPE_X86_64, Some(CPU_SUBTYPE_X86_64_ALL)), None);
+    assert_eq!(offset(CPU_TYPE_X86, None), None);
+}
+
+#[test]
+fn encryption_info() {
+    use load_command::*;
+    let command = EncryptionInfoCommand64 { cmd: LC_ENCRYPTION_INFO_64, cmdsize: 24, cryptoff: 0x4000, cryptsize: 0xffffffff, cryptid: 1, pad: 0 };
+    assert!(command.is_encrypted());
+    assert_eq!(command.encrypted_range(), 0x4000..0x1_0000_3fff);
+    let command = EncryptionInfoCommand32 { cmd: LC_ENCRYPTION_INFO, cmdsize: 20, cryptoff: 0x1000, cryptsize: 0x2000, cryptid: 0 };
+    assert!(!command.is_encrypted());
+    assert_eq!(command.encrypted_range(), 0x1000..0x3000);
+}
+
+#[test]
+fn uuid_display() {
+    use load_command::*;
+    let command = UuidCommand {
+        cmd: LC_UUID, cmdsize: SIZEOF_UUID_COMMAND as u32,
+        uuid: [0x0a, 0x1b, 0x2c, 0x3d, 0x4e, 0x5f, 0x60, 0x71, 0x82, 0x93, 0xa4, 0xb5, 0xc6, 0xd7, 0xe8, 0xf9],
+    };
+    assert_eq!(command.to_string(), "0A1B2C3D-4E5F-6071-8293-A4B5C6D7E8F9");
+}
+
+#[test]
+fn macho_uuid() {
+    // 7ED20495-3323-3BF4-B68F-BF5D8C237BA8
+    let expected = [0x7e, 0xd2, 0x04, 0x95, 0x33, 0x23, 0x3b, 0xf4, 0xb6, 0x8f, 0xbf, 0x5d, 0x8c, 0x23, 0x7b, 0xa8];
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert_eq!(binary.uuid(), Some(expected));
+    assert_eq!(Mach::Binary(binary).uuid(), Some(expected));
+    let bytes = macho_64(cputype::CPU_TYPE_X86_64, header::MH_EXECUTE, &[]);
+    assert_eq!(MachO::parse(&bytes, 0).unwrap().uuid(), None);
+}
+
+#[test]
+fn dysymtab_relocations() {
+    use scroll::{LE, Pwrite};
+    use goblin::mach::relocation::{Relocation, RelocationInfo};
+    let ctx = le_64();
+    let mut bytes = vec![0u8; 24];
+    bytes.pwrite_with(RelocationInfo { r_address: 0x10, r_info: 0x0e00_0005 }, 0, LE).unwrap();
+    bytes.pwrite_with(RelocationInfo { r_address: 0x20, r_info: 0x0600_0001 }, 8, LE).unwrap();
+    bytes.pwrite_with(RelocationInfo { r_address: 0x28, r_info: 0x0600_0002 }, 16, LE).unwrap();
+    let mut command = load_command::DysymtabCommand::new();
+    command.extreloff = 0;
+    command.nextrel = 1;
+    command.locreloff = 8;
+    command.nlocrel = 2;
+    let normal = |reloc: goblin::error::Result<Relocation>| match reloc.unwrap() {
+        Relocation::Normal(reloc) => reloc,
+        reloc => panic!("unexpected {:?}", reloc),
+    };
+    let external = command.external_relocations(&bytes, ctx).map(normal).collect::<Vec<_>>();
+    assert_eq!(external.len(), 1);
+    assert!(external[0].is_extern());
+    assert_eq!(external[0].r_symbolnum(), 5);
+    let local = command.local_relocations(&bytes, ctx).map(normal).collect::<Vec<_>>();
+    assert_eq!(local.iter().map(|reloc| reloc.r_address).collect::<Vec<_>>(), vec![0x20, 0x28]);
+    assert!(local.iter().all(|reloc| !reloc.is_extern()));
+    command.nlocrel = 3;
+    assert!(command.local_relocations(&bytes, ctx).nth(2).unwrap().is_err());
+}
+
+#[test]
+fn relocation_type_names() {
+    use relocation::*;
+    use cputype::*;
+    assert_eq!(reloc_type_to_str(CPU_TYPE_ARM64, ARM64_RELOC_ADDEND), "ARM64_RELOC_ADDEND");
+    assert_eq!(reloc_type_to_str(CPU_TYPE_ARM64, ARM64_RELOC_SUBTRACTOR), "ARM64_RELOC_SUBTRACTOR");
+    assert_eq!(reloc_type_to_str(CPU_TYPE_X86, GENERIC_RELOC_TLV), "GENERIC_RELOC_TLV");
+    assert_eq!(reloc_type_to_str(CPU_TYPE_ARM, ARM_RELOC_HALF_SECTDIFF), "ARM_RELOC_HALF_SECTDIFF");
+    assert_eq!(reloc_type_to_str(CPU_TYPE_POWERPC, PPC_RELOC_HA16), "PPC_RELOC_HA16");
+    assert_eq!(reloc_type_to_str(CPU_TYPE_POWERPC64, PPC_RELOC_LOCAL_SECTDIFF), "PPC_RELOC_LOCAL_SECTDIFF");
+    // the same r_type means different things on different cputypes
+    assert_eq!(reloc_type_to_str(CPU_TYPE_X86_64, 2), "X86_64_RELOC_BRANCH");
+    assert_eq!(reloc_type_to_str(CPU_TYPE_POWERPC, 2), "PPC_RELOC_BR14");
+    assert_eq!(reloc_type_to_str(CPU_TYPE_POWERPC, 16), "UNKNOWN");
+    assert_eq!(reloc_to_str(PPC_RELOC_JBSR, CPU_TYPE_POWERPC), "PPC_RELOC_JBSR");
+}
+
+#[test]
+fn scattered_relocations() {
+    use scroll::{LE, Pwrite};
+    use relocation::*;
+    let ctx = goblin::container::Ctx::new(goblin::container::Container::Little, LE);
+    let mut bytes = vec![0u8; 16];
+    // a GENERIC_RELOC_SECTDIFF of a long at 0x1c, and its GENERIC_RELOC_PAIR
+    bytes.pwrite_with(0xa200_001cu32, 0, LE).unwrap();
+    bytes.pwrite_with(0x1f40i32, 4, LE).unwrap();
+    bytes.pwrite_with(0xa100_0000u32, 8, LE).unwrap();
+    bytes.pwrite_with(0x1f00i32, 12, LE).unwrap();
+    let section = goblin::mach::segment::Section { reloff: 0, nreloc: 2, ..Default::default() };
+    let relocs = section.iter_relocations(&bytes, ctx).collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(relocs.iter().all(|reloc| reloc.is_scattered()));
+    match relocs[0] {
+        Relocation::Scattered(reloc) => {
+            assert_eq!(reloc.r_address(), 0x1c);
+            assert_eq!(reloc.r_type(), GENERIC_RELOC_SECTDIFF);
+            assert_eq!(reloc.r_length(), 2);
+            assert_eq!(reloc.is_pic(), false);
+            assert_eq!(reloc.r_value, 0x1f40);
+        },
+        reloc => panic!("unexpected {:?}", reloc),
+    }
+    assert_eq!(relocs[1].to_str(goblin::mach::cputype::CPU_TYPE_X86), "GENERIC_RELOC_PAIR");
+    let reloc = RelocationInfo { r_address: 0x1c, r_info: 0x0d00_0001 };
+    assert!(!reloc.is_scattered());
+}
+
+#[test]
+fn relocation_accessors() {
+    use scroll::{BE, LE, Pwrite};
+    use relocation::*;
+    let reloc = RelocationInfo { r_address: 0x8, r_info: 0x1d00_0007 };
+    assert_eq!(reloc.symbolnum(), 7);
+    assert!(reloc.is_pcrel());
+    assert_eq!(reloc.length(), 4);
+    assert!(reloc.is_extern());
+    assert_eq!(reloc.r_type(), X86_64_RELOC_SIGNED);
+    assert_eq!(reloc.to_str(goblin::mach::cputype::CPU_TYPE_X86_64), "X86_64_RELOC_SIGNED");
+    // the same relocation, as laid out by a big-endian compiler
+    let mut bytes = [0u8; 8];
+    bytes.pwrite_with(0x8i32, 0, BE).unwrap();
+    bytes.pwrite_with(0x0000_07d1u32, 4, BE).unwrap();
+    let ctx = goblin::container::Ctx::new(goblin::container::Container::Little, BE);
+    let section = goblin::mach::segment::Section { reloff: 0, nreloc: 1, ..Default::default() };
+    match section.iter_relocations(&bytes, ctx).next().unwrap().unwrap() {
+        Relocation::Normal(be) => {
+            assert_eq!(be.r_info, reloc.r_info);
+            assert_eq!(be.length(), 4);
+        },
+        reloc => panic!("unexpected {:?}", reloc),
+    }
+    let reloc = RelocationInfo { r_address: 0, r_info: 0x0600_0000 };
+    assert_eq!(reloc.length(), 8);
+    assert!(!reloc.is_pcrel());
+    let mut bytes = [0u8; 8];
+    bytes.pwrite_with(reloc, 0, LE).unwrap();
+    assert_eq!(bytes[7], 0x06);
+}
+
+#[test]
+fn segments_find() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let text = binary.segments.find("__TEXT").unwrap();
+    assert_eq!(text.nsects, 6);
+    assert!(binary.segments.find("__LINKEDIT").is_some());
+    assert!(binary.segments.find("__TEX").is_none());
+    assert!(binary.segments.find("__TEXT\0").is_none());
+    let (section, data) = binary.segments.find_section("__TEXT", "__text").unwrap().unwrap();
+    assert_eq!(section.segname().unwrap(), "__TEXT");
+    assert_eq!(data.len() as u64, section.size);
+    assert!(binary.segments.find_section("__DATA", "__text").unwrap().is_none());
+    assert!(binary.segments.find_section("__NOPE", "__text").unwrap().is_none());
+}
+
+#[test]
+fn vaddr_offset_translation() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let segments = &binary.segments;
+    let (text, _) = segments.find_section("__TEXT", "__text").unwrap().unwrap();
+    assert_eq!(segments.vaddr_to_offset(text.addr), Some(text.offset as u64));
+    assert_eq!(segments.offset_to_vaddr(text.offset as u64), Some(text.addr));
+    // __PAGEZERO has no file contents
+    let pagezero = segments.find("__PAGEZERO").unwrap();
+    assert_eq!(pagezero.filesize, 0);
+    assert_eq!(segments.vaddr_to_offset(pagezero.vmaddr), None);
+    assert_eq!(segments.offset_to_vaddr(0), Some(segments.find("__TEXT").unwrap().vmaddr));
+    let linkedit = segments.find("__LINKEDIT").unwrap();
+    assert_eq!(segments.vaddr_to_offset(linkedit.vmaddr + linkedit.filesize), None);
+    assert_eq!(segments.offset_to_vaddr(linkedit.fileoff + linkedit.filesize), None);
+    assert_eq!(segments.vaddr_to_offset(u64::max_value()), None);
+    // a segment which wraps around the end of the address space
+    let segment = segment_64("__TEXT", u64::max_value() - 0xf, 0x20, 0, 0x20, &[]);
+    let bytes = macho_64(cputype::CPU_TYPE_X86_64, header::MH_EXECUTE, &[segment]);
+    let binary = MachO::parse(&bytes, 0).unwrap();
+    assert_eq!(binary.segments.offset_to_vaddr(0xf), Some(u64::max_value()));
+    assert_eq!(binary.segments.offset_to_vaddr(0x10), None);
+}
+
+#[test]
+fn data_at_vaddr() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let segments = &binary.segments;
+    let (text, data) = segments.find_section("__TEXT", "__text").unwrap().unwrap();
+    assert_eq!(segments.data_at_vaddr(text.addr, data.len()), Some(data));
+    assert_eq!(segments.data_at_vaddr(text.addr + 1, 2), Some(&data[1..3]));
+    let segment = segments.find("__TEXT").unwrap();
+    let end = segment.vmaddr + segment.filesize;
+    assert_eq!(segments.data_at_vaddr(end - 4, 4).map(|data| data.len()), Some(4));
+    // refuses to cross into the next segment
+    assert_eq!(segments.data_at_vaddr(end - 4, 5), None);
+    assert_eq!(segments.data_at_vaddr(segments.find("__PAGEZERO").unwrap().vmaddr, 1), None);
+    assert_eq!(segments.data_at_vaddr(text.addr, usize::max_value()), None);
+}
+
+#[test]
+fn zerofill_sections() {
+    use scroll::{Pread, Pwrite, LE};
+    use load_command::*;
+    let ctx = le_64();
+    let mut bytes = vec![0u8; SIZEOF_SEGMENT_COMMAND_64 + 3 * SIZEOF_SECTION_64];
+    let mut command = bytes.pread_with::<SegmentCommand64>(0, LE).unwrap();
+    command.cmd = LC_SEGMENT_64;
+    command.nsects = 3;
+    let sections = [
+        (&b"__bss"[..], constants::S_ZEROFILL),
+        (&b"__thread_bss"[..], constants::S_THREAD_LOCAL_ZEROFILL),
+        (&b"__common"[..], constants::S_GB_ZEROFILL),
+    ];
+    for (i, &(name, flags)) in sections.iter().enumerate() {
+        let mut section = [0u8; SIZEOF_SECTION_64].pread_with::<Section64>(0, LE).unwrap();
+        section.sectname[..name.len()].copy_from_slice(name);
+        // far larger than the binary
+        section.size = 0x10_0000;
+        section.flags = flags;
+        bytes.pwrite_with(section, SIZEOF_SEGMENT_COMMAND_64 + i * SIZEOF_SECTION_64, LE).unwrap();
+    }
+    let segment = segment::Segment::from_64(&bytes, &command, 0, ctx).unwrap();
+    let sections = segment.sections().unwrap();
+    assert_eq!(sections.len(), 3);
+    assert_eq!(sections[0].0.name().unwrap(), "__bss");
+    for &(ref section, data) in &sections {
+        assert!(section.is_zerofill());
+        assert_eq!(section.size, 0x10_0000);
+        assert!(data.is_empty());
+    }
+}
+
+#[test]
+fn macho_function_starts() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let (text, _) = binary.segments.find_section("__TEXT", "__text").unwrap().unwrap();
+    let starts = binary.function_starts().unwrap();
+    assert!(!starts.is_empty());
+    assert_eq!(starts[0].address, text.addr);
+    for start in &starts {
+        assert_eq!(start.containing_section, Some("__TEXT,__text".to_string()));
+    }
+    assert_eq!(Mach::Binary(binary).function_starts().unwrap(), starts);
+}
+
+#[test]
+fn nlist_type() {
+    use goblin::mach::symbols::*;
+    let nlist = |n_type, n_sect| nlist(n_type, n_sect, 0, 0);
+    let symbol = nlist(N_SECT | N_EXT, 1);
+    assert_eq!(symbol.type_(), SymbolType::Section);
+    assert!(symbol.is_external());
+    assert!(!symbol.is_private_external());
+    assert!(!symbol.is_stab());
+    assert_eq!(symbol.section_index(), Some(1));
+    let symbol = nlist(N_UNDF | N_EXT, 0);
+    assert_eq!(symbol.type_(), SymbolType::Undefined);
+    assert_eq!(symbol.section_index(), None);
+    let symbol = nlist(N_SECT | N_PEXT, 3);
+    assert!(!symbol.is_external());
+    assert!(symbol.is_private_external());
+    assert_eq!(nlist(N_ABS, 0).type_(), SymbolType::Absolute);
+    assert_eq!(nlist(N_INDR | N_EXT, 0).type_(), SymbolType::Indirect);
+    assert_eq!(nlist(N_PBUD, 0).type_(), SymbolType::PreboundUndefined);
+    assert_eq!(nlist(0x6, 0).type_(), SymbolType::Unknown(0x6));
+    // N_FUN; its type bits must not be taken for N_SECT
+    let symbol = nlist(0x24, 1);
+    assert!(symbol.is_stab());
+    assert_eq!(symbol.type_(), SymbolType::Stab(0x24));
+    assert_eq!(symbol.section_index(), None);
+}
+
+#[test]
+fn nlist_desc_flags() {
+    use goblin::mach::symbols::*;
+    let nlist = |n_type, n_sect, n_desc| nlist(n_type, n_sect, n_desc, 0);
+    // a lazily bound weak import from the second library
+    let symbol = nlist(N_UNDF | N_EXT, 0, 0x0200 | N_WEAK_REF | N_REF_TO_WEAK | REFERENCE_FLAG_UNDEFINED_LAZY);
+    assert_eq!(symbol.reference_type(), REFERENCE_FLAG_UNDEFINED_LAZY);
+    assert!(symbol.is_weak_ref());
+    assert!(!symbol.is_weak_def());
+    assert_eq!(symbol.library_ordinal(), 2);
+    // the same bits mean something else for a defined symbol
+    let symbol = nlist(N_SECT | N_EXT, 1, N_WEAK_DEF | N_ARM_THUMB_DEF);
+    assert!(symbol.is_weak_def());
+    assert!(symbol.is_thumb());
+    assert!(!symbol.is_weak_ref());
+    assert_eq!(symbol.reference_type(), 0);
+    assert!(!nlist(N_UNDF, 0, N_ARM_THUMB_DEF).is_thumb());
+}
+
+#[test]
+fn stab_kinds() {
+    use goblin::mach::symbols::*;
+    use goblin::mach::stab;
+    let nlist = |n_type| nlist(n_type, 0, 0, 0);
+    assert_eq!(nlist(stab::N_OSO).stab(), Some(stab::Kind::Oso));
+    assert_eq!(nlist(stab::N_SO).stab(), Some(stab::Kind::So));
+    assert_eq!(nlist(stab::N_BNSYM).stab(), Some(stab::Kind::Bnsym));
+    assert_eq!(nlist(0xf0).stab(), Some(stab::Kind::Unknown(0xf0)));
+    assert_eq!(nlist(N_SECT | N_EXT).stab(), None);
+    assert_eq!(stab::stab_to_str(stab::N_FUN), "N_FUN");
+    assert_eq!(stab::stab_to_str(0xf0), "UNKNOWN_STAB");
+}
+
+#[test]
+fn library_ordinal_names() {
+    use goblin::mach::symbols::*;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert_eq!(binary.libs.len(), 2);
+    assert_eq!(binary.library_ordinal_name(SELF_LIBRARY_ORDINAL), Some("self"));
+    assert_eq!(binary.library_ordinal_name(1), Some(binary.libs[1]));
+    assert_eq!(binary.library_ordinal_name(2), None);
+    assert_eq!(binary.library_ordinal_name(DYNAMIC_LOOKUP_ORDINAL), Some("flat-namespace"));
+    assert_eq!(binary.library_ordinal_name(EXECUTABLE_ORDINAL), Some("main-executable"));
+    assert_eq!(binary.library_ordinal_name(0x100), None);
+    let undefined = binary.symbols().map(|symbol| symbol.unwrap().1).find(|nlist| nlist.is_undefined()).unwrap();
+    assert_eq!(undefined.library_ordinal(), 1);
+    let libsystem = binary.libs[1];
+    assert_eq!(Mach::Binary(binary).library_ordinal_name(1), Some(libsystem));
+}
+
+#[test]
+fn imports_special_ordinals_and_weak() {
+    use goblin::mach::bind_opcodes::*;
+    use load_command::*;
+    let ctx = le_64();
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    // "_a" from the main executable, "_b" by flat lookup, then a weak bind of "_c"
+    let bytes = [BIND_OPCODE_SET_DYLIB_SPECIAL_IMM | BIND_SPECIAL_DYLIB_MAIN_EXECUTABLE, BIND_OPCODE_SET_SYMBOL_TRAILING_FLAGS_IMM, b'_', b'a', 0,
+                 BIND_OPCODE_SET_TYPE_IMM | BIND_TYPE_POINTER, BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 2, 0x10, BIND_OPCODE_DO_BIND,
+                 BIND_OPCODE_SET_DYLIB_SPECIAL_IMM | BIND_SPECIAL_DYLIB_FLAT_LOOKUP, BIND_OPCODE_SET_SYMBOL_TRAILING_FLAGS_IMM, b'_', b'b', 0,
+                 BIND_OPCODE_DO_BIND, BIND_OPCODE_DONE,
+                 BIND_OPCODE_SET_SYMBOL_TRAILING_FLAGS_IMM, b'_', b'c', 0, BIND_OPCODE_SET_TYPE_IMM | BIND_TYPE_POINTER,
+                 BIND_OPCODE_SET_SEGMENT_AND_OFFSET_ULEB | 2, 0x20, BIND_OPCODE_DO_BIND, BIND_OPCODE_DONE];
+    let mut command = DyldInfoCommand::default();
+    command.bind_size = 16;
+    command.weak_bind_off = 16;
+    command.weak_bind_size = bytes.len() as u32 - 16;
+    let interpreter = imports::BindInterpreter::new(&bytes, &command);
+    let imports = interpreter.imports(&binary.libs, &binary.segments, &ctx).unwrap();
+    let summary = imports.iter().map(|import| (import.name, import.dylib, import.is_weak)).collect::<Vec<_>>();
+    assert_eq!(summary, [("_a", "main-executable", false), ("_b", "flat-namespace", false), ("_c", "flat-namespace", true)]);
+    assert_eq!(imports[2].address, binary.segments[2].vmaddr + 0x20);
+}
+
+#[test]
+fn imports_without_dyld_info() {
+    let bytes = macho_64(cputype::CPU_TYPE_X86_64, header::MH_EXECUTE, &[]);
+    let binary = MachO::parse(&bytes, 0).unwrap();
+    assert!(binary.imports().unwrap().is_empty());
+    assert!(binary.exports().unwrap().is_empty());
+}
+
+#[test]
+fn export_addresses_and_sizes() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let text = binary.segments.find("__TEXT").unwrap();
+    let (text_section, _) = binary.segments.find_section("__TEXT", "__text").unwrap().unwrap();
+    let exports = binary.exports().unwrap();
+    let main = exports.iter().find(|export| export.name == "_main").unwrap();
+    assert_eq!(main.address, text.vmaddr + main.offset);
+    assert_eq!(main.flags, goblin::mach::exports::EXPORT_SYMBOL_FLAGS_KIND_REGULAR);
+    assert_eq!(main.kind, goblin::mach::exports::SymbolKind::Regular);
+    assert!(main.reexport().is_none());
+    assert!(main.size > 0);
+    assert!(main.address + main.size as u64 <= text_section.addr + text_section.size);
+    let mach = Mach::parse(&DEADBEEF_MACH_64[..]).unwrap();
+    let names = mach.exports().unwrap().into_iter().map(|export| (export.name, export.address)).collect::<Vec<_>>();
+    assert_eq!(names, exports.iter().map(|export| (export.name.clone(), export.address)).collect::<Vec<_>>());
+}
+
+#[test]
+fn export_in_section_past_address_space() {
+    use load_command::*;
+    use goblin::mach::constants::S_ZEROFILL;
+    // _main at 0x10 into a __TEXT whose zerofill __text section runs past the end of the address space
+    let vmaddr = 0xffff_ffff_ffff_f000;
+    let text = segment_64("__TEXT", vmaddr, 0x1000, 0, 0, &[("__text", vmaddr, 0x2000, 0, S_ZEROFILL)]);
+    let trie = [0x00, 0x01, b'_', b'm', b'a', b'i', b'n', 0x00, 0x09, 0x02, 0x00, 0x10, 0x00];
+    let dataoff = goblin::mach::header::SIZEOF_HEADER_64 + text.len() + SIZEOF_LINKEDIT_DATA_COMMAND;
+    let trie_command = command(LinkeditDataCommand { cmd: LC_DYLD_EXPORTS_TRIE, cmdsize: SIZEOF_LINKEDIT_DATA_COMMAND as u32, dataoff: dataoff as u32, datasize: trie.len() as u32 }, SIZEOF_LINKEDIT_DATA_COMMAND);
+    let mut bytes = macho_64(cputype::CPU_TYPE_ARM64, goblin::mach::header::MH_EXECUTE, &[text, trie_command]);
+    bytes.extend_from_slice(&trie);
+    let binary = MachO::parse(&bytes, 0).unwrap();
+    let exports = binary.exports().unwrap();
+    assert_eq!(exports.len(), 1);
+    assert_eq!(exports[0].address, vmaddr + 0x10);
+    assert_eq!(exports[0].size, 0);
+}
+
+#[test]
+fn export_reexport_target() {
+    use goblin::mach::exports::*;
+    let export = Export::new("_foo".to_string(), ExportInfo::Reexport { lib: "libbar.dylib", lib_symbol_name: None, flags: EXPORT_SYMBOL_FLAGS_REEXPORT });
+    assert_eq!(export.reexport(), Some(("libbar.dylib", "_foo")));
+    let export = Export::new("_foo".to_string(), ExportInfo::Reexport { lib: "libbar.dylib", lib_symbol_name: Some("_bar"), flags: EXPORT_SYMBOL_FLAGS_REEXPORT });
+    assert_eq!(export.reexport(), Some(("libbar.dylib", "_bar")));
+    assert_eq!(export.flags, EXPORT_SYMBOL_FLAGS_REEXPORT);
+    assert_eq!(export.kind, SymbolKind::Regular);
+    let export = Export::new("_tlv".to_string(), ExportInfo::Regular { address: 0x10, flags: EXPORT_SYMBOL_FLAGS_KIND_THREAD_LOCAL });
+    assert_eq!(export.kind, SymbolKind::ThreadLocal);
+    assert!(!export.is_absolute());
+    let export = Export::new("_abs".to_string(), ExportInfo::Regular { address: 0x10, flags: EXPORT_SYMBOL_FLAGS_KIND_ABSOLUTE });
+    assert_eq!(export.kind, SymbolKind::Absolute);
+    assert!(export.is_absolute());
+}
+
+#[test]
+fn undersized_load_commands() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; SIZEOF_SEGMENT_COMMAND_64 + SIZEOF_UUID_COMMAND];
+    for &(cmd, cmdsize) in &[(LC_SEGMENT_64, 0), (LC_SEGMENT_64, 4), (LC_SEGMENT_64, 8), (LC_SEGMENT_64, 0x40), (LC_UUID, 0), (0x1234, 0)] {
+        bytes.pwrite_with(LoadCommandHeader { cmd: cmd, cmdsize: cmdsize }, 0, LE).unwrap();
+        match LoadCommand::parse(&bytes, &mut 0, LE) {
+            Err(goblin::error::Error::Malformed(_)) => (),
+            result => panic!("{:#x} with cmdsize {} parsed as {:?}", cmd, cmdsize, result),
+        }
+    }
+    // unknown commands only need their header
+    bytes.pwrite_with(LoadCommandHeader { cmd: 0x1234, cmdsize: 8 }, 0, LE).unwrap();
+    let mut offset = 0;
+    LoadCommand::parse(&bytes, &mut offset, LE).unwrap();
+    assert_eq!(offset, 8);
+    // a binary whose only command has a zero cmdsize is an error, rather than a loop on the same command
+    let bytes = macho_64(cputype::CPU_TYPE_X86_64, header::MH_EXECUTE, &[command(LoadCommandHeader { cmd: LC_UUID, cmdsize: 0 }, SIZEOF_LOAD_COMMAND)]);
+    assert!(MachO::parse(&bytes, 0).is_err());
+}
+
+#[test]
+fn load_commands_bounded_by_sizeofcmds() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    use goblin::mach::header::*;
+    let binary = |ncmds: usize, sizeofcmds: u32, cmdsize| {
+        let uuid = command(LoadCommandHeader { cmd: LC_UUID, cmdsize: cmdsize }, SIZEOF_UUID_COMMAND);
+        let mut bytes = macho_64(cputype::CPU_TYPE_X86_64, MH_EXECUTE, &[uuid.clone(), uuid]);
+        // overwrite the header's ncmds and sizeofcmds
+        bytes.pwrite_with(ncmds as u32, 16, LE).unwrap();
+        bytes.pwrite_with(sizeofcmds, 20, LE).unwrap();
+        bytes
+    };
+    let size = SIZEOF_UUID_COMMAND as u32;
+    assert_eq!(MachO::parse(&binary(2, 2 * size, size), 0).unwrap().load_commands.len(), 2);
+    // more commands than fit in sizeofcmds
+    assert!(MachO::parse(&binary(2, size, size), 0).is_err());
+    // a command which extends past sizeofcmds
+    assert!(MachO::parse(&binary(1, size, size + 8), 0).is_err());
+    // sizeofcmds past the end of the binary
+    assert!(MachO::parse(&binary(1, 0x1000, size), 0).is_err());
+    // a huge ncmds
+    assert!(MachO::parse(&binary(usize::max_value(), 2 * size, size), 0).is_err());
+}
+
+#[test]
+fn note_and_fileset_entry_commands() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; SIZEOF_NOTE_COMMAND];
+    let mut data_owner = [0u8; 16];
+    data_owner[..9].copy_from_slice(b"addrable\0");
+    bytes.pwrite_with(NoteCommand { cmd: LC_NOTE, cmdsize: SIZEOF_NOTE_COMMAND as u32, data_owner: data_owner, offset: 0x4000, size: 0x20 }, 0, LE).unwrap();
+    let command = LoadCommand::parse(&bytes, &mut 0, LE).unwrap();
+    assert_eq!(cmd_to_str(command.command.cmd()), "LC_NOTE");
+    match command.command {
+        CommandVariant::Note(note) => {
+            assert_eq!(note.data_owner().unwrap(), "addrable");
+            assert_eq!(note.offset, 0x4000);
+            assert_eq!(note.size, 0x20);
+        },
+        command => panic!("wrong command: {:?}", command),
+    }
+
+    let mut bytes = vec![0u8; 48];
+    bytes.pwrite_with(FilesetEntryCommand { cmd: LC_FILESET_ENTRY, cmdsize: 48, vmaddr: 0xffff_fe00_0700_4000, fileoff: 0x8000, entry_id: 32, reserved: 0 }, 0, LE).unwrap();
+    bytes.pwrite("com.apple.iokit", 32).unwrap();
+    let command = LoadCommand::parse(&bytes, &mut 0, LE).unwrap();
+    assert_eq!(cmd_to_str(command.command.cmd()), "LC_FILESET_ENTRY");
+    match command.command {
+        CommandVariant::FilesetEntry(entry) => {
+            assert_eq!(entry.entry_id(&bytes).unwrap(), "com.apple.iokit");
+            assert_eq!(entry.vmaddr, 0xffff_fe00_0700_4000);
+            assert_eq!(entry.fileoff, 0x8000);
+        },
+        command => panic!("wrong command: {:?}", command),
+    }
+}
+
+#[test]
+fn fileset_entries() {
+    use scroll::Pwrite;
+    use load_command::*;
+    use goblin::mach::header::*;
+    let inner = 0x100;
+    let mut entry = command(FilesetEntryCommand { cmd: LC_FILESET_ENTRY, cmdsize: 48, vmaddr: 0xffff_fe00_0700_4000, fileoff: inner as u64, entry_id: 32, reserved: 0 }, 48);
+    entry.pwrite("com.apple.kernel", 32).unwrap();
+    let mut bytes = macho_64(cputype::CPU_TYPE_ARM64, MH_FILESET, &[entry]);
+    bytes.resize(inner, 0);
+    let uuid = command(LoadCommandHeader { cmd: LC_UUID, cmdsize: SIZEOF_UUID_COMMAND as u32 }, SIZEOF_UUID_COMMAND);
+    bytes.extend_from_slice(&macho_64(cputype::CPU_TYPE_ARM64, MH_EXECUTE, &[uuid]));
+
+    let mach = Mach::parse(&bytes).unwrap();
+    assert_eq!(mach.fileset_entries(), vec![("com.apple.kernel".to_string(), 0xffff_fe00_0700_4000, inner as u64)]);
+    match mach.fileset_entry_macho("com.apple.kernel").unwrap() {
+        Some(Mach::Binary(kernel)) => {
+            assert_eq!(kernel.header.filetype, MH_EXECUTE);
+            assert_eq!(kernel.load_commands.len(), 1);
+            assert_eq!(kernel.load_commands[0].offset, inner + SIZEOF_HEADER_64);
+        },
+        _ => panic!("no kernel entry"),
+    }
+    assert!(mach.fileset_entry_macho("com.apple.iokit").unwrap().is_none());
+}
+
+#[test]
+fn chained_fixups_header_and_imports() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    use chained_fixups::*;
+    let mut bytes = vec![0u8; 0x60];
+    let base = 0x10;
+    let header = DyldChainedFixupsHeader { fixups_version: 0, starts_offset: 0, imports_offset: 28, symbols_offset: 40, imports_count: 3, imports_format: DYLD_CHAINED_IMPORT, symbols_format: 0 };
+    bytes.pwrite_with(header, base, LE).unwrap();
+    // _malloc from libSystem, a weak _foo from flat-namespace, and _bar from the main executable
+    bytes.pwrite_with(1u32 | (1 << 9), base + 28, LE).unwrap();
+    bytes.pwrite_with(0xfeu32 | 0x100 | (9 << 9), base + 32, LE).unwrap();
+    bytes.pwrite_with(0xffu32 | (14 << 9), base + 36, LE).unwrap();
+    bytes.pwrite("\0_malloc\0_foo\0_bar\0", base + 40).unwrap();
+    let command = LinkeditDataCommand { cmd: LC_DYLD_CHAINED_FIXUPS, cmdsize: 16, dataoff: base as u32, datasize: 0x50 };
+    let fixups = command.chained_fixups(&bytes, LE).unwrap();
+    assert_eq!(fixups.header.imports_count, 3);
+    let imports = fixups.import_table().unwrap();
+    assert_eq!(imports[0], ChainedImport { lib_ordinal: 1, weak_import: false, name_offset: 1, addend: 0 });
+    assert_eq!(imports[1], ChainedImport { lib_ordinal: -2, weak_import: true, name_offset: 9, addend: 0 });
+    assert_eq!(imports[2].lib_ordinal, -1);
+    let names = imports.iter().map(|import| fixups.symbol_name(import.name_offset).unwrap()).collect::<Vec<_>>();
+    assert_eq!(names, ["_malloc", "_foo", "_bar"]);
+    assert!(fixups.import(3).is_err());
+
+    // the 64-bit format with addends
+    let mut bytes = vec![0u8; 0x40];
+    let header = DyldChainedFixupsHeader { fixups_version: 0, starts_offset: 0, imports_offset: 28, symbols_offset: 44, imports_count: 1, imports_format: DYLD_CHAINED_IMPORT_ADDEND64, symbols_format: 0 };
+    bytes.pwrite_with(header, 0, LE).unwrap();
+    bytes.pwrite_with(0xfffdu64 | (1 << 32), 28, LE).unwrap();
+    bytes.pwrite_with(-8i64, 36, LE).unwrap();
+    bytes.pwrite("\0_weak\0", 44).unwrap();
+    let command = LinkeditDataCommand { cmd: LC_DYLD_CHAINED_FIXUPS, cmdsize: 16, dataoff: 0, datasize: 0x40 };
+    let fixups = command.chained_fixups(&bytes, LE).unwrap();
+    let import = fixups.import(0).unwrap();
+    assert_eq!(import, ChainedImport { lib_ordinal: -3, weak_import: false, name_offset: 1, addend: -8 });
+    assert_eq!(fixups.symbol_name(import.name_offset).unwrap(), "_weak");
+    // ordinals below the special ones are libraries, however high
+    let import = |format, import: u64| {
+        let mut bytes = [0u8; 16];
+        bytes.pwrite_with(import, 0, LE).unwrap();
+        ChainedImport::parse(&bytes, 0, format, LE).unwrap().lib_ordinal
+    };
+    assert_eq!(import(DYLD_CHAINED_IMPORT, 0x80), 0x80);
+    assert_eq!(import(DYLD_CHAINED_IMPORT, 0xf0), 0xf0);
+    assert_eq!(import(DYLD_CHAINED_IMPORT, 0xf1), -15);
+    assert_eq!(import(DYLD_CHAINED_IMPORT_ADDEND64, 0x8000), 0x8000);
+    assert_eq!(import(DYLD_CHAINED_IMPORT_ADDEND64, 0xfff0), 0xfff0);
+    assert_eq!(import(DYLD_CHAINED_IMPORT_ADDEND64, 0xfff1), -15);
+
+    // out of bounds, and unknown versions
+    let command = LinkeditDataCommand { cmd: LC_DYLD_CHAINED_FIXUPS, cmdsize: 16, dataoff: 0x30, datasize: 0x40 };
+    assert!(command.chained_fixups(&bytes, LE).is_err());
+    bytes.pwrite_with(1u32, 0, LE).unwrap();
+    let command = LinkeditDataCommand { cmd: LC_DYLD_CHAINED_FIXUPS, cmdsize: 16, dataoff: 0, datasize: 0x40 };
+    assert!(command.chained_fixups(&bytes, LE).is_err());
+}
+
+#[test]
+fn dyld_exports_trie() {
+    use load_command::*;
+    use goblin::mach::header::*;
+    let trie_offset = SIZEOF_HEADER_64 + SIZEOF_LINKEDIT_DATA_COMMAND;
+    let trie = [0x00, 0x01, b'_', b'm', b'a', b'i', b'n', 0x00, 0x09, 0x02, 0x00, 0x10, 0x00];
+    let command = command(LinkeditDataCommand { cmd: LC_DYLD_EXPORTS_TRIE, cmdsize: SIZEOF_LINKEDIT_DATA_COMMAND as u32, dataoff: trie_offset as u32, datasize: trie.len() as u32 }, SIZEOF_LINKEDIT_DATA_COMMAND);
+    let mut bytes = macho_64(cputype::CPU_TYPE_ARM64, MH_EXECUTE, &[command]);
+    bytes.extend_from_slice(&trie);
+    let macho = MachO::parse(&bytes, 0).unwrap();
+    assert_eq!(load_command::cmd_to_str(macho.load_commands[0].command.cmd()), "LC_DYLD_EXPORTS_TRIE");
+    let exports = macho.exports().unwrap();
+    assert_eq!(exports.len(), 1);
+    assert_eq!(exports[0].name, "_main");
+    assert_eq!(exports[0].offset, 0x10);
+    assert!(macho.chained_fixups().unwrap().is_none());
+}
+
+#[test]
+fn chained_fixups_rebases_and_imports() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    use chained_fixups::*;
+    use goblin::mach::header::*;
+    let binary = |pointer_format: u16, pointers: [u64; 2]| {
+        let mut bytes = macho_64(cputype::CPU_TYPE_ARM64, MH_EXECUTE, &[
+            segment_64("__TEXT", 0x1_0000_0000, 0x4000, 0, 0x100, &[]),
+            segment_64("__DATA", 0x1_0000_4000, 0x4000, 0x100, 0x20, &[]),
+            command(LinkeditDataCommand { cmd: LC_DYLD_CHAINED_FIXUPS, cmdsize: SIZEOF_LINKEDIT_DATA_COMMAND as u32, dataoff: 0x120, datasize: 80 }, SIZEOF_LINKEDIT_DATA_COMMAND),
+        ]);
+        bytes.resize(0x170, 0);
+        bytes.pwrite_with(pointers[0], 0x100, LE).unwrap();
+        bytes.pwrite_with(pointers[1], 0x108, LE).unwrap();
+        let mut offset = 0x120;
+        bytes.gwrite_with(DyldChainedFixupsHeader { fixups_version: 0, starts_offset: 28, imports_offset: 64, symbols_offset: 68, imports_count: 1, imports_format: DYLD_CHAINED_IMPORT, symbols_format: 0 }, &mut offset, LE).unwrap();
+        // the starts in image: no fixups in __TEXT, and __DATA's 12 bytes in
+        bytes.gwrite_with(2u32, &mut offset, LE).unwrap();
+        bytes.gwrite_with(0u32, &mut offset, LE).unwrap();
+        bytes.gwrite_with(12u32, &mut offset, LE).unwrap();
+        bytes.gwrite_with(DyldChainedStartsInSegment { size: 24, page_size: 0x4000, pointer_format: pointer_format, segment_offset: 0x4000, max_valid_pointer: 0, page_count: 1 }, &mut offset, LE).unwrap();
+        bytes.gwrite_with(0u16, &mut offset, LE).unwrap();
+        bytes.gwrite_with(1u32 | (1 << 9), &mut offset, LE).unwrap();
+        bytes.gwrite("\0_printf\0", &mut offset).unwrap();
+        bytes
+    };
+
+    // a rebase to __TEXT + 0x3f00 followed two strides later by a bind of _printf + 4
+    let bytes = binary(DYLD_CHAINED_PTR_64_OFFSET, [0x3f00 | (2 << 51), (1 << 63) | (4 << 24)]);
+    let macho = MachO::parse(&bytes, 0).unwrap();
+    let fixups = macho.chained_fixups().unwrap().unwrap();
+    let (starts, page_starts) = fixups.starts_in_segment(1).unwrap().unwrap();
+    assert_eq!(starts.pointer_format, DYLD_CHAINED_PTR_64_OFFSET);
+    assert_eq!(page_starts, [0]);
+    assert!(fixups.starts_in_segment(0).unwrap().is_none());
+    assert_eq!(fixups.rebases(macho.segments.as_slice()).unwrap(), [ChainedRebase { segment_index: 1, segment_offset: 0, address: 0x1_0000_4000, target: 0x1_0000_3f00, is_auth: false }]);
+    assert_eq!(fixups.imports(macho.segments.as_slice()).unwrap(), [ChainedBind { segment_index: 1, segment_offset: 8, address: 0x1_0000_4008, name: "_printf", library_ordinal: 1, addend: 4, weak: false, is_auth: false }]);
+
+    // an authenticated rebase and a bind with a negative addend, one stride apart
+    let bytes = binary(DYLD_CHAINED_PTR_ARM64E, [(1 << 63) | 0x3f00 | (1 << 51), (1 << 62) | (((-4i64 as u64) & 0x7_ffff) << 32)]);
+    let macho = MachO::parse(&bytes, 0).unwrap();
+    let fixups = macho.chained_fixups().unwrap().unwrap();
+    let rebases = fixups.rebases(macho.segments.as_slice()).unwrap();
+    assert_eq!(rebases.len(), 1);
+    assert_eq!(rebases[0].target, 0x1_0000_3f00);
+    assert!(rebases[0].is_auth);
+    let imports = fixups.imports(macho.segments.as_slice()).unwrap();
+    assert_eq!(imports.len(), 1);
+    assert_eq!(imports[0].addend, -4);
+    assert_eq!(imports[0].address, 0x1_0000_4008);
+
+    // a chain which runs off the end of the segment
+    let bytes = binary(DYLD_CHAINED_PTR_64, [0x3f00 | (2 << 51), (1 << 63) | (0x10 << 51)]);
+    let macho = MachO::parse(&bytes, 0).unwrap();
+    assert!(macho.chained_fixups().unwrap().unwrap().rebases(macho.segments.as_slice()).is_err());
+
+    // a second pointer whose address overflows, as __DATA is at the end of the address space
+    let at_end = |pointers| {
+        let mut bytes = binary(DYLD_CHAINED_PTR_64_OFFSET, pointers);
+        bytes.pwrite_with(u64::max_value() - 4, SIZEOF_HEADER_64 + SIZEOF_SEGMENT_COMMAND_64 + 24, LE).unwrap();
+        bytes
+    };
+    let bytes = at_end([0x3f00 | (2 << 51), 0x3f00]);
+    let macho = MachO::parse(&bytes, 0).unwrap();
+    assert!(macho.chained_fixups().unwrap().unwrap().rebases(macho.segments.as_slice()).is_err());
+    let bytes = at_end([0x3f00 | (2 << 51), (1 << 63) | (4 << 24)]);
+    let macho = MachO::parse(&bytes, 0).unwrap();
+    assert!(macho.chained_fixups().unwrap().unwrap().imports(macho.segments.as_slice()).is_err());
+}
+
+#[test]
+fn file_types() {
+    use goblin::mach::header::*;
+    let mach = Mach::parse(&DEADBEEF_MACH_64[..]).unwrap();
+    assert_eq!(mach.file_type(), Some(FileType::Execute));
+    assert!(mach.is_executable());
+    assert!(!mach.is_library());
+    assert!(!mach.is_object());
+    match mach {
+        Mach::Binary(ref macho) => {
+            assert_eq!(macho.file_type(), FileType::Execute);
+            assert!(macho.is_executable());
+            assert!(!macho.is_object_file());
+        },
+        _ => panic!("not a binary"),
+    }
+    assert_eq!(FileType::from(MH_DYLIB), FileType::Dylib);
+    assert!(FileType::Dylib.is_library());
+    assert!(FileType::DylibStub.is_library());
+    assert!(FileType::Object.is_object());
+    assert_eq!(FileType::from(0x42), FileType::Unknown(0x42));
+    assert_eq!(u32::from(FileType::KextBundle), MH_KEXT_BUNDLE);
+    assert_eq!(format!("{}", FileType::Bundle), "BUNDLE");
+    assert_eq!(constants::filetype_to_str(MH_DSYM), "DSYM");
+}
+
+#[test]
+fn header_flags() {
+    use goblin::mach::header::*;
+    let macho = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert_eq!(macho.header.flags, MH_NOUNDEFS | MH_DYLDLINK | MH_TWOLEVEL | MH_PIE);
+    assert!(macho.header.is_pie());
+    assert!(macho.header.is_two_level());
+    assert!(!macho.header.allows_stack_execution());
+    assert!(!macho.header.has_no_heap_execution());
+    assert!(!macho.header.has_weak_defines());
+    assert!(!macho.header.binds_to_weak());
+    assert_eq!(macho.header.flags_to_strings(), ["MH_NOUNDEFS", "MH_DYLDLINK", "MH_TWOLEVEL", "MH_PIE"]);
+    let header = Header { flags: MH_ALLOW_STACK_EXECUTION | MH_PREBINDABLE | 0x10000000, ..Default::default() };
+    assert!(header.allows_stack_execution());
+    assert!(header.has_flag(MH_ALLOW_STACK_EXECUTION | MH_PREBINDABLE));
+    assert!(!header.has_flag(MH_ALLOW_STACK_EXECUTION | MH_PIE));
+    assert_eq!(header.flags_to_strings(), ["MH_PREBINDABLE", "MH_ALLOW_STACK_EXECUTION", "UNKNOWN FLAG"]);
+    assert!(MH_FLAGS.iter().all(|&flag| flag_to_str(flag) != "UNKNOWN FLAG"));
+}
+
+#[test]
+fn prebound_dylib_linked_modules() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; 40];
+    bytes.pwrite_with(PreboundDylibCommand { cmd: LC_PREBOUND_DYLIB, cmdsize: 40, name: 20, nmodules: 10, linked_modules: 36 }, 0, LE).unwrap();
+    bytes.pwrite("/usr/lib/libfoo", 20).unwrap();
+    // modules 0, 3 and 9
+    bytes[36] = 0b0000_1001;
+    bytes[37] = 0b0000_0010;
+    let command = LoadCommand::parse(&bytes, &mut 0, LE).unwrap();
+    match command.command {
+        CommandVariant::PreboundDylib(mut prebound) => {
+            assert_eq!(prebound.name(&bytes).unwrap(), "/usr/lib/libfoo");
+            assert_eq!(prebound.linked_modules(&bytes).unwrap(), [0b0000_1001, 0b0000_0010]);
+            let linked = (0..12).filter(|&n| prebound.is_module_linked(&bytes, n)).collect::<Vec<_>>();
+            assert_eq!(linked, [0, 3, 9]);
+            // a bit vector which extends past the command
+            prebound.nmodules = 33;
+            assert!(prebound.linked_modules(&bytes).is_err());
+            assert!(!prebound.is_module_linked(&bytes, 0));
+        },
+        command => panic!("wrong command: {:?}", command),
+    }
+}
+
+#[test]
+fn leb128() {
+    use goblin::mach::leb::*;
+    let uleb = |bytes: &[u8]| {
+        let mut offset = 0;
+        read_uleb128(bytes, &mut offset).map(|value| (value, offset))
+    };
+    let sleb = |bytes: &[u8]| {
+        let mut offset = 0;
+        read_sleb128(bytes, &mut offset).map(|value| (value, offset))
+    };
+    assert_eq!(uleb(&[0x00]).unwrap(), (0, 1));
+    assert_eq!(uleb(&[0x7f]).unwrap(), (0x7f, 1));
+    assert_eq!(uleb(&[0x80, 0x01]).unwrap(), (0x80, 2));
+    assert_eq!(uleb(&[0xe5, 0x8e, 0x26, 0xff]).unwrap(), (624485, 3));
+    assert_eq!(uleb(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]).unwrap(), (u64::max_value(), 10));
+    // padded, but still only 10 bytes
+    assert_eq!(uleb(&[0x81, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]).unwrap(), (1, 10));
+    // overflowing into the 65th bit, more than 10 bytes, and past the end
+    assert!(uleb(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]).is_err());
+    assert!(uleb(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]).is_err());
+    assert!(uleb(&[0x80, 0x80]).is_err());
+    assert!(uleb(&[]).is_err());
+
+    assert_eq!(sleb(&[0x00]).unwrap(), (0, 1));
+    assert_eq!(sleb(&[0x3f]).unwrap(), (63, 1));
+    assert_eq!(sleb(&[0x7f]).unwrap(), (-1, 1));
+    assert_eq!(sleb(&[0x80, 0x01]).unwrap(), (128, 2));
+    assert_eq!(sleb(&[0x80, 0x7f]).unwrap(), (-128, 2));
+    assert_eq!(sleb(&[0xc0, 0xbb, 0x78]).unwrap(), (-123456, 3));
+    assert_eq!(sleb(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f]).unwrap(), (i64::min_value(), 10));
+    assert_eq!(sleb(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00]).unwrap(), (i64::max_value(), 10));
+    assert!(sleb(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01]).is_err());
+    assert!(sleb(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]).is_err());
+    assert!(sleb(&[0xff]).is_err());
+}
+
+#[test]
+fn segment_protections() {
+    use segment::Protection;
+    use constants::*;
+    let macho = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let protections = macho.segments.iter().map(|segment| format!("{}/{}", segment.initial_protection(), segment.max_protection())).collect::<Vec<_>>();
+    assert_eq!(protections, ["---/---", "r-x/rwx", "rw-/rwx", "r--/rwx"]);
+    let text = macho.segments.find("__TEXT").unwrap();
+    assert_eq!(text.initial_protection(), Protection { read: true, write: false, execute: true });
+    assert!(!text.initial_protection().is_writable_and_executable());
+    assert!(text.max_protection().is_writable_and_executable());
+    assert_eq!(Protection::from(VM_PROT_NONE), Protection::default());
+    assert_eq!(format!("{}", Protection::from(VM_PROT_WRITE | VM_PROT_EXECUTE)), "-wx");
+}
+
+#[test]
+fn flat_sections() {
+    let macho = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let names = macho.segments.flat_sections().map(|section| {
+        let (segment, section, _data) = section.unwrap();
+        assert_eq!(section.segname().unwrap(), segment.name().unwrap());
+        format!("{},{}", segment.name().unwrap(), section.name().unwrap())
+    }).collect::<Vec<_>>();
+    let nested = macho.segments.sections().flat_map(|sections| sections.map(|section| section.unwrap().0)).map(|section| {
+        format!("{},{}", section.segname().unwrap(), section.name().unwrap())
+    }).collect::<Vec<_>>();
+    assert_eq!(names, nested);
+    assert_eq!(names.len(), 8);
+    assert_eq!(names[0], "__TEXT,__text");
+}
+
+#[test]
+fn fixed_size_names() {
+    use load_command::*;
+    let name = |s: &str| {
+        let mut name = [0u8; 16];
+        name[..s.len()].copy_from_slice(s.as_bytes());
+        name
+    };
+    let mut section = Section64 { sectname: name("__text"), segname: name("__TEXT"), addr: 0, size: 0, offset: 0, align: 0, reloff: 0, nreloc: 0, flags: 0, reserved1: 0, reserved2: 0, reserved3: 0 };
+    let short = segment::Section::from(section);
+    assert_eq!(short.name().unwrap(), "__text");
+    assert_eq!(short.segname().unwrap(), "__TEXT");
+    // names which fill all 16 bytes have no terminator
+    section.sectname = name("__objc_classrefs");
+    section.segname = name("__DATA_CONST_XYZ");
+    let full = segment::Section::from(section);
+    assert_eq!(full.name().unwrap(), "__objc_classrefs");
+    assert_eq!(full.segname().unwrap(), "__DATA_CONST_XYZ");
+    assert_eq!(fixed_str(&[0xff, 0xfe, 0]).is_err(), true);
+    assert_eq!(fixed_str(&[0; 16]).unwrap(), "");
+}
+
+#[test]
+fn relocation_iterator_from_both_ends() {
+    use scroll::{LE, Pwrite};
+    use goblin::mach::relocation::RelocationInfo;
+    let ctx = le_64();
+    let mut bytes = vec![0u8; 32];
+    for i in 0..4 {
+        bytes.pwrite_with(RelocationInfo { r_address: i * 8, r_info: 0x0600_0001 }, i as usize * 8, LE).unwrap();
+    }
+    let address = |reloc: goblin::error::Result<relocation::Relocation>| match reloc.unwrap() {
+        relocation::Relocation::Normal(reloc) => reloc.r_address,
+        reloc => panic!("unexpected {:?}", reloc),
+    };
+    let mut relocs = segment::RelocationIterator::new(&bytes, 0, 4, ctx);
+    assert_eq!(relocs.len(), 4);
+    assert_eq!(relocs.next().map(&address), Some(0));
+    assert_eq!(relocs.len(), 3);
+    assert_eq!(relocs.next_back().map(&address), Some(24));
+    assert_eq!(relocs.len(), 2);
+    assert_eq!(relocs.map(&address).collect::<Vec<_>>(), [8, 16]);
+    let reversed = segment::RelocationIterator::new(&bytes, 0, 4, ctx).rev().map(&address).collect::<Vec<_>>();
+    assert_eq!(reversed, [24, 16, 8, 0]);
+    // the front and back meet in the middle
+    let mut relocs = segment::RelocationIterator::new(&bytes, 8, 2, ctx);
+    assert_eq!(relocs.next_back().map(&address), Some(16));
+    assert_eq!(relocs.next().map(&address), Some(8));
+    assert!(relocs.next().is_none());
+    assert!(relocs.next_back().is_none());
+    assert_eq!(relocs.len(), 0);
+    // relocations past the end of the data are errors from either end
+    let mut relocs = segment::RelocationIterator::new(&bytes, 16, 3, ctx);
+    assert!(relocs.next_back().unwrap().is_err());
+    assert!(relocs.next().unwrap().is_ok());
+}
+
+#[test]
+fn command_variant_parse_with_bytes() {
+    use scroll::LE;
+    use load_command::*;
+    let macho = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let dylib = macho.load_commands.iter().find(|cmd| cmd.command.cmd() == LC_LOAD_DYLIB).unwrap();
+    let (command, cmd_bytes) = CommandVariant::parse_with_bytes(&DEADBEEF_MACH_64, dylib.offset, LE).unwrap();
+    assert_eq!(cmd_bytes.len(), command.cmdsize());
+    assert_eq!(cmd_bytes.as_ptr(), DEADBEEF_MACH_64[dylib.offset..].as_ptr());
+    match command {
+        CommandVariant::LoadDylib(command) => assert_eq!(command.name(cmd_bytes).unwrap(), macho.libs[1]),
+        command => panic!("wrong command: {:?}", command),
+    }
+    assert!(CommandVariant::parse_with_bytes(&DEADBEEF_MACH_64, DEADBEEF_MACH_64.len() + 1, LE).is_err());
+    assert!(CommandVariant::parse_with_bytes(&DEADBEEF_MACH_64, DEADBEEF_MACH_64.len() - 4, LE).is_err());
+}
+
+#[test]
+fn rpaths_and_libraries() {
+    use scroll::Pwrite;
+    use load_command::*;
+    use goblin::mach::header::*;
+    let commands: [(u32, &str, usize); 4] = [
+        (LC_RPATH, "@loader_path/../lib", 32),
+        (LC_LOAD_DYLIB, "/usr/lib/libSystem.B.dylib", 56),
+        (LC_LOAD_WEAK_DYLIB, "/usr/lib/libweak.dylib", 48),
+        (LC_RPATH, "@executable_path", 32),
+    ];
+    let commands = commands.iter().map(|&(cmd, path, cmdsize)| {
+        if cmd == LC_RPATH {
+            let mut bytes = command(RpathCommand { cmd: cmd, cmdsize: cmdsize as u32, path: 12 }, cmdsize);
+            bytes.pwrite(path, 12).unwrap();
+            bytes
+        } else {
+            let dylib = Dylib { name: 24, timestamp: 2, current_version: 0x10000, compatibility_version: 0x10000 };
+            let mut bytes = command(DylibCommand { cmd: cmd, cmdsize: cmdsize as u32, dylib: dylib }, cmdsize);
+            bytes.pwrite(path, 24).unwrap();
+            bytes
+        }
+    }).collect::<Vec<_>>();
+    let bytes = macho_64(cputype::CPU_TYPE_X86_64, MH_EXECUTE, &commands);
+    let mach = Mach::parse(&bytes).unwrap();
+    assert_eq!(mach.rpaths(), ["@loader_path/../lib", "@executable_path"]);
+    assert_eq!(mach.libraries(), ["/usr/lib/libSystem.B.dylib", "/usr/lib/libweak.dylib"]);
+    match mach {
+        Mach::Binary(macho) => {
+            // weak dylibs take up a library ordinal too
+            assert_eq!(macho.libs, ["self", "/usr/lib/libSystem.B.dylib", "/usr/lib/libweak.dylib"]);
+            assert_eq!(macho.library_ordinal_name(2), Some("/usr/lib/libweak.dylib"));
+        },
+        _ => panic!("not a binary"),
+    }
+    let macho = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert!(macho.rpaths().is_empty());
+    assert_eq!(macho.libraries(), &macho.libs[1..]);
+}
+
+#[test]
+fn minimum_version() {
+    use load_command::*;
+    use goblin::mach::header::*;
+    let write = |commands: &[CommandVariant]| {
+        let commands = commands.iter().map(|variant| match *variant {
+            CommandVariant::BuildVersion(build) => command(build, variant.cmdsize()),
+            CommandVariant::VersionMinTvos(version) | CommandVariant::VersionMinIphoneos(version) => command(version, variant.cmdsize()),
+            _ => unreachable!(),
+        }).collect::<Vec<_>>();
+        macho_64(cputype::CPU_TYPE_ARM64, MH_EXECUTE, &commands)
+    };
+    let tvos = VersionMinCommand { cmd: LC_VERSION_MIN_TVOS, version: 0x000c0100, ..VersionMinCommand::new(true) };
+    assert_eq!(tvos.platform(), Platform::TvOS);
+    let ios = VersionMinCommand { version: 0x000d0000, ..VersionMinCommand::new(true) };
+    let build = BuildVersionCommand { cmd: LC_BUILD_VERSION, cmdsize: SIZEOF_BUILD_VERSION_COMMAND as u32, platform: PLATFORM_MACCATALYST, minos: 0x000d0102, sdk: 0x000e0000, ntools: 0 };
+
+    let bytes = write(&[CommandVariant::VersionMinTvos(tvos), CommandVariant::VersionMinIphoneos(ios)]);
+    let mach = Mach::parse(&bytes).unwrap();
+    assert_eq!(mach.minimum_version(), Some((Platform::TvOS, (12, 1, 0))));
+
+    // the build version wins, wherever it is
+    let bytes = write(&[CommandVariant::VersionMinIphoneos(ios), CommandVariant::BuildVersion(build)]);
+    let mach = Mach::parse(&bytes).unwrap();
+    assert_eq!(mach.minimum_version(), Some((Platform::MacCatalyst, (13, 1, 2))));
+
+    let bytes = write(&[]);
+    assert_eq!(Mach::parse(&bytes).unwrap().minimum_version(), None);
+}
+
+#[test]
+fn unimplemented_command_round_trip() {
+    use scroll::{Pread, Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; 24];
+    bytes.pwrite_with(LoadCommandHeader { cmd: 0x7f, cmdsize: 24 }, 0, LE).unwrap();
+    for (i, byte) in bytes[SIZEOF_LOAD_COMMAND..].iter_mut().enumerate() {
+        *byte = i as u8 + 1;
+    }
+    let command = bytes.pread_with::<CommandVariant>(0, LE).unwrap();
+    match command {
+        CommandVariant::Unimplemented(header, ref body) => {
+            assert_eq!(header.cmd, 0x7f);
+            assert_eq!(body.len(), 16);
+            assert_eq!(body[15], 16);
+        },
+        ref command => panic!("wrong command: {:?}", command),
+    }
+    assert_eq!(command.cmdsize(), 24);
+    let mut written = vec![0u8; 24];
+    assert_eq!(written.pwrite_with(&command, 0, LE).unwrap(), 24);
+    assert_eq!(written, bytes);
+    // the body has to fit too
+    assert!(written[..16].pwrite_with(&command, 0, LE).is_err());
+}
+
+#[test]
+fn section_debug_with_bad_names() {
+    let mut section = segment::Section::default();
+    section.sectname[..6].copy_from_slice(b"__t\xffxt");
+    section.segname[..6].copy_from_slice(b"__TEXT");
+    let debug = format!("{:?}", section);
+    assert!(debug.contains("sectname: \"__t\u{fffd}xt\""), "{}", debug);
+    assert!(debug.contains("segname: \"__TEXT\""), "{}", debug);
+}
+
+#[test]
+fn segment_debug_with_bad_sections() {
+    use load_command::*;
+    let bytes = [0u8; SIZEOF_SEGMENT_COMMAND_64];
+    let mut segname = [0u8; 16];
+    segname[..7].copy_from_slice(b"__BAD\xc0D");
+    // one section, which doesn't fit after the command
+    let command = SegmentCommand64 { cmd: LC_SEGMENT_64, cmdsize: SIZEOF_SEGMENT_COMMAND_64 as u32, segname: segname, vmaddr: 0, vmsize: 0, fileoff: 0, filesize: 0, maxprot: 0, initprot: 0, nsects: 1, flags: 0 };
+    let segment = segment::Segment::from_64(&bytes, &command, 0, le_64()).unwrap();
+    assert!(segment.sections().is_err());
+    let debug = format!("{:?}", segment);
+    assert!(debug.contains("segname: \"__BAD\u{fffd}D\""), "{}", debug);
+    assert!(debug.contains("sections: Err("), "{}", debug);
+}
+
+#[test]
+fn segment_section_by_name() {
+    use load_command::*;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let text = binary.segments.find("__TEXT").unwrap();
+    let sections = text.sections().unwrap();
+    let &(ref last, last_data) = sections.last().unwrap();
+    let (section, data) = text.section_by_name(last.name().unwrap()).unwrap().unwrap();
+    assert_eq!(section.addr, last.addr);
+    assert_eq!(data, last_data);
+    assert!(text.section_by_name("__tex").unwrap().is_none());
+    assert!(text.section_by_name("__text\0").unwrap().is_none());
+    // the first section is found even when a later one is out of bounds
+    let mut bytes = vec![0u8; SIZEOF_SEGMENT_COMMAND_64 + SIZEOF_SECTION_64];
+    bytes[SIZEOF_SEGMENT_COMMAND_64..SIZEOF_SEGMENT_COMMAND_64 + 6].copy_from_slice(b"__text");
+    let command = SegmentCommand64 { cmd: LC_SEGMENT_64, cmdsize: bytes.len() as u32, segname: [0; 16], vmaddr: 0, vmsize: 0, fileoff: 0, filesize: 0, maxprot: 0, initprot: 0, nsects: 2, flags: 0 };
+    let segment = segment::Segment::from_64(&bytes, &command, 0, le_64()).unwrap();
+    assert!(segment.section_by_name("__text").unwrap().is_some());
+    assert!(segment.section_by_name("__data").is_err());
+}
+
+#[test]
+fn segments_with_ctx() {
+    use goblin::container::{Container, Ctx};
+    let ctx = Ctx::new(Container::Little, scroll::BE);
+    let segments = segment::Segments::with_ctx(ctx);
+    assert!(segments.is_empty());
+    assert_eq!(segments.ctx(), ctx);
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert_eq!(binary.segments.ctx(), le_64());
+}
+
+#[test]
+fn vm_size_and_file_size_covered() {
+    use scroll::{BE, Pwrite};
+    use goblin::mach::fat::*;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let pagezero = binary.segments.find("__PAGEZERO").unwrap();
+    let total = binary.segments.iter().map(|segment| segment.vmsize).sum::<u64>();
+    assert_eq!(binary.vm_size(), total - pagezero.vmsize);
+    let linkedit = binary.segments.find("__LINKEDIT").unwrap();
+    assert_eq!(binary.file_size_covered(), linkedit.fileoff + linkedit.filesize);
+    assert!(binary.file_size_covered() <= DEADBEEF_MACH_64.len() as u64);
+
+    // a fat binary with that binary, and an architecture past the end of the file
+    let offset = 0x1000;
+    let mut bytes = vec![0u8; offset + DEADBEEF_MACH_64.len()];
+    bytes.pwrite_with(FatHeader { magic: FAT_MAGIC, nfat_arch: 2 }, 0, BE).unwrap();
+    let arch = FatArch { cputype: cputype::CPU_TYPE_X86_64, cpusubtype: 3, offset: offset as u32, size: DEADBEEF_MACH_64.len() as u32, align: 12 };
+    bytes.pwrite_with(arch, SIZEOF_FAT_HEADER, BE).unwrap();
+    let arch = FatArch { cputype: cputype::CPU_TYPE_ARM64, offset: 0x10_0000, size: 0x100, ..arch };
+    bytes.pwrite_with(arch, SIZEOF_FAT_HEADER + SIZEOF_FAT_ARCH, BE).unwrap();
+    bytes[offset..].copy_from_slice(&DEADBEEF_MACH_64[..]);
+    let mach = Mach::parse(&bytes).unwrap();
+    // the container has no segments of its own, but each architecture does
+    assert_eq!(mach.vm_size(), 0);
+    assert_eq!(mach.file_size_covered(), 0);
+    assert_eq!(mach.overlay(bytes.len()), None);
+    assert_eq!(mach.file_type(), None);
+    assert!(!mach.is_executable());
+    match mach {
+        Mach::Fat(multi) => assert_eq!(multi.get(0).unwrap().vm_size(), binary.vm_size()),
+        _ => panic!("not a fat binary"),
+    }
+}
+
+#[test]
+fn page_zero() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let pagezero = binary.segments.page_zero().unwrap();
+    assert_eq!(pagezero.name().unwrap(), "__PAGEZERO");
+    assert_eq!(pagezero.vmaddr, 0);
+    assert_eq!(pagezero.filesize, 0);
+    assert_eq!(binary.page_zero_size(), Some(pagezero.vmsize));
+    assert_eq!(Mach::Binary(binary).page_zero_size(), Some(0x1_0000_0000));
+    let segments = segment::Segments::with_ctx(goblin::container::Ctx::default());
+    assert!(segments.page_zero().is_none());
+}
+
+#[test]
+fn load_command_iterator() {
+    use scroll::{Pwrite, LE};
+    use load_command::*;
+    let mut bytes = vec![0u8; 4 + 2 * SIZEOF_UUID_COMMAND + 4];
+    bytes.pwrite_with(LoadCommandHeader { cmd: LC_UUID, cmdsize: SIZEOF_UUID_COMMAND as u32 }, 4, LE).unwrap();
+    bytes.pwrite_with(LoadCommandHeader { cmd: LC_UUID, cmdsize: SIZEOF_UUID_COMMAND as u32 }, 4 + SIZEOF_UUID_COMMAND, LE).unwrap();
+    let commands = LoadCommandIterator::new(&bytes[..4 + 2 * SIZEOF_UUID_COMMAND], 4, 2, LE).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(commands.iter().map(|command| command.offset).collect::<Vec<_>>(), [4, 4 + SIZEOF_UUID_COMMAND]);
+    // stops after ncmds, or at the end of the region
+    assert_eq!(LoadCommandIterator::new(&bytes, 4, 1, LE).count(), 1);
+    assert_eq!(LoadCommandIterator::new(&bytes[..4 + 2 * SIZEOF_UUID_COMMAND], 4, 5, LE).count(), 2);
+    // a partial header at the end of the region is an error, and the last thing yielded
+    let results = LoadCommandIterator::new(&bytes, 4, 5, LE).collect::<Vec<_>>();
+    assert_eq!(results.len(), 3);
+    assert!(results[2].is_err());
+    // a zero cmdsize doesn't loop forever
+    bytes.pwrite_with(LoadCommandHeader { cmd: LC_UUID, cmdsize: 0 }, 4, LE).unwrap();
+    let results = LoadCommandIterator::new(&bytes, 4, 1000, LE).collect::<Vec<_>>();
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+    // a command running past the region
+    bytes.pwrite_with(LoadCommandHeader { cmd: LC_UUID, cmdsize: SIZEOF_UUID_COMMAND as u32 }, 4 + SIZEOF_UUID_COMMAND - 8, LE).unwrap();
+    let results = LoadCommandIterator::new(&bytes[..4 + SIZEOF_UUID_COMMAND], 4 + SIZEOF_UUID_COMMAND - 8, 1, LE).collect::<Vec<_>>();
+    assert!(results[0].is_err());
+}
+
+#[test]
+fn linkedit_range() {
+    use load_command::CommandVariant;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let range = binary.linkedit_range().unwrap();
+    assert_eq!(range.end, binary.file_size_covered());
+    // the symbol and string tables are within it
+    for command in &binary.load_commands {
+        if let CommandVariant::Symtab(symtab) = command.command {
+            assert!(range.start <= symtab.symoff as u64);
+            assert!(symtab.stroff as u64 + symtab.strsize as u64 <= range.end);
+        }
+    }
+    assert_eq!(Mach::Binary(binary).linkedit_range(), Some(range));
+}
+
+#[test]
+fn objc_image_info() {
+    use goblin::mach::objc::*;
+    // a Swift 5 image, whose image info has both the unstable and stable ABI versions
+    let bytes = [0, 0, 0, 0, 0x40, 0x07, 0x05, 0x01];
+    let info = ImageInfo::parse(&bytes, scroll::LE).unwrap();
+    assert_eq!(info.version, 0);
+    assert!(info.is_objc2());
+    assert!(info.has_flag(OBJC_IMAGE_HAS_CATEGORY_CLASS_PROPERTIES));
+    assert!(!info.supports_gc());
+    assert_eq!(info.swift_version(), Some(7));
+    assert_eq!(info.swift_stable_version(), 0x0105);
+    let info = ImageInfo::parse_legacy(&[0, 0, 0, 0, 0, 0, 0, 0x02], scroll::BE).unwrap();
+    assert!(!info.is_objc2());
+    assert!(info.supports_gc());
+    assert_eq!(info.swift_version(), None);
+    assert!(ImageInfo::parse(&bytes[..7], scroll::LE).is_err());
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert_eq!(binary.objc_image_info().unwrap(), None);
+    // a binary with that image info in its __DATA_CONST,__objc_imageinfo, or in a legacy __OBJC,__image_info
+    let swift5 = bytes;
+    let with_section = |segname: &str, sectname: &str| {
+        let offset = header::SIZEOF_HEADER_64 + load_command::SIZEOF_SEGMENT_COMMAND_64 + load_command::SIZEOF_SECTION_64;
+        let segment = segment_64(segname, 0x1_0000_4000, 0x1000, offset as u64, 8, &[(sectname, 0x1_0000_4000, 8, offset as u32, 0)]);
+        let mut bytes = macho_64(cputype::CPU_TYPE_ARM64, header::MH_EXECUTE, &[segment]);
+        bytes.extend_from_slice(&swift5);
+        bytes
+    };
+    let bytes = with_section("__DATA_CONST", SECT_OBJC_IMAGEINFO);
+    let info = MachO::parse(&bytes, 0).unwrap().objc_image_info().unwrap().unwrap();
+    assert!(info.is_objc2());
+    assert_eq!(info.swift_stable_version(), 0x0105);
+    let bytes = with_section(SEG_OBJC, SECT_IMAGE_INFO);
+    let info = MachO::parse(&bytes, 0).unwrap().objc_image_info().unwrap().unwrap();
+    assert!(!info.is_objc2());
+    assert_eq!(info.swift_version(), Some(7));
+    // an __image_info outside of __OBJC is something else
+    let bytes = with_section("__DATA", SECT_IMAGE_INFO);
+    assert_eq!(MachO::parse(&bytes, 0).unwrap().objc_image_info().unwrap(), None);
+}
+
+#[test]
+fn raw_code_signature() {
+    use load_command::*;
+    use goblin::mach::header::*;
+    let binary = |dataoff: u32, datasize: u32| {
+        let command = command(LinkeditDataCommand { cmd: LC_CODE_SIGNATURE, cmdsize: SIZEOF_LINKEDIT_DATA_COMMAND as u32, dataoff: dataoff, datasize: datasize }, SIZEOF_LINKEDIT_DATA_COMMAND);
+        let mut bytes = macho_64(cputype::CPU_TYPE_ARM64, MH_EXECUTE, &[command]);
+        bytes.extend_from_slice(b"\xfa\xde\x0c\xc0sig!");
+        bytes
+    };
+    let end = (SIZEOF_HEADER_64 + SIZEOF_LINKEDIT_DATA_COMMAND) as u32;
+    let bytes = binary(end, 8);
+    let mach = Mach::parse(&bytes).unwrap();
+    assert_eq!(mach.code_signature().unwrap(), Some(&b"\xfa\xde\x0c\xc0sig!"[..]));
+    let bytes = binary(end, 9);
+    assert!(Mach::parse(&bytes).unwrap().code_signature().is_err());
+    let bytes = binary(u32::max_value(), u32::max_value());
+    assert!(Mach::parse(&bytes).unwrap().code_signature().is_err());
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert_eq!(binary.code_signature().unwrap(), None);
+}
+
+#[test]
+fn overlay() {
+    let mut bytes = DEADBEEF_MACH_64.to_vec();
+    let covered = MachO::parse(&bytes, 0).unwrap().file_size_covered() as usize;
+    assert_eq!(MachO::parse(&bytes, 0).unwrap().overlay(covered), None);
+    bytes.resize(covered, 0);
+    bytes.extend_from_slice(b"PK\x03\x04appended");
+    let mach = Mach::parse(&bytes).unwrap();
+    let overlay = mach.overlay(bytes.len()).unwrap();
+    assert_eq!(overlay, covered..covered + 12);
+    assert_eq!(&bytes[overlay][..4], b"PK\x03\x04");
+    // a total length short of the segments isn't an overlay
+    assert_eq!(mach.overlay(covered - 1), None);
+}
+
+#[test]
+fn alignment() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    for segment in &*binary.segments {
+        assert!(segment.is_page_aligned(0x1000));
+        for section in segment.sections().unwrap() {
+            assert!(section.0.is_addr_aligned());
+        }
+    }
+    assert!(!binary.segments.find("__TEXT").unwrap().is_page_aligned(0));
+    let mut section = segment::Section { addr: 0x1004, align: 2, ..Default::default() };
+    assert_eq!(section.alignment(), 4);
+    assert!(section.is_addr_aligned());
+    section.align = 4;
+    assert_eq!(section.alignment(), 16);
+    assert!(!section.is_addr_aligned());
+    section.align = 64;
+    assert_eq!(section.alignment(), 0);
+    assert!(!section.is_addr_aligned());
+}
+
+#[test]
+fn symtab_builder() {
+    use goblin::mach::symbols::*;
+    use goblin::container::{Container, Ctx};
+    // the builder overwrites n_strx
+    let nlist = |n_type, n_sect, n_value| Nlist { n_strx: 0xdead, ..nlist(n_type, n_sect, 0, n_value) };
+    let mut builder = SymtabBuilder::new();
+    builder.add("_main", nlist(N_SECT | N_EXT, 1, 0x1_0000_0f50))
+        .add("_helper", nlist(N_SECT, 1, 0x1_0000_0f80))
+        .add("_main", nlist(N_SECT | N_EXT, 1, 0x1_0000_0f50))
+        .add("", nlist(N_UNDF, 0, 0));
+    assert_eq!(builder.len(), 4);
+    let ctx = le_64();
+    let symoff = 0x20;
+    let built = builder.build(symoff, ctx).unwrap();
+    assert_eq!(built.command.symoff, 0x20);
+    assert_eq!(built.command.nsyms, 4);
+    assert_eq!(built.command.stroff as usize, symoff + 4 * SIZEOF_NLIST_64);
+    // "\0_main\0_helper\0", padded to 8 bytes
+    assert_eq!(built.command.strsize, 16);
+    assert_eq!(built.data.len(), 4 * SIZEOF_NLIST_64 + 16);
+    let mut bytes = vec![0xffu8; symoff];
+    bytes.extend_from_slice(&built.data);
+    let symbols = Symbols::parse(&bytes, &built.command, ctx).unwrap();
+    let symbols = symbols.iter().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(symbols.iter().map(|&(name, _)| name).collect::<Vec<_>>(), ["_main", "_helper", "_main", ""]);
+    assert_eq!(symbols[0].1.n_strx, 1);
+    assert_eq!(symbols[2].1.n_strx, 1);
+    assert_eq!(symbols[3].1.n_strx, 0);
+    assert_eq!(symbols[1].1.n_value, 0x1_0000_0f80);
+    // values and section ordinals have to fit
+    assert!(builder.build(symoff, Ctx::new(Container::Little, scroll::LE)).is_err());
+    let mut builder = SymtabBuilder::new();
+    builder.add("_x", nlist(N_SECT, 256, 0));
+    assert!(builder.build(0, ctx).is_err());
+}
+
+#[test]
+fn entry_point() {
+    // its LC_MAIN's entryoff of 0xf40 is the start of __text
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert_eq!(binary.entry_point(), Some(0x1_0000_0f40));
+    assert_eq!(Mach::Binary(binary).entry_point(), Some(0x1_0000_0f40));
+    // a dylib has neither LC_MAIN nor LC_UNIXTHREAD
+    let bytes = macho_64(cputype::CPU_TYPE_X86_64, header::MH_DYLIB, &[]);
+    assert_eq!(MachO::parse(&bytes, 0).unwrap().entry_point(), None);
+}
+
+#[test]
+fn section_at_offset() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let mut found = 0;
+    for section in binary.segments.flat_sections() {
+        let (segment, section, _) = section.unwrap();
+        let at = |off| binary.segments.section_at_offset(off).map(|(segment, section)| (segment.name().unwrap().to_string(), section.name().unwrap().to_string()));
+        let expected = Some((segment.name().unwrap().to_string(), section.name().unwrap().to_string()));
+        if section.is_zerofill() {
+            assert!(at(section.offset as u64) != expected);
+        } else if section.size > 0 {
+            assert_eq!(at(section.offset as u64), expected);
+            assert_eq!(at(section.offset as u64 + section.size - 1), expected);
+            found += 1;
+        }
+    }
+    assert!(found > 0);
+    // the header and load commands aren't in any section
+    assert!(binary.segments.section_at_offset(0).is_none());
+    assert!(binary.segments.section_at_offset(u64::MAX).is_none());
+}
+
+#[test]
+fn data_in_code_kind() {
+    use load_command::*;
+    let entry = |kind| DataInCodeEntry { offset: 0x1000, length: 16, kind: kind };
+    assert_eq!(entry(DICE_KIND_DATA).kind_enum(), DiceKind::Data);
+    assert_eq!(entry(DICE_KIND_DATA).kind_enum().element_size(), None);
+    assert_eq!(entry(DICE_KIND_JUMP_TABLE8).kind_enum().element_size(), Some(1));
+    assert_eq!(entry(DICE_KIND_JUMP_TABLE16).kind_enum().element_size(), Some(2));
+    assert_eq!(entry(DICE_KIND_JUMP_TABLE32).kind_enum(), DiceKind::JumpTable32);
+    assert_eq!(entry(DICE_KIND_ABS_JUMP_TABLE32).kind_enum().element_size(), Some(4));
+    assert_eq!(entry(DICE_KIND_ABS_JUMP_TABLE32).kind_enum().to_str(), "DICE_KIND_ABS_JUMP_TABLE32");
+    assert_eq!(entry(0x42).kind_enum(), DiceKind::Unknown(0x42));
+    assert_eq!(entry(0x42).kind_enum().element_size(), None);
+}
+
+#[test]
+fn symbols_bounded_by_strtab() {
+    use scroll::{Pwrite, LE};
+    use goblin::mach::symbols::*;
+    let ctx = le_64();
+    let mut builder = SymtabBuilder::new();
+    builder.add("_a", nlist(N_SECT | N_EXT, 1, 0, 0x1000)).add("_b", nlist(N_SECT | N_EXT, 1, 0, 0x1000)).add("", nlist(N_SECT | N_EXT, 1, 0, 0x1000));
+    let built = builder.build(0, ctx).unwrap();
+    // trailing bytes after the string table, which names must not run into
+    let mut bytes = built.data.clone();
+    bytes.extend_from_slice(b"_junk\0");
+    let names = |bytes: &[u8]| Symbols::parse(bytes, &built.command, ctx).unwrap().iter().map(|symbol| symbol.map(|(name, _)| name.to_string())).collect::<Vec<_>>();
+    assert_eq!(names(&bytes).into_iter().collect::<Result<Vec<_>, _>>().unwrap(), ["_a", "_b", ""]);
+    // an n_strx at or past strsize is an error rather than a read of whatever follows the table
+    bytes.pwrite_with(built.command.strsize, SIZEOF_NLIST_64, LE).unwrap();
+    let results = names(&bytes);
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+    let symbols = Symbols::parse(&bytes, &built.command, ctx).unwrap();
+    assert!(symbols.get(1).is_err());
+    assert_eq!(symbols.get(0).unwrap().0, "_a");
+}
+
+#[test]
+fn lazy_parsing() {
+    use std::io::Cursor;
+    use scroll::{Pwrite, BE};
+    use goblin::mach::fat::*;
+    use goblin::mach::lazy::*;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let lazy = LazyMachO::parse(Cursor::new(&DEADBEEF_MACH_64[..]), 0).unwrap();
+    assert_eq!(lazy.header.ncmds, binary.header.ncmds);
+    assert!(lazy.ctx().container.is_big());
+    let commands = lazy.load_commands().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(commands.len(), binary.load_commands.len());
+    assert_eq!(commands.iter().map(|cmd| cmd.offset).collect::<Vec<_>>(), binary.load_commands.iter().map(|cmd| cmd.offset).collect::<Vec<_>>());
+    // a fat container with the binary as its only architecture, after some padding
+    let offset = 0x1000;
+    let mut bytes = vec![0u8; offset];
+    bytes.pwrite_with(FatHeader { magic: FAT_MAGIC, nfat_arch: 1 }, 0, BE).unwrap();
+    let arch = FatArch { cputype: binary.header.cputype, cpusubtype: 3, offset: offset as u32, size: DEADBEEF_MACH_64.len() as u32, align: 12 };
+    bytes.pwrite_with(arch, SIZEOF_FAT_HEADER, BE).unwrap();
+    bytes.extend_from_slice(&DEADBEEF_MACH_64[..]);
+    // and some data after it, which isn't part of it
+    bytes.extend_from_slice(&[0xaa; 0x10]);
+    let mut multi = LazyMultiArch::new(Cursor::new(&bytes[..])).unwrap();
+    assert_eq!(multi.arches().len(), 1);
+    assert_eq!(multi.read_arch(0).unwrap(), &DEADBEEF_MACH_64[..]);
+    assert!(multi.read_arch(1).is_err());
+    {
+        let mut macho = multi.macho(0).unwrap();
+        assert_eq!(macho.load_commands().count(), binary.load_commands.len());
+        // offsets are relative to the start of the binary, not the container
+        assert_eq!(macho.read_at(0, 4).unwrap(), &DEADBEEF_MACH_64[..4]);
+        assert!(macho.read_at(DEADBEEF_MACH_64.len() as u64, 1).is_err());
+        assert!(macho.read_at(DEADBEEF_MACH_64.len() as u64 - 4, 8).is_err());
+    }
+    // but reading the binary on its own goes to the end of the data
+    let mut macho = LazyMachO::parse(Cursor::new(&bytes[..]), offset as u64).unwrap();
+    assert_eq!(macho.read_at(DEADBEEF_MACH_64.len() as u64, 0x10).unwrap(), &[0xaa; 0x10][..]);
+    // sizes past the end of the data are errors rather than huge allocations
+    bytes.pwrite_with(FatHeader { magic: FAT_MAGIC, nfat_arch: 0xffff_ffff }, 0, BE).unwrap();
+    assert!(LazyMultiArch::new(Cursor::new(&bytes[..])).is_err());
+    assert!(LazyMachO::parse(Cursor::new(&bytes[..]), 0).is_err());
+}
+
+#[test]
+fn ctx_from_mach_magic() {
+    use goblin::container::{Container, Ctx};
+    use goblin::mach::header::*;
+    use scroll::{BE, LE};
+    assert_eq!(Ctx::from_mach_magic(MH_MAGIC), Some(Ctx::new(Container::Little, BE)));
+    assert_eq!(Ctx::from_mach_magic(MH_CIGAM), Some(Ctx::new(Container::Little, LE)));
+    assert_eq!(Ctx::from_mach_magic(MH_MAGIC_64), Some(Ctx::new(Container::Big, BE)));
+    assert_eq!(Ctx::from_mach_magic(MH_CIGAM_64), Some(Ctx::new(Container::Big, LE)));
+    assert_eq!(Ctx::from_mach_magic(goblin::mach::fat::FAT_MAGIC), None);
+    // agrees with the context of a parsed header
+    let magic = goblin::mach::peek(&DEADBEEF_MACH_64, 0).unwrap();
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    assert_eq!(Ctx::from_mach_magic(magic), Some(binary.header.ctx().unwrap()));
+}
+
+#[test]
+fn peek_kind() {
+    use goblin::mach::{peek_kind, MachKind};
+    use goblin::mach::fat::*;
+    use goblin::mach::header::*;
+    use scroll::{Pwrite, BE};
+    assert_eq!(peek_kind(&DEADBEEF_MACH_64), Some(MachKind::Thin64));
+    let kind = |magic: u32| {
+        let mut bytes = [0u8; 4];
+        bytes.pwrite_with(magic, 0, BE).unwrap();
+        peek_kind(&bytes)
+    };
+    assert_eq!(kind(MH_MAGIC), Some(MachKind::Thin32));
+    assert_eq!(kind(MH_CIGAM), Some(MachKind::Thin32));
+    assert_eq!(kind(MH_CIGAM_64), Some(MachKind::Thin64));
+    assert_eq!(kind(FAT_MAGIC), Some(MachKind::Fat32));
+    assert_eq!(kind(FAT_MAGIC_64), Some(MachKind::Fat64));
+    assert_eq!(kind(0x7f454c46), None);
+    assert_eq!(peek_kind(&[0xca, 0xfe]), None);
+}
+
+#[test]
+fn fat_magic_64() {
+    use std::io::Cursor;
+    use scroll::{Pwrite, BE};
+    use goblin::mach::fat::*;
+    use goblin::mach::lazy::LazyMultiArch;
+    let offset = 0x1000;
+    let mut bytes = vec![0u8; offset];
+    bytes.pwrite_with(FatHeader { magic: FAT_MAGIC_64, nfat_arch: 2 }, 0, BE).unwrap();
+    let arch = FatArch64 { cputype: cputype::CPU_TYPE_X86_64, cpusubtype: 3, offset: offset as u64, size: DEADBEEF_MACH_64.len() as u64, align: 12, reserved: 0 };
+    bytes.pwrite_with(arch, SIZEOF_FAT_HEADER, BE).unwrap();
+    // a slice more than 4GiB into the file
+    let far = FatArch64 { cputype: cputype::CPU_TYPE_ARM64, offset: 0x1_0000_4000, size: 0x4000, ..arch };
+    bytes.pwrite_with(far, SIZEOF_FAT_HEADER + SIZEOF_FAT_ARCH_64, BE).unwrap();
+    bytes.extend_from_slice(&DEADBEEF_MACH_64[..]);
+    let multi = match Mach::parse(&bytes).unwrap() {
+        Mach::Fat(multi) => multi,
+        Mach::Binary(_) => panic!("FAT_MAGIC_64 binary parsed as a thin binary"),
+    };
+    assert!(multi.is_64());
+    assert_eq!(multi.narches, 2);
+    let arches = multi.iter_arches_64().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(arches[0].offset, offset as u64);
+    assert_eq!(arches[1].offset, 0x1_0000_4000);
+    assert_eq!(arches[1].cputype, cputype::CPU_TYPE_ARM64);
+    // the far slice doesn't fit in a 32-bit FatArch, or in the data
+    let narrow = multi.iter_arches().collect::<Vec<_>>();
+    assert_eq!(narrow[0].as_ref().unwrap().size as usize, DEADBEEF_MACH_64.len());
+    assert!(narrow[1].is_err());
+    assert_eq!(multi.get(0).unwrap().header.ncmds, MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap().header.ncmds);
+    assert!(multi.get(1).is_err());
+    assert_eq!(multi.find_cputype(cputype::CPU_TYPE_X86_64, None).unwrap().map(|arch| arch.offset as usize), Some(offset));
+    // the lazy reader picks the same width
+    let mut lazy = LazyMultiArch::new(Cursor::new(&bytes[..])).unwrap();
+    assert_eq!(lazy.arches()[1].offset, 0x1_0000_4000);
+    assert_eq!(lazy.read_arch(0).unwrap(), &DEADBEEF_MACH_64[..]);
+    assert!(lazy.read_arch(1).is_err());
+    // 32-bit headers widen losslessly
+    let narrow = FatArch { cputype: 7, cpusubtype: 3, offset: 0x1000, size: 0x2000, align: 12 };
+    assert_eq!(FatArch64::from(narrow).to_fat_arch().unwrap().size, 0x2000);
+    match goblin::peek_bytes(&{ let mut hint = [0u8; 16]; hint.copy_from_slice(&bytes[..16]); hint }).unwrap() {
+        goblin::Hint::MachFat(narches) => assert_eq!(narches, 2),
+        hint => panic!("wrong hint: {:?}", hint),
+    }
+}
+
+#[test]
+fn segment_flags() {
+    use goblin::mach::constants::*;
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    for segment in &*binary.segments {
+        assert!(!segment.is_protected());
+        assert!(!segment.is_read_only());
+    }
+    let mut segment = segment::Segment::new(goblin::container::Ctx::default(), &[]);
+    segment.flags = SG_PROTECTED_VERSION_1 | SG_NORELOC;
+    assert!(segment.is_protected());
+    assert!(!segment.is_read_only());
+    assert!(segment.has_flag(SG_NORELOC));
+    assert!(!segment.has_flag(SG_NORELOC | SG_HIGHVM));
+    segment.flags = SG_READ_ONLY;
+    assert!(segment.is_read_only());
+    assert!(!segment.is_protected());
+}
+
+#[test]
+fn dump_load_commands() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let dump = binary.dump_load_commands();
+    assert_eq!(dump, MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap().dump_load_commands());
+    assert_eq!(dump.lines().filter(|line| line.starts_with("Load command ")).count(), binary.load_commands.len());
+    assert!(dump.starts_with("Load command 0\n      cmd LC_SEGMENT_64\n  cmdsize 72\n  segname __PAGEZERO\n"));
+    assert!(dump.contains("Section\n  sectname __text\n   segname __TEXT\n      addr 0x0000000100000f40\n      size 0x0000000000000034\n    offset 3904\n     align 2^4\n"));
+    assert!(dump.contains("      cmd LC_LOAD_DYLINKER\n  cmdsize 32\n     name /usr/lib/dyld (offset 12)\n"));
+    assert!(dump.contains("     name /usr/lib/libSystem.B.dylib (offset 24)\ntime stamp 2\ncurrent version 1213.0.0\ncompatibility version 1.0.0\n"));
+    assert!(dump.contains("     uuid 7ED20495-3323-3BF4-B68F-BF5D8C237BA8\n"));
+    assert!(dump.contains("      cmd LC_MAIN\n  cmdsize 24\n entryoff 3904\nstacksize 0\n"));
+    assert_eq!(Mach::Binary(binary).dump_load_commands(), dump);
+    // a fat binary has no load commands of its own
+    use scroll::{Pwrite, BE};
+    use goblin::mach::fat::*;
+    let mut bytes = vec![0u8; 0x1000];
+    bytes.pwrite_with(FatHeader { magic: FAT_MAGIC, nfat_arch: 1 }, 0, BE).unwrap();
+    let arch = FatArch { cputype: cputype::CPU_TYPE_X86_64, cpusubtype: 3, offset: 0x1000, size: DEADBEEF_MACH_64.len() as u32, align: 12 };
+    bytes.pwrite_with(arch, SIZEOF_FAT_HEADER, BE).unwrap();
+    bytes.extend_from_slice(&DEADBEEF_MACH_64[..]);
+    let mach = Mach::parse(&bytes).unwrap();
+    assert_eq!(mach.dump_load_commands(), "");
+    match mach {
+        Mach::Fat(multi) => assert_eq!(multi.get(0).unwrap().dump_load_commands(), dump),
+        _ => panic!("not a fat binary"),
+    }
+}
+
+#[test]
+fn dump_routines_and_unknown_commands() {
+    use load_command::*;
+    let routines = RoutinesCommand64 { cmd: LC_ROUTINES_64, cmdsize: SIZEOF_ROUTINES_COMMAND_64 as u32, init_address: 0x1_0000_1f40, init_module: 5, reserved1: 0, reserved2: 0, reserved3: 0, reserved4: 0, reserved5: 0, reserved6: 7 };
+    let mut unknown = command(LoadCommandHeader { cmd: 0x7f, cmdsize: 12 }, 12);
+    unknown[8..].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+    let bytes = macho_64(cputype::CPU_TYPE_X86_64, header::MH_DYLIB, &[command(routines, SIZEOF_ROUTINES_COMMAND_64), unknown]);
+    let dump = MachO::parse(&bytes, 0).unwrap().dump_load_commands();
+    assert!(dump.contains("      cmd LC_ROUTINES_64\n  cmdsize 72\ninit_address 0x0000000100001f40\ninit_module 5\nreserved1 0\n"), "{}", dump);
+    assert!(dump.contains("reserved6 7\n"), "{}", dump);
+    assert!(dump.ends_with("      cmd LC_UNKNOWN (0x7f)\n  cmdsize 12\n     data de ad be ef\n"), "{}", dump);
+}
+
+#[test]
+fn macho_is_encrypted() {
+    use load_command::*;
+    assert!(!MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap().is_encrypted());
+    let binary = |cryptid| {
+        let command = command(EncryptionInfoCommand64 { cmd: LC_ENCRYPTION_INFO_64, cmdsize: SIZEOF_ENCRYPTION_INFO_COMMAND_64 as u32, cryptoff: 0x4000, cryptsize: 0x8000, cryptid: cryptid, pad: 0 }, SIZEOF_ENCRYPTION_INFO_COMMAND_64);
+        macho_64(cputype::CPU_TYPE_ARM64, header::MH_EXECUTE, &[command])
+    };
+    let encrypted = binary(1);
+    assert!(MachO::parse(&encrypted, 0).unwrap().is_encrypted());
+    assert!(Mach::parse(&encrypted).unwrap().is_encrypted());
+    let decrypted = binary(0);
+    assert!(!Mach::parse(&decrypted).unwrap().is_encrypted());
+}
+
+#[test]
+fn init_pointers() {
+    use scroll::{Pwrite, LE};
+    use goblin::container::{Container, Ctx};
+    use goblin::mach::constants::*;
+    let mut data = [0u8; 16];
+    data.pwrite_with(0x1_0000_0f40u64, 0, LE).unwrap();
+    data.pwrite_with(0x1_0000_0f80u64, 8, LE).unwrap();
+    let section = segment::Section { size: 16, flags: S_MOD_INIT_FUNC_POINTERS, ..Default::default() };
+    assert_eq!(section.init_pointers(&data, le_64()).unwrap(), [0x1_0000_0f40, 0x1_0000_0f80]);
+    let section = segment::Section { size: 16, flags: S_MOD_TERM_FUNC_POINTERS, ..Default::default() };
+    assert_eq!(section.init_pointers(&data, Ctx::new(Container::Little, LE)).unwrap(), [0x0f40, 0x1, 0x0f80, 0x1]);
+    assert!(section.init_pointers(&data[..12], le_64()).is_err());
+    assert!(section.init_pointers(&[], le_64()).unwrap().is_empty());
+    // other sections aren't pointer arrays
+    let section = segment::Section { size: 16, flags: S_REGULAR, ..Default::default() };
+    assert!(section.init_pointers(&data, le_64()).is_err());
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let (section, data) = binary.segments.find_section("__TEXT", "__text").unwrap().unwrap();
+    assert!(section.init_pointers(data, binary.segments.ctx()).is_err());
+}
+
+#[test]
+fn cstrings() {
+    let binary = MachO::parse(&DEADBEEF_MACH_64[..], 0).unwrap();
+    let (section, data) = binary.segments.find_section("__TEXT", "__cstring").unwrap().unwrap();
+    // the printf format string of the test program
+    assert_eq!(section.cstrings(data).collect::<Vec<_>>(), [(0x1_0000_0f96, "0x%x\n")]);
+    // padding is skipped, as is invalid UTF-8, without ending the iteration; an unterminated last string is kept
+    let section = segment::Section { addr: 0x1000, flags: constants::S_CSTRING_LITERALS, ..Default::default() };
+    let data = b"hello\0\0\0\xff\xfe\0world\0tail";
+    assert_eq!(section.cstrings(data).collect::<Vec<_>>(), [(0x1000, "hello"), (0x100b, "world"), (0x1011, "tail")]);
+    assert_eq!(section.cstrings(&[]).count(), 0);
+    // any other type of section has no strings
+    let section = segment::Section { flags: constants::S_REGULAR, ..section };
+    assert_eq!(section.cstrings(data).count(), 0);
+}